@@ -1,5 +1,9 @@
-use cosmwasm_schema::write_api;
+use std::env::current_dir;
+
+use cosmwasm_schema::{export_schema, schema_for, write_api};
+use dao_bets::dao_bets::{BetReceipt, ClaimReceipt};
 use dao_bets_game::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
+use general::ibc::IbcHooksSudoMsg;
 
 fn main() {
     write_api! {
@@ -7,5 +11,14 @@ fn main() {
         execute: ExecuteMsg,
         query: QueryMsg,
         migrate: MigrateMsg,
+        sudo: IbcHooksSudoMsg,
     }
+
+    // Data set on the responses of bets and claims
+    let mut out_dir = current_dir().unwrap();
+    out_dir.push("schema");
+    out_dir.push("receipts");
+    std::fs::create_dir_all(&out_dir).unwrap();
+    export_schema(&schema_for!(BetReceipt), &out_dir);
+    export_schema(&schema_for!(ClaimReceipt), &out_dir);
 }
@@ -1,21 +1,42 @@
+use cosmwasm_schema::cw_serde;
 use cosmwasm_std::{
-    coins, entry_point, to_json_binary, Addr, BankMsg, Binary, Decimal, Deps, DepsMut, Env,
-    MessageInfo, Order, Response, StdError, StdResult, Uint128, WasmMsg,
+    entry_point, from_json, to_json_binary, Addr, Attribute, Binary, Decimal, Deps, DepsMut, Empty,
+    Env, MessageInfo, Order, Reply, Response, StdError, StdResult, Storage, Uint128, WasmMsg,
 };
-use cw_ownable::{assert_owner, get_ownership, initialize_owner};
+use cw20::{Cw20ReceiveMsg, Denom};
+use cw_ownable::{get_ownership, initialize_owner};
 use cw_storage_plus::Bound;
-use cw_utils::must_pay;
-use dao_bets::dao_bets::{Bet, BetInfo, BetOption, ClaimInfo, Config};
+use cw_utils::{must_pay, one_coin};
+use dao_bets::dao_bets::{
+    Bet, BetInfo, BetOption, BetPayout, BetReceipt, ClaimInfo, ClaimReceipt, Config, ConfigChange,
+    ConfigUpdate, Sponsorship, WalletInfo,
+};
+use general::asset::{denom_key, transfer_msg};
+use general::elo::EloDelta;
+use general::events::{attr, patch_field, VeloAction, VeloContract, VeloEvent};
+use general::experience::{compute_experience, ExperienceAction};
+use general::hooks::{
+    hook_messages, GameHookMsg, HookPayout, HooksResponse, HOOK_REPLY_ID, MAX_HOOKS,
+};
+use general::ibc::{
+    intermediate_sender, payout_msg, sent_packet, IbcDenom, IbcDenomsResponse, IbcHooksSudoMsg,
+    IbcOrigin, IbcPayout, IBC_PAYOUT_REPLY_ID,
+};
+use general::migration::{self, MigrationStep, StepRun};
+use general::rbac::{self, assert_role, Role};
+use general::timelock::{self, PendingChange};
 use general::users::ExecuteMsg::AddExperienceAndElo;
+use neutron_sdk::bindings::query::NeutronQuery;
 
 use crate::{
     error::ContractError,
     msg::{
         ExecuteMsg, InstantiateMsg, MigrateMsg, MyBetsResponse, PendingRewardRoundsResponse,
-        QueryMsg,
+        QueryMsg, ReceiveMsg, SimulateBetOnResponse,
     },
     state::{
-        bet_info_key, bet_info_storage, claim_info_key, claim_info_storage, CONFIG, FINISHED_BETS,
+        bet_info_key, bet_info_storage, claim_info_key, claim_info_storage, CONFIG,
+        FAILED_IBC_PAYOUTS, FINISHED_BETS, HOOKS, IBC_DENOMS, IBC_ORIGINS, IBC_PAYOUTS,
         NEXT_BET_ID, TOTALS_SPENT, UNFINISHED_BETS,
     },
 };
@@ -24,10 +45,26 @@ use crate::{
 const MAX_PAGE_LIMIT: u32 = 250;
 
 const FEE_PRECISION: u128 = 100;
+// 10%, the gaming fee has 2 decimals of precision
+const MAX_GAMING_FEE: u128 = 1_000;
 
 const CONTRACT_NAME: &str = env!("CARGO_PKG_NAME");
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
+// State rewrites run by migrate, by the version that introduced them
+const MIGRATIONS: &[MigrationStep<MigrateMsg>] = &[
+    MigrationStep {
+        version: "0.2.0",
+        name: "config_denom",
+        run: StepRun::Once(migrate_config_denom),
+    },
+    MigrationStep {
+        version: "0.2.0",
+        name: "timelock_delay",
+        run: StepRun::Once(migrate_timelock_delay),
+    },
+];
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
     deps: DepsMut,
@@ -47,36 +84,65 @@ pub fn instantiate(
         ),
     )?;
 
-    if !msg.config.fee_receiver_wallet_list.is_empty() {
-        let mut total_ratio = Decimal::zero();
-        for dev_wallet in msg.config.fee_receiver_wallet_list.clone() {
-            total_ratio += dev_wallet.ratio;
-        }
-
-        if total_ratio != Decimal::one() {
-            return Err(ContractError::WrongRatio {});
-        }
-    }
+    validate_config(deps.as_ref(), &msg.config)?;
 
     CONFIG.save(deps.storage, &msg.config)?;
     NEXT_BET_ID.save(deps.storage, &1)?;
 
-    Ok(Response::new().add_attribute("velo_method", "instantiate_dao_bets"))
+    Ok(Response::new().add_event(velo_event(VeloAction::Instantiate)))
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn execute(
-    deps: DepsMut,
+    deps: DepsMut<NeutronQuery>,
     env: Env,
     info: MessageInfo,
     msg: ExecuteMsg,
 ) -> Result<Response, ContractError> {
     match msg {
-        ExecuteMsg::UpdateOwnership(action) => update_ownership(deps, env, info, action),
-        ExecuteMsg::UpdateConfig { config } => update_config(deps, info, config),
-        ExecuteMsg::BetOn { bet_id, option } => bet_on(deps, env, info, bet_id, option),
-        ExecuteMsg::CollectWinnings {} => collect_winnings(deps, info),
-        ExecuteMsg::CollectionWinningBet { bet_id } => collect_winnings_bet(deps, info, bet_id),
+        ExecuteMsg::UpdateOwnership(action) => {
+            update_ownership(deps.into_empty(), env, info, action)
+        }
+        ExecuteMsg::GrantRole { role, address } => {
+            grant_role(deps.into_empty(), info, role, address)
+        }
+        ExecuteMsg::RevokeRole { role, address } => {
+            revoke_role(deps.into_empty(), info, role, address)
+        }
+        ExecuteMsg::UpdateConfig { config } => update_config(deps.into_empty(), env, info, config),
+        ExecuteMsg::ExecuteChange { id } => execute_change(deps.into_empty(), env, id),
+        ExecuteMsg::CancelChange { id } => cancel_change(deps.into_empty(), info, id),
+        ExecuteMsg::UpdateTimelockDelay { seconds } => {
+            update_timelock_delay(deps.into_empty(), env, info, seconds)
+        }
+        ExecuteMsg::BetOn { bet_id, option } => bet_on_native(deps, env, info, bet_id, option),
+        ExecuteMsg::Receive(wrapper) => receive(deps, env, info, wrapper),
+        ExecuteMsg::CollectWinnings {} => collect_winnings(deps, env, info.sender),
+        ExecuteMsg::CollectionWinningBet { bet_id } => {
+            collect_winnings_bet(deps, env, info, bet_id)
+        }
+        ExecuteMsg::IbcBetOn {
+            channel,
+            original_sender,
+            bet_id,
+            option,
+            return_to_origin,
+        } => ibc_bet_on(
+            deps,
+            env,
+            info,
+            channel,
+            original_sender,
+            bet_id,
+            option,
+            return_to_origin,
+        ),
+        ExecuteMsg::CollectIbcWinnings { player } => collect_ibc_winnings(deps, env, player),
+        ExecuteMsg::RetryIbcPayout { player } => retry_ibc_payout(deps.into_empty(), env, player),
+        ExecuteMsg::AddIbcDenom { ibc_denom } => add_ibc_denom(deps.into_empty(), info, ibc_denom),
+        ExecuteMsg::RemoveIbcDenom { ibc_denom } => {
+            remove_ibc_denom(deps.into_empty(), info, ibc_denom)
+        }
         ExecuteMsg::CreateBet {
             topic,
             description,
@@ -86,7 +152,7 @@ pub fn execute(
             expected_result_timestamp,
             options,
         } => create_bet(
-            deps,
+            deps.into_empty(),
             info,
             topic,
             description,
@@ -104,7 +170,7 @@ pub fn execute(
             expected_result_timestamp,
             img_url,
         } => modify_bet(
-            deps,
+            deps.into_empty(),
             info,
             bet_id,
             topic,
@@ -116,11 +182,87 @@ pub fn execute(
         ExecuteMsg::CompleteBet {
             bet_id,
             result_option,
-        } => complete_bet(deps, info, bet_id, result_option),
-        ExecuteMsg::CancelBet { bet_id } => cancel_bet(deps, info, bet_id),
+        } => complete_bet(deps.into_empty(), info, bet_id, result_option),
+        ExecuteMsg::CancelBet { bet_id } => cancel_bet(deps.into_empty(), info, bet_id),
+        ExecuteMsg::Sponsor { bet_id } => {
+            let config = CONFIG.load(deps.storage)?;
+            // CW20 games are sponsored through Receive
+            let Denom::Native(denom) = &config.token_denom else {
+                return Err(ContractError::InvalidFunds {});
+            };
+            let amount = must_pay(&info, denom)?;
+            sponsor(deps.into_empty(), info.sender, bet_id, amount)
+        }
+        ExecuteMsg::AddHook { address } => add_hook(deps.into_empty(), info, address),
+        ExecuteMsg::RemoveHook { address } => remove_hook(deps.into_empty(), info, address),
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(deps: DepsMut, _env: Env, msg: Reply) -> Result<Response, ContractError> {
+    match msg.id {
+        // A listener failed, its state changes are reverted but the game action goes through
+        HOOK_REPLY_ID => {
+            let hook: Addr = from_json(&msg.payload)?;
+            let error = msg.result.into_result().err().unwrap_or_default();
+            Ok(Response::new().add_event(
+                velo_event(VeloAction::HookFailed)
+                    .add_attribute(attr::HOOK, hook)
+                    .add_attribute(attr::ERROR, error),
+            ))
+        }
+        // The winnings left for the origin chain, track them until ibc-hooks reports the outcome
+        IBC_PAYOUT_REPLY_ID => {
+            let payout: IbcPayout = from_json(&msg.payload)?;
+            let response = msg.result.into_result().map_err(StdError::generic_err)?;
+            let (channel, sequence) = sent_packet(&response)?;
+            IBC_PAYOUTS.save(deps.storage, (channel.clone(), sequence), &payout)?;
+
+            Ok(Response::new().add_event(
+                velo_event(VeloAction::IbcPayout)
+                    .add_attribute(attr::PLAYER, payout.player)
+                    .add_attribute(attr::AMOUNT, payout.amount)
+                    .add_attribute(attr::CHANNEL, channel)
+                    .add_attribute(attr::SEQUENCE, sequence.to_string()),
+            ))
+        }
+        id => Err(StdError::generic_err(format!("Unknown reply id: {}", id)).into()),
     }
 }
 
+/// Outcome of the winnings sent back to origin chains. Failed and timed out transfers are refunded
+/// to the game by the transfer module, so their amount is credited back to the player
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn sudo(deps: DepsMut, _env: Env, msg: IbcHooksSudoMsg) -> Result<Response, ContractError> {
+    let IbcHooksSudoMsg::IbcLifecycleComplete(outcome) = msg;
+    let (channel, sequence, success) = outcome.outcome();
+
+    // Only payouts are tracked, other transfers have nothing to settle
+    let key = (channel.clone(), sequence);
+    let Some(payout) = IBC_PAYOUTS.may_load(deps.storage, key.clone())? else {
+        return Ok(Response::new());
+    };
+    IBC_PAYOUTS.remove(deps.storage, key);
+
+    if success {
+        return Ok(Response::new());
+    }
+
+    FAILED_IBC_PAYOUTS.update(
+        deps.storage,
+        payout.player.clone(),
+        |failed| -> StdResult<_> { Ok(failed.unwrap_or_default().checked_add(payout.amount)?) },
+    )?;
+
+    Ok(Response::new().add_event(
+        velo_event(VeloAction::IbcPayoutFailed)
+            .add_attribute(attr::PLAYER, payout.player)
+            .add_attribute(attr::AMOUNT, payout.amount)
+            .add_attribute(attr::CHANNEL, channel)
+            .add_attribute(attr::SEQUENCE, sequence.to_string()),
+    ))
+}
+
 fn update_ownership(
     deps: DepsMut,
     env: Env,
@@ -128,40 +270,425 @@ fn update_ownership(
     action: cw_ownable::Action,
 ) -> Result<Response, ContractError> {
     let ownership = cw_ownable::update_ownership(deps, &env.block, &info.sender, action)?;
-    Ok(Response::new().add_attributes(ownership.into_attributes()))
+    Ok(Response::new().add_event(
+        velo_event(VeloAction::UpdateOwnership).add_attributes(ownership.into_attributes()),
+    ))
 }
 
-fn update_config(
+fn grant_role(
     deps: DepsMut,
     info: MessageInfo,
-    config: Config,
+    role: Role,
+    address: String,
 ) -> Result<Response, ContractError> {
-    assert_owner(deps.storage, &info.sender)?;
+    let member = rbac::grant_role(deps.storage, deps.api, &info.sender, role, &address)?;
+    Ok(Response::new().add_event(
+        velo_event(VeloAction::GrantRole)
+            .add_attribute(attr::ROLE, role.as_str())
+            .add_attribute(attr::MEMBER, member),
+    ))
+}
+
+fn revoke_role(
+    deps: DepsMut,
+    info: MessageInfo,
+    role: Role,
+    address: String,
+) -> Result<Response, ContractError> {
+    let member = rbac::revoke_role(deps.storage, deps.api, &info.sender, role, &address)?;
+    Ok(Response::new().add_event(
+        velo_event(VeloAction::RevokeRole)
+            .add_attribute(attr::ROLE, role.as_str())
+            .add_attribute(attr::MEMBER, member),
+    ))
+}
+
+fn update_config(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    update: ConfigUpdate,
+) -> Result<Response, ContractError> {
+    assert_role(deps.storage, Role::ConfigAdmin, &info.sender)?;
+
+    // Fail now rather than when the change is executed
+    let mut config = CONFIG.load(deps.storage)?;
+    patch_config(&mut config, update.clone())?;
+    validate_config(deps.as_ref(), &config)?;
+
+    // Only the fields moving funds wait for the timelock, the rest applies at once
+    let (timelocked, immediate) = update.split_timelocked();
+    let mut resp = Response::new();
+    if immediate != ConfigUpdate::default() {
+        resp = apply_change(deps.branch(), ConfigChange::Config { config: immediate })?;
+    }
+    if timelocked != ConfigUpdate::default() {
+        let queued = queue_or_apply_change(
+            deps,
+            env,
+            info.sender,
+            ConfigChange::Config { config: timelocked },
+        )?;
+        resp = resp.add_events(queued.events);
+    }
+
+    Ok(resp)
+}
+
+/// Sets the fields of the update, returning the old and new value of each one as attributes
+fn patch_config(config: &mut Config, update: ConfigUpdate) -> StdResult<Vec<Attribute>> {
+    let mut attributes = vec![];
+    patch_field(
+        &mut attributes,
+        "minimum_bet",
+        &mut config.minimum_bet,
+        update.minimum_bet,
+    )?;
+    patch_field(
+        &mut attributes,
+        "gaming_fee",
+        &mut config.gaming_fee,
+        update.gaming_fee,
+    )?;
+    patch_field(
+        &mut attributes,
+        "token_denom",
+        &mut config.token_denom,
+        update.token_denom,
+    )?;
+    patch_field(
+        &mut attributes,
+        "users_contract",
+        &mut config.users_contract,
+        update.users_contract,
+    )?;
+    patch_field(
+        &mut attributes,
+        "experience",
+        &mut config.experience,
+        update.experience,
+    )?;
+    patch_field(
+        &mut attributes,
+        "elo_k_factor",
+        &mut config.elo_k_factor,
+        update.elo_k_factor,
+    )?;
+    patch_field(
+        &mut attributes,
+        "fee_receiver_wallet_list",
+        &mut config.fee_receiver_wallet_list,
+        update.fee_receiver_wallet_list,
+    )?;
 
-    let mut total_ratio = Decimal::zero();
-    for fee_receiver_wallet in &config.fee_receiver_wallet_list {
-        total_ratio += fee_receiver_wallet.ratio;
+    Ok(attributes)
+}
+
+fn validate_config(deps: Deps, config: &Config) -> Result<(), ContractError> {
+    if config.minimum_bet.is_zero() {
+        return Err(ContractError::InvalidMinimumBet {});
+    }
+    if config.gaming_fee > Uint128::new(MAX_GAMING_FEE) {
+        return Err(ContractError::GamingFeeTooHigh {
+            max: Uint128::new(MAX_GAMING_FEE),
+        });
     }
+    if let Denom::Cw20(token) = &config.token_denom {
+        deps.api.addr_validate(token.as_str())?;
+    }
+    deps.api.addr_validate(config.users_contract.as_str())?;
+
+    // Fees stay in the contract when there are no wallets to split them between
+    if !config.fee_receiver_wallet_list.is_empty() {
+        let mut total_ratio = Decimal::zero();
+        for fee_receiver_wallet in &config.fee_receiver_wallet_list {
+            deps.api
+                .addr_validate(fee_receiver_wallet.address.as_str())?;
+            total_ratio += fee_receiver_wallet.ratio;
+        }
 
-    if total_ratio != Decimal::one() {
-        return Err(ContractError::WrongRatio {});
+        if total_ratio != Decimal::one() {
+            return Err(ContractError::WrongRatio {});
+        }
     }
 
-    CONFIG.save(deps.storage, &config)?;
+    config.experience.validate()?;
 
-    Ok(Response::new())
+    Ok(())
 }
 
-fn bet_on(
+/// Applies the change at once when there is no timelock delay, otherwise queues it
+fn queue_or_apply_change(
+    deps: DepsMut,
+    env: Env,
+    proposer: Addr,
+    change: ConfigChange,
+) -> Result<Response, ContractError> {
+    if timelock::delay(deps.storage)? == 0 {
+        return apply_change(deps, change);
+    }
+
+    let pending = timelock::queue(deps.storage, proposer, env.block.time, change)?;
+    Ok(Response::new().add_event(
+        velo_event(VeloAction::QueueChange)
+            .add_attribute(attr::CHANGE_ID, pending.id.to_string())
+            .add_attribute(attr::CHANGE, pending.change.as_str())
+            .add_attribute(
+                attr::EXECUTABLE_AT,
+                pending.executable_at.seconds().to_string(),
+            ),
+    ))
+}
+
+fn apply_change(deps: DepsMut, change: ConfigChange) -> Result<Response, ContractError> {
+    match change {
+        ConfigChange::Config { config: update } => {
+            let mut config = CONFIG.load(deps.storage)?;
+            let attributes = patch_config(&mut config, update)?;
+            validate_config(deps.as_ref(), &config)?;
+            CONFIG.save(deps.storage, &config)?;
+
+            Ok(Response::new()
+                .add_event(velo_event(VeloAction::UpdateConfig).add_attributes(attributes)))
+        }
+        ConfigChange::TimelockDelay { seconds } => {
+            timelock::set_delay(deps.storage, seconds)?;
+
+            Ok(Response::new().add_event(
+                velo_event(VeloAction::UpdateTimelockDelay)
+                    .add_attribute(attr::DELAY, seconds.to_string()),
+            ))
+        }
+    }
+}
+
+fn execute_change(deps: DepsMut, env: Env, id: u64) -> Result<Response, ContractError> {
+    let pending: PendingChange<ConfigChange> =
+        timelock::take_ready(deps.storage, id, env.block.time)?;
+    let res = apply_change(deps, pending.change)?;
+
+    Ok(res.add_event(
+        velo_event(VeloAction::ExecuteChange).add_attribute(attr::CHANGE_ID, id.to_string()),
+    ))
+}
+
+fn cancel_change(deps: DepsMut, info: MessageInfo, id: u64) -> Result<Response, ContractError> {
+    assert_role(deps.storage, Role::Owner, &info.sender)?;
+    let pending: PendingChange<ConfigChange> = timelock::cancel(deps.storage, id)?;
+
+    Ok(Response::new().add_event(
+        velo_event(VeloAction::CancelChange)
+            .add_attribute(attr::CHANGE_ID, id.to_string())
+            .add_attribute(attr::CHANGE, pending.change.as_str()),
+    ))
+}
+
+fn update_timelock_delay(
     deps: DepsMut,
     env: Env,
     info: MessageInfo,
+    seconds: u64,
+) -> Result<Response, ContractError> {
+    assert_role(deps.storage, Role::Owner, &info.sender)?;
+
+    let change = ConfigChange::TimelockDelay { seconds };
+    if seconds >= timelock::delay(deps.storage)? {
+        return apply_change(deps, change);
+    }
+
+    queue_or_apply_change(deps, env, info.sender, change)
+}
+
+fn bet_on_native(
+    deps: DepsMut<NeutronQuery>,
+    env: Env,
+    info: MessageInfo,
     bet_id: Uint128,
     option: String,
 ) -> Result<Response, ContractError> {
     let config = CONFIG.load(deps.storage)?;
 
-    let funds_sent = must_pay(&info, &config.token_denom)?;
+    // CW20 games only take bets through Receive
+    let Denom::Native(denom) = &config.token_denom else {
+        return Err(ContractError::InvalidFunds {});
+    };
+    let funds_sent = must_pay(&info, denom)?;
+
+    bet_on(deps, env, info.sender, bet_id, option, funds_sent)
+}
+
+fn receive(
+    deps: DepsMut<NeutronQuery>,
+    env: Env,
+    info: MessageInfo,
+    wrapper: Cw20ReceiveMsg,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+
+    // Only the CW20 contract of the game can send tokens
+    if config.token_denom != Denom::Cw20(info.sender) {
+        return Err(ContractError::InvalidFunds {});
+    }
+
+    let player = deps.api.addr_validate(&wrapper.sender)?;
+    match from_json(&wrapper.msg)? {
+        ReceiveMsg::BetOn { bet_id, option } => {
+            bet_on(deps, env, player, bet_id, option, wrapper.amount)
+        }
+        ReceiveMsg::Sponsor { bet_id } => {
+            sponsor(deps.into_empty(), player, bet_id, wrapper.amount)
+        }
+    }
+}
+
+/// Escrows a prize for the winners of a bet that is not settled yet
+fn sponsor(
+    deps: DepsMut,
+    sponsor: Addr,
+    bet_id: Uint128,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    let mut bet = UNFINISHED_BETS
+        .load(deps.storage, bet_id.u128())
+        .map_err(|_| ContractError::BetNotFound {})?;
+
+    match bet.sponsorships.iter_mut().find(|s| s.sponsor == sponsor) {
+        Some(sponsorship) => sponsorship.amount = sponsorship.amount.checked_add(amount)?,
+        None => bet.sponsorships.push(Sponsorship {
+            sponsor: sponsor.clone(),
+            amount,
+        }),
+    }
+    UNFINISHED_BETS.save(deps.storage, bet_id.u128(), &bet)?;
+
+    Ok(Response::new().add_event(
+        velo_event(VeloAction::Sponsor)
+            .add_attribute(attr::BET_ID, bet_id)
+            .add_attribute(attr::SPONSOR, sponsor.to_string())
+            .add_attribute(attr::AMOUNT, amount.to_string()),
+    ))
+}
+
+/// Sends every sponsor of a bet without winners its escrow back
+fn refund_sponsors(storage: &dyn Storage, bet: &Bet) -> Result<Response, ContractError> {
+    let config = CONFIG.load(storage)?;
+    let mut resp = Response::new();
+    for sponsorship in bet.sponsorships.iter() {
+        resp = resp
+            .add_message(transfer_msg(
+                &config.token_denom,
+                &sponsorship.sponsor,
+                sponsorship.amount,
+            )?)
+            .add_event(
+                velo_event(VeloAction::RefundSponsor)
+                    .add_attribute(attr::BET_ID, bet.bet_id.to_string())
+                    .add_attribute(attr::SPONSOR, sponsorship.sponsor.to_string())
+                    .add_attribute(attr::AMOUNT, sponsorship.amount.to_string()),
+            );
+    }
+
+    Ok(resp)
+}
+
+#[allow(clippy::too_many_arguments)]
+fn ibc_bet_on(
+    deps: DepsMut<NeutronQuery>,
+    env: Env,
+    info: MessageInfo,
+    channel: String,
+    original_sender: String,
+    bet_id: Uint128,
+    option: String,
+    return_to_origin: bool,
+) -> Result<Response, ContractError> {
+    if info.sender != intermediate_sender(deps.api, &channel, &original_sender)? {
+        return Err(ContractError::InvalidIbcSender {});
+    }
+
+    let funds_sent = one_coin(&info)?;
+    if !IBC_DENOMS.has(deps.storage, funds_sent.denom.clone()) {
+        return Err(ContractError::IbcDenomNotAllowed {
+            denom: funds_sent.denom,
+        });
+    }
+
+    IBC_ORIGINS.save(
+        deps.storage,
+        info.sender.clone(),
+        &IbcOrigin {
+            channel: channel.clone(),
+            sender: original_sender.clone(),
+            return_to_origin,
+        },
+    )?;
+
+    let resp = bet_on(
+        deps,
+        env,
+        info.sender.clone(),
+        bet_id,
+        option,
+        funds_sent.amount,
+    )?;
+
+    Ok(resp.add_event(
+        velo_event(VeloAction::IbcBet)
+            .add_attribute(attr::BET_ID, bet_id)
+            .add_attribute(attr::PLAYER, info.sender)
+            .add_attribute(attr::CHANNEL, channel)
+            .add_attribute(attr::ORIGINAL_SENDER, original_sender)
+            .add_attribute(attr::IBC_DENOM, funds_sent.denom),
+    ))
+}
+
+fn retry_ibc_payout(deps: DepsMut, env: Env, player: Addr) -> Result<Response, ContractError> {
+    let amount = FAILED_IBC_PAYOUTS
+        .may_load(deps.storage, player.clone())?
+        .ok_or(ContractError::NoFailedIbcPayout {})?;
+    FAILED_IBC_PAYOUTS.remove(deps.storage, player.clone());
+
+    let config = CONFIG.load(deps.storage)?;
+
+    // Sent to the origin the player has now, locally if they stopped asking for it
+    Ok(Response::new()
+        .add_submessage(payout_msg(
+            &env,
+            IBC_ORIGINS.may_load(deps.storage, player.clone())?.as_ref(),
+            &config.token_denom,
+            &player,
+            amount,
+        )?)
+        .add_event(
+            velo_event(VeloAction::RetryIbcPayout)
+                .add_attribute(attr::PLAYER, player)
+                .add_attribute(attr::AMOUNT, amount),
+        ))
+}
+
+fn collect_ibc_winnings(
+    deps: DepsMut<NeutronQuery>,
+    env: Env,
+    player: Addr,
+) -> Result<Response, ContractError> {
+    let origin = IBC_ORIGINS.may_load(deps.storage, player.clone())?;
+    if !origin.is_some_and(|origin| origin.return_to_origin) {
+        return Err(ContractError::NotReturnedToOrigin {});
+    }
+
+    collect_winnings(deps, env, player)
+}
+
+/// Places a bet of `funds_sent` tokens, already received by the contract, for `player`
+fn bet_on(
+    deps: DepsMut<NeutronQuery>,
+    env: Env,
+    player: Addr,
+    bet_id: Uint128,
+    option: String,
+    funds_sent: Uint128,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
 
     if funds_sent < config.minimum_bet {
         return Err(ContractError::BetUnderMinBetAmount {});
@@ -175,18 +702,18 @@ fn bet_on(
         return Err(ContractError::BetAlreadyFinished {});
     }
 
-    let totals = TOTALS_SPENT.may_load(deps.storage, info.clone().sender)?;
+    let totals = TOTALS_SPENT.may_load(deps.storage, player.clone())?;
     if let Some(totals) = totals {
         TOTALS_SPENT.save(
             deps.storage,
-            info.clone().sender,
+            player.clone(),
             &totals.checked_add(funds_sent)?,
         )?;
     } else {
-        TOTALS_SPENT.save(deps.storage, info.clone().sender, &funds_sent)?;
+        TOTALS_SPENT.save(deps.storage, player.clone(), &funds_sent)?;
     }
 
-    let bet_info_key = bet_info_key(bet_id.u128(), &info.sender.clone());
+    let bet_info_key = bet_info_key(bet_id.u128(), &player);
     let bet_info = bet_info_storage().may_load(deps.storage, bet_info_key.clone())?;
 
     let mut amount_bet = funds_sent;
@@ -209,8 +736,13 @@ fn bet_on(
     UNFINISHED_BETS.save(deps.storage, bet_id.u128(), &bet)?;
 
     let experience_message = AddExperienceAndElo {
-        user: info.sender.clone(),
-        experience: funds_sent.u128() as u64 * config.exp_per_denom_bet,
+        user: player.clone(),
+        experience: compute_experience(
+            &deps.querier,
+            &config.experience,
+            funds_sent,
+            ExperienceAction::Bet,
+        )?,
         elo: None,
     };
 
@@ -224,28 +756,58 @@ fn bet_on(
         deps.storage,
         bet_info_key.clone(),
         &BetInfo {
-            player: info.sender.clone(),
+            player: player.clone(),
             bet_id,
             amount: amount_bet,
             option: option.clone(),
         },
     )?;
 
+    let receipt = BetReceipt {
+        bet_id,
+        option: option.clone(),
+        stake: funds_sent,
+        total_stake: amount_bet,
+        current_bet_amounts: bet.current_bet_amounts,
+    };
+
+    let hook_msgs = hook_messages(
+        load_hooks(deps.storage)?,
+        GameHookMsg::Bet {
+            player: player.clone(),
+            id: bet_id,
+            side: receipt.option.clone(),
+            denom: denom_key(&config.token_denom),
+            amount: funds_sent,
+        },
+    )?;
+
     Ok(Response::new()
+        .set_data(to_json_binary(&receipt)?)
+        .add_submessages(hook_msgs)
         .add_message(wasm_message)
-        .add_attribute("velo_action", "bet".to_string())
-        .add_attribute("velo_bet_id", bet_id.to_string())
-        .add_attribute("velo_option", option)
-        .add_attribute("velo_amount", funds_sent)
-        .add_attribute("velo_account", info.sender.to_string()))
+        .add_event(
+            velo_event(VeloAction::Bet)
+                .add_attribute(attr::BET_ID, bet_id.to_string())
+                .add_attribute(attr::PLAYER, player.to_string())
+                .add_attribute(attr::OPTION, option)
+                .add_attribute(attr::AMOUNT, funds_sent),
+        ))
 }
 
-fn collect_winnings(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
+fn collect_winnings(
+    deps: DepsMut<NeutronQuery>,
+    env: Env,
+    player: Addr,
+) -> Result<Response, ContractError> {
     let config = CONFIG.load(deps.storage)?;
     let mut winnings = Uint128::zero();
     let mut amount_commissionable = Uint128::zero();
+    let mut elo_delta = EloDelta::default();
+    let mut payouts: Vec<BetPayout> = vec![];
+    let mut settled = false;
 
-    let my_game_list = query_my_games_without_limit(deps.as_ref(), info.sender.clone())?;
+    let my_game_list = query_my_games_without_limit(deps.as_ref().into_empty(), player.clone())?;
 
     for game in my_game_list.my_bets_list {
         let bet_id = game.bet_id;
@@ -255,12 +817,15 @@ fn collect_winnings(deps: DepsMut, info: MessageInfo) -> Result<Response, Contra
             None => continue,
         };
 
-        let bet_info_key = bet_info_key(bet_id.u128(), &info.sender);
+        let bet_info_key = bet_info_key(bet_id.u128(), &player);
 
         bet_info_storage().remove(deps.storage, bet_info_key.clone())?;
+        settled = true;
+
+        let claim_info_key = claim_info_key(bet_id.u128(), &player);
+        let bet_amount = finished_bet.prize();
 
-        let claim_info_key = claim_info_key(bet_id.u128(), &info.sender);
-        let bet_amount = finished_bet.current_bet_amounts.values().sum::<Uint128>();
+        record_elo(&mut elo_delta, config.elo_k_factor, &finished_bet, &game);
 
         // Hasn't won this round and it's not cancelled
         if finished_bet.result_option.unwrap_or_default() != game.option && !finished_bet.cancelled
@@ -281,19 +846,24 @@ fn collect_winnings(deps: DepsMut, info: MessageInfo) -> Result<Response, Contra
         if !finished_bet.cancelled {
             amount_commissionable += round_winnings;
         }
+        payouts.push(BetPayout {
+            bet_id,
+            amount: round_winnings,
+        });
 
         claim_info_storage().save(
             deps.storage,
             claim_info_key,
             &ClaimInfo {
-                player: info.sender.clone(),
+                player: player.clone(),
                 bet_id,
                 claimed_amount: round_winnings,
             },
         )?;
     }
 
-    if winnings == Uint128::zero() {
+    // Lost bets are settled too, so their Elo loss is recorded even with nothing to pay
+    if !settled {
         return Err(ContractError::Std(StdError::generic_err(
             "Nothing to claim",
         )));
@@ -303,22 +873,28 @@ fn collect_winnings(deps: DepsMut, info: MessageInfo) -> Result<Response, Contra
     let mut messages_fees = Vec::new();
     let mut resp = Response::new();
     if amount_commissionable > Uint128::zero() {
-        fee = compute_gaming_fee(deps.as_ref(), winnings)?;
+        fee = compute_gaming_fee(deps.as_ref().into_empty(), winnings)?;
         for fee_wallet in config.fee_receiver_wallet_list {
-            let amount = fee.mul_floor(fee_wallet.ratio).u128();
-            if amount > 0 {
-                let token_transfer_msg = BankMsg::Send {
-                    to_address: fee_wallet.address.to_string(),
-                    amount: coins(amount, &config.token_denom),
-                };
+            let amount = fee.mul_floor(fee_wallet.ratio);
+            if !amount.is_zero() {
+                let token_transfer_msg =
+                    transfer_msg(&config.token_denom, &fee_wallet.address, amount)?;
                 messages_fees.push(token_transfer_msg)
             }
         }
+    }
 
+    let elo = elo_delta.into_elo();
+    if amount_commissionable > Uint128::zero() || elo.is_some() {
         let experience_message = AddExperienceAndElo {
-            user: info.sender.clone(),
-            experience: winnings.u128() as u64 * config.exp_per_denom_won,
-            elo: None,
+            user: player.clone(),
+            experience: compute_experience(
+                &deps.querier,
+                &config.experience,
+                amount_commissionable,
+                ExperienceAction::Won,
+            )?,
+            elo,
         };
 
         let wasm_message = WasmMsg::Execute {
@@ -330,21 +906,58 @@ fn collect_winnings(deps: DepsMut, info: MessageInfo) -> Result<Response, Contra
     }
 
     let amount_winnings = winnings.u128() - fee.u128();
-    let msg_send_winnings = BankMsg::Send {
-        to_address: info.sender.to_string(),
-        amount: coins(amount_winnings, &config.token_denom),
+    if amount_winnings != 0 {
+        resp = resp.add_submessage(payout_msg(
+            &env,
+            IBC_ORIGINS.may_load(deps.storage, player.clone())?.as_ref(),
+            &config.token_denom,
+            &player,
+            Uint128::from(amount_winnings),
+        )?);
+    }
+
+    let receipt = ClaimReceipt {
+        player: player.clone(),
+        payouts,
+        gross: winnings,
+        fee,
+        net: Uint128::from(amount_winnings),
     };
 
+    let hook_msgs = hook_messages(
+        load_hooks(deps.storage)?,
+        GameHookMsg::Claim {
+            player: player.clone(),
+            denom: denom_key(&config.token_denom),
+            payouts: receipt
+                .payouts
+                .iter()
+                .map(|payout| HookPayout {
+                    id: payout.bet_id,
+                    amount: payout.amount,
+                })
+                .collect(),
+            gross: receipt.gross,
+            fee: receipt.fee,
+            net: receipt.net,
+        },
+    )?;
+
     Ok(resp
+        .set_data(to_json_binary(&receipt)?)
+        .add_submessages(hook_msgs)
         .add_messages(messages_fees)
-        .add_message(msg_send_winnings)
-        .add_attribute("velo_action", "collect-winnings")
-        .add_attribute("velo_claimer", info.sender)
-        .add_attribute("velo_amount", amount_winnings.to_string()))
+        .add_event(
+            velo_event(VeloAction::Claim)
+                .add_attribute(attr::PLAYER, player)
+                .add_attribute(attr::AMOUNT, amount_winnings.to_string())
+                .add_attribute(attr::FEE, fee),
+        ))
 }
 
 fn collect_winnings_bet(
-    deps: DepsMut,
+    deps: DepsMut<NeutronQuery>,
+    env: Env,
     info: MessageInfo,
     bet_id: Uint128,
 ) -> Result<Response, ContractError> {
@@ -365,51 +978,60 @@ fn collect_winnings_bet(
     bet_info_storage().remove(deps.storage, bet_info_key.clone())?;
 
     let claim_info_key = claim_info_key(bet_id.u128(), &info.sender);
-    let bet_amount = finished_bet.current_bet_amounts.values().sum::<Uint128>();
+    let bet_amount = finished_bet.prize();
 
-    // Hasn't won this round and it's not cancelled
-    if finished_bet.result_option.unwrap_or_default() != game.option && !finished_bet.cancelled {
-        return Err(ContractError::NothingToClaim {});
-    }
+    // Hasn't won this round and it's not cancelled, the bet is settled to record the Elo loss
+    let lost = finished_bet.result_option.clone().unwrap_or_default() != game.option
+        && !finished_bet.cancelled;
 
     let round_winnings = if finished_bet.cancelled {
         game.amount
+    } else if lost {
+        Uint128::zero()
     } else {
         let total_won_shares = finished_bet.current_bet_amounts.get(&game.option).unwrap();
         let user_won_shares = game.amount;
         bet_amount.multiply_ratio(user_won_shares, total_won_shares.u128())
     };
 
-    claim_info_storage().save(
-        deps.storage,
-        claim_info_key,
-        &ClaimInfo {
-            player: info.sender.clone(),
-            bet_id,
-            claimed_amount: round_winnings,
-        },
-    )?;
+    if !round_winnings.is_zero() {
+        claim_info_storage().save(
+            deps.storage,
+            claim_info_key,
+            &ClaimInfo {
+                player: info.sender.clone(),
+                bet_id,
+                claimed_amount: round_winnings,
+            },
+        )?;
+    }
 
     let mut fee = Uint128::zero();
     let mut messages_fees = Vec::new();
     let mut resp = Response::new();
     if !finished_bet.cancelled {
-        fee = compute_gaming_fee(deps.as_ref(), round_winnings)?;
+        fee = compute_gaming_fee(deps.as_ref().into_empty(), round_winnings)?;
         for fee_wallet in config.fee_receiver_wallet_list {
-            let amount = fee.mul_floor(fee_wallet.ratio).u128();
-            if amount > 0 {
-                let token_transfer_msg = BankMsg::Send {
-                    to_address: fee_wallet.address.to_string(),
-                    amount: coins(amount, &config.token_denom),
-                };
+            let amount = fee.mul_floor(fee_wallet.ratio);
+            if !amount.is_zero() {
+                let token_transfer_msg =
+                    transfer_msg(&config.token_denom, &fee_wallet.address, amount)?;
                 messages_fees.push(token_transfer_msg)
             }
         }
 
+        let mut elo_delta = EloDelta::default();
+        record_elo(&mut elo_delta, config.elo_k_factor, &finished_bet, &game);
+
         let experience_message = AddExperienceAndElo {
             user: info.sender.clone(),
-            experience: round_winnings.u128() as u64 * config.exp_per_denom_won,
-            elo: None,
+            experience: compute_experience(
+                &deps.querier,
+                &config.experience,
+                round_winnings,
+                ExperienceAction::Won,
+            )?,
+            elo: elo_delta.into_elo(),
         };
 
         let wasm_message = WasmMsg::Execute {
@@ -421,17 +1043,59 @@ fn collect_winnings_bet(
     }
 
     let amount_winnings = round_winnings.u128() - fee.u128();
-    let msg_send_winnings = BankMsg::Send {
-        to_address: info.sender.to_string(),
-        amount: coins(amount_winnings, &config.token_denom),
+    if amount_winnings != 0 {
+        resp = resp.add_submessage(payout_msg(
+            &env,
+            IBC_ORIGINS
+                .may_load(deps.storage, info.sender.clone())?
+                .as_ref(),
+            &config.token_denom,
+            &info.sender,
+            Uint128::from(amount_winnings),
+        )?);
+    }
+
+    let receipt = ClaimReceipt {
+        player: info.sender.clone(),
+        payouts: vec![BetPayout {
+            bet_id,
+            amount: round_winnings,
+        }],
+        gross: round_winnings,
+        fee,
+        net: Uint128::from(amount_winnings),
     };
 
+    let hook_msgs = hook_messages(
+        load_hooks(deps.storage)?,
+        GameHookMsg::Claim {
+            player: info.sender.clone(),
+            denom: denom_key(&config.token_denom),
+            payouts: receipt
+                .payouts
+                .iter()
+                .map(|payout| HookPayout {
+                    id: payout.bet_id,
+                    amount: payout.amount,
+                })
+                .collect(),
+            gross: receipt.gross,
+            fee: receipt.fee,
+            net: receipt.net,
+        },
+    )?;
+
     Ok(resp
+        .set_data(to_json_binary(&receipt)?)
+        .add_submessages(hook_msgs)
         .add_messages(messages_fees)
-        .add_message(msg_send_winnings)
-        .add_attribute("velo_action", "collect-winnings")
-        .add_attribute("velo_claimer", info.sender)
-        .add_attribute("velo_amount", amount_winnings.to_string()))
+        .add_event(
+            velo_event(VeloAction::Claim)
+                .add_attribute(attr::BET_ID, bet_id)
+                .add_attribute(attr::PLAYER, info.sender)
+                .add_attribute(attr::AMOUNT, amount_winnings.to_string())
+                .add_attribute(attr::FEE, fee),
+        ))
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -446,7 +1110,7 @@ fn create_bet(
     expected_result_timestamp: Option<u64>,
     options: Vec<BetOption>,
 ) -> Result<Response, ContractError> {
-    assert_owner(deps.storage, &info.sender)?;
+    assert_role(deps.storage, Role::MarketCreator, &info.sender)?;
 
     let bet_id = NEXT_BET_ID.load(deps.storage)?;
     NEXT_BET_ID.save(deps.storage, &(bet_id + 1))?;
@@ -467,13 +1131,15 @@ fn create_bet(
         result_option: None,
         cancelled: false,
         num_players: 0,
+        sponsorships: vec![],
+        boost: Uint128::zero(),
     };
 
     UNFINISHED_BETS.save(deps.storage, bet_id, &bet)?;
 
-    Ok(Response::new()
-        .add_attribute("velo_action", "create-bet")
-        .add_attribute("velo_bet_id", bet_id.to_string()))
+    Ok(Response::new().add_event(
+        velo_event(VeloAction::CreateBet).add_attribute(attr::BET_ID, bet_id.to_string()),
+    ))
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -487,7 +1153,7 @@ fn modify_bet(
     expected_result_timestamp: Option<u64>,
     img_url: Option<String>,
 ) -> Result<Response, ContractError> {
-    assert_owner(deps.storage, &info.sender)?;
+    assert_role(deps.storage, Role::MarketCreator, &info.sender)?;
 
     let mut bet = UNFINISHED_BETS
         .load(deps.storage, bet_id.u128())
@@ -516,8 +1182,7 @@ fn modify_bet(
     UNFINISHED_BETS.save(deps.storage, bet_id.u128(), &bet)?;
 
     Ok(Response::new()
-        .add_attribute("velo_action", "modify-bet")
-        .add_attribute("velo_bet_id", bet_id))
+        .add_event(velo_event(VeloAction::ModifyBet).add_attribute(attr::BET_ID, bet_id)))
 }
 
 fn complete_bet(
@@ -526,7 +1191,7 @@ fn complete_bet(
     bet_id: Uint128,
     result_option: String,
 ) -> Result<Response, ContractError> {
-    assert_owner(deps.storage, &info.sender)?;
+    assert_role(deps.storage, Role::Resolver, &info.sender)?;
 
     let mut bet = UNFINISHED_BETS
         .load(deps.storage, bet_id.u128())
@@ -534,13 +1199,34 @@ fn complete_bet(
 
     bet.result_option = Some(result_option.clone());
 
+    // Sponsors are refunded if nobody bet on the result
+    let resp = if bet
+        .current_bet_amounts
+        .get(&result_option)
+        .is_some_and(|amount| !amount.is_zero())
+    {
+        bet.boost = bet.sponsored();
+        Response::new()
+    } else {
+        refund_sponsors(deps.storage, &bet)?
+    };
+
     FINISHED_BETS.save(deps.storage, bet_id.u128(), &bet)?;
     UNFINISHED_BETS.remove(deps.storage, bet_id.u128())?;
 
-    Ok(Response::new()
-        .add_attribute("velo_action", "complete-bet")
-        .add_attribute("velo_bet_id", bet_id.to_string())
-        .add_attribute("velo_result_option", result_option))
+    let hook_msgs = hook_messages(
+        load_hooks(deps.storage)?,
+        GameHookMsg::Settle {
+            id: bet_id,
+            result: Some(result_option.clone()),
+        },
+    )?;
+
+    Ok(resp.add_submessages(hook_msgs).add_event(
+        velo_event(VeloAction::CompleteBet)
+            .add_attribute(attr::BET_ID, bet_id)
+            .add_attribute(attr::RESULT_OPTION, result_option),
+    ))
 }
 
 fn cancel_bet(
@@ -548,26 +1234,124 @@ fn cancel_bet(
     info: MessageInfo,
     bet_id: Uint128,
 ) -> Result<Response, ContractError> {
-    assert_owner(deps.storage, &info.sender)?;
+    assert_role(deps.storage, Role::Resolver, &info.sender)?;
 
     let mut bet = UNFINISHED_BETS
         .load(deps.storage, bet_id.u128())
         .map_err(|_| ContractError::BetNotFound {})?;
 
     bet.cancelled = true;
+    let resp = refund_sponsors(deps.storage, &bet)?;
 
     FINISHED_BETS.save(deps.storage, bet_id.u128(), &bet)?;
     UNFINISHED_BETS.remove(deps.storage, bet_id.u128())?;
 
+    let hook_msgs = hook_messages(
+        load_hooks(deps.storage)?,
+        GameHookMsg::Settle {
+            id: bet_id,
+            result: None,
+        },
+    )?;
+
+    Ok(resp
+        .add_submessages(hook_msgs)
+        .add_event(velo_event(VeloAction::CancelBet).add_attribute(attr::BET_ID, bet_id)))
+}
+
+fn add_hook(deps: DepsMut, info: MessageInfo, address: Addr) -> Result<Response, ContractError> {
+    assert_role(deps.storage, Role::ConfigAdmin, &info.sender)?;
+    deps.api.addr_validate(address.as_str())?;
+
+    if HOOKS.has(deps.storage, address.clone()) {
+        return Err(ContractError::HookAlreadyRegistered {});
+    }
+
+    if load_hooks(deps.storage)?.len() >= MAX_HOOKS {
+        return Err(ContractError::TooManyHooks { max: MAX_HOOKS });
+    }
+
+    HOOKS.save(deps.storage, address.clone(), &Empty {})?;
+
+    Ok(Response::new()
+        .add_event(velo_event(VeloAction::AddHook).add_attribute(attr::HOOK, address)))
+}
+
+fn add_ibc_denom(
+    deps: DepsMut,
+    info: MessageInfo,
+    ibc_denom: String,
+) -> Result<Response, ContractError> {
+    assert_role(deps.storage, Role::ConfigAdmin, &info.sender)?;
+
+    // Bets and winnings are in the game token, so only the same native token can be accepted
+    if CONFIG.load(deps.storage)?.token_denom != Denom::Native(ibc_denom.clone()) {
+        return Err(ContractError::IbcDenomMismatch { ibc_denom });
+    }
+
+    IBC_DENOMS.save(deps.storage, ibc_denom.clone(), &Empty {})?;
+
+    Ok(Response::new()
+        .add_event(velo_event(VeloAction::AddIbcDenom).add_attribute(attr::IBC_DENOM, ibc_denom)))
+}
+
+fn remove_ibc_denom(
+    deps: DepsMut,
+    info: MessageInfo,
+    ibc_denom: String,
+) -> Result<Response, ContractError> {
+    assert_role(deps.storage, Role::ConfigAdmin, &info.sender)?;
+
+    if !IBC_DENOMS.has(deps.storage, ibc_denom.clone()) {
+        return Err(ContractError::IbcDenomNotAllowed { denom: ibc_denom });
+    }
+
+    IBC_DENOMS.remove(deps.storage, ibc_denom.clone());
+
+    Ok(Response::new().add_event(
+        velo_event(VeloAction::RemoveIbcDenom).add_attribute(attr::IBC_DENOM, ibc_denom),
+    ))
+}
+
+fn remove_hook(deps: DepsMut, info: MessageInfo, address: Addr) -> Result<Response, ContractError> {
+    assert_role(deps.storage, Role::ConfigAdmin, &info.sender)?;
+
+    if !HOOKS.has(deps.storage, address.clone()) {
+        return Err(ContractError::HookNotRegistered {});
+    }
+
+    HOOKS.remove(deps.storage, address.clone());
+
     Ok(Response::new()
-        .add_attribute("velo_action", "cancel-bet")
-        .add_attribute("velo_bet_id", bet_id.to_string()))
+        .add_event(velo_event(VeloAction::RemoveHook).add_attribute(attr::HOOK, address)))
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
         QueryMsg::Ownership {} => to_json_binary(&get_ownership(deps.storage)?),
+        QueryMsg::PendingChanges { start_after, limit } => to_json_binary(
+            &timelock::query_pending_changes::<ConfigChange>(deps.storage, start_after, limit)?,
+        ),
+        QueryMsg::MigrationPlan { from_version } => {
+            to_json_binary(&migration::query_migration_plan(
+                deps.storage,
+                CONTRACT_VERSION,
+                MIGRATIONS,
+                from_version,
+            )?)
+        }
+        QueryMsg::Roles { address } => to_json_binary(&rbac::query_roles(deps.storage, &address)?),
+        QueryMsg::RoleMembers {
+            role,
+            start_after,
+            limit,
+        } => to_json_binary(&rbac::query_role_members(
+            deps.storage,
+            role,
+            start_after,
+            limit,
+        )?),
         QueryMsg::Config {} => to_json_binary(&query_config(deps)?),
         QueryMsg::MyCurrentBets {
             player,
@@ -654,6 +1438,26 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
             limit,
         )?),
         QueryMsg::TotalBets {} => to_json_binary(&query_total_bets(deps)?),
+        QueryMsg::SimulateBetOn {
+            bet_id,
+            option,
+            amount,
+            player,
+        } => to_json_binary(&query_simulate_bet_on(
+            deps, env, bet_id, option, amount, player,
+        )?),
+        QueryMsg::Hooks { start_after, limit } => {
+            to_json_binary(&query_hooks(deps, start_after, limit)?)
+        }
+        QueryMsg::IbcOrigin { player } => {
+            to_json_binary(&IBC_ORIGINS.may_load(deps.storage, player)?)
+        }
+        QueryMsg::IbcDenoms {} => to_json_binary(&query_ibc_denoms(deps)?),
+        QueryMsg::FailedIbcPayout { player } => to_json_binary(
+            &FAILED_IBC_PAYOUTS
+                .may_load(deps.storage, player)?
+                .unwrap_or_default(),
+        ),
     }
 }
 
@@ -661,6 +1465,21 @@ fn query_config(deps: Deps) -> StdResult<Config> {
     CONFIG.load(deps.storage)
 }
 
+fn query_ibc_denoms(deps: Deps) -> StdResult<IbcDenomsResponse> {
+    let denom = denom_key(&CONFIG.load(deps.storage)?.token_denom);
+    let ibc_denoms = IBC_DENOMS
+        .keys(deps.storage, None, None, Order::Ascending)
+        .map(|res| {
+            res.map(|ibc_denom| IbcDenom {
+                ibc_denom,
+                denom: denom.clone(),
+            })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(IbcDenomsResponse { ibc_denoms })
+}
+
 fn query_my_current_bets(
     deps: Deps,
     player: Addr,
@@ -778,7 +1597,7 @@ fn query_my_pending_reward(deps: Deps, player: Addr) -> StdResult<Uint128> {
             None => continue,
         };
 
-        let bet_amount = finished_bet.current_bet_amounts.values().sum::<Uint128>();
+        let bet_amount = finished_bet.prize();
 
         // Hasn't won this round and it's not cancelled
         if finished_bet.result_option.unwrap_or_default() != game.option && !finished_bet.cancelled
@@ -818,7 +1637,7 @@ fn query_my_pending_reward_rounds(
             None => continue,
         };
 
-        let bet_amount = finished_bet.current_bet_amounts.values().sum::<Uint128>();
+        let bet_amount = finished_bet.prize();
 
         // Hasn't won this round and it's not cancelled
         if finished_bet.result_option.unwrap_or_default() != game.option && !finished_bet.cancelled
@@ -868,7 +1687,7 @@ fn query_my_pending_reward_rounds_by_topic(
             continue;
         }
 
-        let bet_amount = finished_bet.current_bet_amounts.values().sum::<Uint128>();
+        let bet_amount = finished_bet.prize();
 
         // Hasn't won this round and it's not cancelled
         if finished_bet.result_option.unwrap_or_default() != game.option && !finished_bet.cancelled
@@ -908,7 +1727,7 @@ fn query_my_pending_reward_round(
 
     let game = bet_info_storage().load(deps.storage, bet_info_key)?;
 
-    let bet_amount = finished_bet.current_bet_amounts.values().sum::<Uint128>();
+    let bet_amount = finished_bet.prize();
 
     // Hasn't won this round and it's not cancelled
     if finished_bet.result_option.unwrap_or_default() != game.option && !finished_bet.cancelled {
@@ -1031,17 +1850,192 @@ fn query_total_bets(deps: Deps) -> StdResult<Uint128> {
     Ok(Uint128::from(NEXT_BET_ID.load(deps.storage)? - 1))
 }
 
+fn query_hooks(
+    deps: Deps,
+    start_after: Option<Addr>,
+    limit: Option<u32>,
+) -> StdResult<HooksResponse> {
+    let limit = limit.unwrap_or(MAX_PAGE_LIMIT).min(MAX_PAGE_LIMIT) as usize;
+    let hooks = HOOKS
+        .keys(
+            deps.storage,
+            start_after.map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .take(limit)
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(HooksResponse { hooks })
+}
+
+fn query_simulate_bet_on(
+    deps: Deps,
+    env: Env,
+    bet_id: Uint128,
+    option: String,
+    amount: Uint128,
+    player: Option<Addr>,
+) -> StdResult<SimulateBetOnResponse> {
+    let rejected = |e: ContractError| StdError::generic_err(e.to_string());
+
+    if amount.is_zero() {
+        return Err(StdError::generic_err(
+            "Amount to simulate must be higher than 0",
+        ));
+    }
+
+    if amount < CONFIG.load(deps.storage)?.minimum_bet {
+        return Err(rejected(ContractError::BetUnderMinBetAmount {}));
+    }
+
+    let mut bet = UNFINISHED_BETS
+        .load(deps.storage, bet_id.u128())
+        .map_err(|_| rejected(ContractError::BetNotFound {}))?;
+    if env.block.time.seconds() > bet.end_bet_timestamp {
+        return Err(rejected(ContractError::BetAlreadyFinished {}));
+    }
+
+    // The player's existing stake is added, they can only increase it on the same option
+    let mut amount_bet = amount;
+    if let Some(player) = player {
+        if let Some(bet_info) =
+            bet_info_storage().may_load(deps.storage, bet_info_key(bet_id.u128(), &player))?
+        {
+            if bet_info.option != option {
+                return Err(rejected(ContractError::CantIncreaseBetOnDifferentOption {}));
+            }
+            amount_bet += bet_info.amount;
+        }
+    }
+
+    let option_amount = bet
+        .current_bet_amounts
+        .get_mut(&option)
+        .ok_or_else(|| rejected(ContractError::InvalidOption {}))?;
+    *option_amount = option_amount.checked_add(amount)?;
+    let option_amount = *option_amount;
+
+    // Sponsorships are added to the prize if the option wins
+    let bet_amount = bet.prize().checked_add(bet.sponsored())?;
+    let gross_payout = bet_amount.multiply_ratio(amount_bet, option_amount);
+    let fee = compute_gaming_fee(deps, gross_payout)?;
+
+    Ok(SimulateBetOnResponse {
+        current_bet_amounts: bet.current_bet_amounts,
+        total_stake: amount_bet,
+        multiplier: Decimal::checked_from_ratio(gross_payout, amount_bet)
+            .map_err(|e| StdError::generic_err(e.to_string()))?,
+        gross_payout,
+        fee,
+        net_payout: gross_payout.checked_sub(fee)?,
+    })
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn migrate(deps: DepsMut, _env: Env, MigrateMsg {}: MigrateMsg) -> StdResult<Response> {
-    let version = cw2::get_contract_version(deps.storage)?;
-    if version.contract != CONTRACT_NAME {
-        return Err(StdError::generic_err("Can only upgrade from same type"));
+pub fn migrate(deps: DepsMut, env: Env, msg: MigrateMsg) -> Result<Response, ContractError> {
+    let plan = migration::migrate(
+        deps,
+        &env,
+        CONTRACT_NAME,
+        CONTRACT_VERSION,
+        MIGRATIONS,
+        &msg,
+    )?;
+
+    Ok(Response::new().add_event(
+        velo_event(VeloAction::Migrate)
+            .add_attribute(attr::FROM_VERSION, plan.from_version)
+            .add_attribute(attr::CONTRACT_VERSION, plan.to_version)
+            .add_attribute(attr::STEPS, plan.steps.join(",")),
+    ))
+}
+
+/// Config stored before CW20 tokens and the XP per USD, with a native token to bet with
+#[cw_serde]
+struct LegacyConfig {
+    minimum_bet: Uint128,
+    gaming_fee: Uint128,
+    token_denom: String,
+    users_contract: Addr,
+    exp_per_denom_bet: u64,
+    exp_per_denom_won: u64,
+    fee_receiver_wallet_list: Vec<WalletInfo>,
+}
+
+// The token becomes a native denom, with the XP per USD of the migrate message since XP per
+// token can't be converted
+fn migrate_config_denom(deps: DepsMut, _env: &Env, msg: &MigrateMsg) -> StdResult<()> {
+    let Some(raw) = deps.storage.get(CONFIG.as_slice()) else {
+        return Ok(());
+    };
+    if from_json::<Config>(&raw).is_ok() {
+        return Ok(());
     }
-    cw2::set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
-    Ok(Response::default())
+    let legacy: LegacyConfig = from_json(&raw)?;
+
+    let experience = msg.experience.clone().ok_or_else(|| {
+        StdError::generic_err("experience is required to migrate a config without it")
+    })?;
+    experience.validate()?;
+
+    CONFIG.save(
+        deps.storage,
+        &Config {
+            minimum_bet: legacy.minimum_bet,
+            gaming_fee: legacy.gaming_fee,
+            token_denom: Denom::Native(legacy.token_denom),
+            users_contract: legacy.users_contract,
+            experience,
+            elo_k_factor: 0,
+            fee_receiver_wallet_list: legacy.fee_receiver_wallet_list,
+        },
+    )
+}
+
+// Config changes are timelocked from now on, the delay can be set while migrating
+fn migrate_timelock_delay(deps: DepsMut, _env: &Env, msg: &MigrateMsg) -> StdResult<()> {
+    if let Some(seconds) = msg.timelock_delay {
+        timelock::set_delay(deps.storage, seconds)?;
+    }
+
+    Ok(())
 }
 
 // Helpers
+fn load_hooks(storage: &dyn Storage) -> StdResult<Vec<Addr>> {
+    HOOKS.keys(storage, None, None, Order::Ascending).collect()
+}
+
+fn velo_event(action: VeloAction) -> VeloEvent {
+    VeloEvent::new(VeloContract::DaoBets, action)
+}
+
+/// Elo only moves on bets that were resolved, cancelled ones are just refunded
+fn record_elo(elo_delta: &mut EloDelta, k_factor: u64, finished_bet: &Bet, game: &BetInfo) {
+    let Some(result_option) = &finished_bet.result_option else {
+        return;
+    };
+
+    if finished_bet.cancelled {
+        return;
+    }
+
+    let side_amount = finished_bet
+        .current_bet_amounts
+        .get(&game.option)
+        .copied()
+        .unwrap_or_default();
+    let pool_amount = finished_bet.current_bet_amounts.values().sum::<Uint128>();
+
+    elo_delta.record(
+        k_factor,
+        side_amount,
+        pool_amount,
+        *result_option == game.option,
+    );
+}
+
 fn compute_gaming_fee(deps: Deps, amount: Uint128) -> StdResult<Uint128> {
     let gaming_fee = CONFIG.load(deps.storage)?.gaming_fee;
 
@@ -1,8 +1,11 @@
 use cw_ownable::OwnershipError;
 use cw_utils::PaymentError;
+use general::migration::MigrationError;
+use general::rbac::RbacError;
+use general::timelock::TimelockError;
 use thiserror::Error;
 
-use cosmwasm_std::{OverflowError, StdError};
+use cosmwasm_std::{OverflowError, StdError, Uint128};
 
 #[derive(Error, Debug, PartialEq)]
 pub enum ContractError {
@@ -15,12 +18,30 @@ pub enum ContractError {
     #[error(transparent)]
     Ownership(#[from] OwnershipError),
 
+    #[error(transparent)]
+    Rbac(#[from] RbacError),
+
+    #[error(transparent)]
+    Timelock(#[from] TimelockError),
+
+    #[error(transparent)]
+    Migration(#[from] MigrationError),
+
     #[error("{0}")]
     Overflow(#[from] OverflowError),
 
     #[error("The sum of wallet ratio is not equal to 1")]
     WrongRatio {},
 
+    #[error("Gaming fee can't be higher than {}", max)]
+    GamingFeeTooHigh { max: Uint128 },
+
+    #[error("Minimum bet must be higher than 0")]
+    InvalidMinimumBet {},
+
+    #[error("Wrong denom sent")]
+    InvalidFunds {},
+
     #[error("Need to bet more than minimum bet amount")]
     BetUnderMinBetAmount {},
 
@@ -38,4 +59,28 @@ pub enum ContractError {
 
     #[error("There's nothing to claim")]
     NothingToClaim {},
+
+    #[error("Sender is not the ibc-hooks address of the original sender and channel")]
+    InvalidIbcSender {},
+
+    #[error("Denom {} received through IBC is not accepted", denom)]
+    IbcDenomNotAllowed { denom: String },
+
+    #[error("Winnings of this player are not sent back to another chain")]
+    NotReturnedToOrigin {},
+
+    #[error("IBC denom {} must be the native token of the game", ibc_denom)]
+    IbcDenomMismatch { ibc_denom: String },
+
+    #[error("No failed IBC payout to send again")]
+    NoFailedIbcPayout {},
+
+    #[error("Hook is already registered")]
+    HookAlreadyRegistered {},
+
+    #[error("Hook is not registered")]
+    HookNotRegistered {},
+
+    #[error("Can't register more than {} hooks", max)]
+    TooManyHooks { max: usize },
 }
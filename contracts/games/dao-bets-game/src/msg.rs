@@ -1,7 +1,14 @@
+use std::collections::HashMap;
+
 use cosmwasm_schema::{cw_serde, QueryResponses};
 use cosmwasm_std::{Addr, Decimal, Uint128};
+use cw20::Cw20ReceiveMsg;
 use cw_ownable::{cw_ownable_execute, cw_ownable_query};
-use dao_bets::dao_bets::{Bet, BetInfo, BetOption, Config};
+use dao_bets::dao_bets::{Bet, BetInfo, BetOption, Config, ConfigChange, ConfigUpdate};
+use general::experience::ExperienceConfig;
+use general::migration::MigrationPlanResponse;
+use general::rbac::{Role, RoleMembersResponse, RolesResponse};
+use general::timelock::PendingChangesResponse;
 
 #[cw_serde]
 pub struct InstantiateMsg {
@@ -12,8 +19,21 @@ pub struct InstantiateMsg {
 #[cw_ownable_execute]
 #[cw_serde]
 pub enum ExecuteMsg {
+    // Fields not set are kept. Queued as a pending change when the timelock delay is set
     UpdateConfig {
-        config: Config,
+        config: ConfigUpdate,
+    },
+    // Applies a pending change once its delay has passed, anyone can execute it
+    ExecuteChange {
+        id: u64,
+    },
+    // Owner actions to drop a pending change and to set the delay of sensitive changes.
+    // Increases of the delay apply at once, decreases are queued with the current delay
+    CancelChange {
+        id: u64,
+    },
+    UpdateTimelockDelay {
+        seconds: u64,
     },
     BetOn {
         bet_id: Uint128,
@@ -52,6 +72,62 @@ pub enum ExecuteMsg {
     CancelBet {
         bet_id: Uint128,
     },
+    // Escrow the funds attached as a prize for the winners of a bet, refunded if nobody wins
+    Sponsor {
+        bet_id: Uint128,
+    },
+    // Owner actions to manage contracts notified of bets, settlements and claims
+    AddHook {
+        address: Addr,
+    },
+    RemoveHook {
+        address: Addr,
+    },
+    // Bet with CW20 tokens, the message is a ReceiveMsg
+    Receive(Cw20ReceiveMsg),
+    // Bet placed from another chain, as the wasm memo of an ICS-20 transfer handled by ibc-hooks.
+    // The player is the address ibc-hooks derives for the original sender and channel
+    IbcBetOn {
+        channel: String,
+        original_sender: String,
+        bet_id: Uint128,
+        option: String,
+        return_to_origin: bool,
+    },
+    // Settle winnings of a player that bet from another chain and asked for them to be sent back.
+    // Anyone can relay it, the winnings always go to the origin chain address
+    CollectIbcWinnings {
+        player: Addr,
+    },
+    // Send again winnings whose transfer back to the origin chain failed or timed out.
+    // Anyone can relay it, like CollectIbcWinnings
+    RetryIbcPayout {
+        player: Addr,
+    },
+    // Owner actions to accept denoms received through IBC as the token of the game. Only the
+    // native denom of the game token can be accepted, as bets and winnings are in that token
+    AddIbcDenom {
+        ibc_denom: String,
+    },
+    RemoveIbcDenom {
+        ibc_denom: String,
+    },
+    // Owner actions to grant and revoke the roles of general::rbac
+    GrantRole {
+        role: Role,
+        address: String,
+    },
+    RevokeRole {
+        role: Role,
+        address: String,
+    },
+}
+
+// Bets placed with CW20 tokens, the amount bet is the amount of tokens sent
+#[cw_serde]
+pub enum ReceiveMsg {
+    BetOn { bet_id: Uint128, option: String },
+    Sponsor { bet_id: Uint128 },
 }
 
 #[cw_ownable_query]
@@ -131,6 +207,41 @@ pub enum QueryMsg {
     TotalSpent { player: Addr },
     #[returns(Uint128)]
     TotalBets {},
+    /// With a player the simulation adds to their existing bet and fails where their bet would
+    #[returns(SimulateBetOnResponse)]
+    SimulateBetOn {
+        bet_id: Uint128,
+        option: String,
+        amount: Uint128,
+        player: Option<Addr>,
+    },
+    #[returns(general::hooks::HooksResponse)]
+    Hooks {
+        start_after: Option<Addr>,
+        limit: Option<u32>,
+    },
+    #[returns(Option<general::ibc::IbcOrigin>)]
+    IbcOrigin { player: Addr },
+    #[returns(general::ibc::IbcDenomsResponse)]
+    IbcDenoms {},
+    #[returns(Uint128)]
+    FailedIbcPayout { player: Addr },
+    #[returns(PendingChangesResponse<ConfigChange>)]
+    PendingChanges {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    // Migration steps this code runs when migrating from from_version, the stored version if not set
+    #[returns(MigrationPlanResponse)]
+    MigrationPlan { from_version: Option<String> },
+    #[returns(RolesResponse)]
+    Roles { address: Addr },
+    #[returns(RoleMembersResponse)]
+    RoleMembers {
+        role: Role,
+        start_after: Option<Addr>,
+        limit: Option<u32>,
+    },
 }
 
 #[cw_serde]
@@ -144,6 +255,20 @@ pub struct PendingRewardRoundsResponse {
     pub pending_reward_total: Uint128,
 }
 
+#[cw_serde]
+pub struct SimulateBetOnResponse {
+    // Amounts bet on each option after placing the bet
+    pub current_bet_amounts: HashMap<String, Uint128>,
+    // Total amount the player would have on the option, including their existing stake
+    pub total_stake: Uint128,
+    // Gross payout divided by the total stake
+    pub multiplier: Decimal,
+    // What would be claimable if the chosen option wins, before fees
+    pub gross_payout: Uint128,
+    pub fee: Uint128,
+    pub net_payout: Uint128,
+}
+
 #[cw_serde]
 pub struct ClaimInfoResponse {
     pub claim_info: Vec<ClaimInfo>,
@@ -156,8 +281,18 @@ pub struct ClaimInfo {
     pub claimed_amount: Uint128,
 }
 
+// Optional params of the migration steps, see general::migration
 #[cw_serde]
-pub struct MigrateMsg {}
+#[derive(Default)]
+pub struct MigrateMsg {
+    // Timelock delay of config changes, in seconds
+    #[serde(default)]
+    pub timelock_delay: Option<u64>,
+    // XP of a config stored before the XP per USD, which had XP per token instead. Required
+    // when migrating from such a config
+    #[serde(default)]
+    pub experience: Option<ExperienceConfig>,
+}
 
 #[cw_serde]
 pub struct WalletInfo {
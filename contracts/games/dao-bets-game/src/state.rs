@@ -1,6 +1,7 @@
-use cosmwasm_std::{Addr, Uint128};
+use cosmwasm_std::{Addr, Empty, Uint128};
 use cw_storage_plus::{Index, IndexList, IndexedMap, Item, Map, MultiIndex};
 use dao_bets::dao_bets::{Bet, BetInfo, BetInfoKey, ClaimInfo, ClaimInfoKey, Config};
+use general::ibc::{IbcOrigin, IbcPayout};
 
 /// Top level storage key. Values must not conflict.
 /// Each key is only one byte long to ensure we use the smallest possible storage keys.
@@ -11,6 +12,11 @@ pub enum TopKey {
     UnfinishedBets = b'2',
     FinishedBets = b'3',
     TotalsSpent = b'4',
+    Hooks = b'5',
+    IbcOrigins = b'6',
+    IbcDenoms = b'7',
+    IbcPayouts = b'8',
+    FailedIbcPayouts = b'9',
 }
 
 impl TopKey {
@@ -61,6 +67,21 @@ pub const FINISHED_BETS: IndexedMap<u128, Bet, BetIndexes> = IndexedMap::new(
 
 pub const TOTALS_SPENT: Map<Addr, Uint128> = Map::new(TopKey::TotalsSpent.as_str());
 
+// Listener contracts notified of bets, settlements and claims
+pub const HOOKS: Map<Addr, Empty> = Map::new(TopKey::Hooks.as_str());
+
+// Map player -> origin of the player's latest bet placed from another chain
+pub const IBC_ORIGINS: Map<Addr, IbcOrigin> = Map::new(TopKey::IbcOrigins.as_str());
+
+// Denoms received through IBC that are accepted as the token of the game, always its native denom
+pub const IBC_DENOMS: Map<String, Empty> = Map::new(TopKey::IbcDenoms.as_str());
+
+// Map (channel, sequence) -> winnings sent back to an origin chain, until the transfer is acknowledged
+pub const IBC_PAYOUTS: Map<(String, u64), IbcPayout> = Map::new(TopKey::IbcPayouts.as_str());
+
+// Map player -> winnings whose transfer back failed, to be sent again
+pub const FAILED_IBC_PAYOUTS: Map<Addr, Uint128> = Map::new(TopKey::FailedIbcPayouts.as_str());
+
 /// Defines indexes for accessing bids
 pub struct BetInfoIndexes<'a> {
     pub player: MultiIndex<'a, Addr, BetInfo, BetInfoKey>,
@@ -0,0 +1,224 @@
+use std::collections::BTreeMap;
+
+use cosmwasm_std::{to_json_binary, Addr, DepsMut, Order, Response, StdResult, Storage, WasmMsg};
+use cw_storage_plus::Bound;
+use general::elo::EloDelta;
+use general::events::{attr, VeloAction};
+use general::users::ExecuteMsg::AddExperienceAndElo;
+use prediction::prediction_game::{find_pool, BetInfo, FinishedRound, RoundSummary};
+
+use crate::contract::{record_elo, velo_event};
+use crate::error::ContractError;
+use crate::state::{
+    bet_info_storage, claim_info_storage, CompactionCursor, COMPACTION_CURSOR, CONFIG, POSITIONS,
+    ROUNDS, ROUND_SUMMARIES,
+};
+
+const DEFAULT_COMPACT_LIMIT: u32 = 100;
+// Claims and bets removed or checked per call, whatever rounds they belong to
+const MAX_COMPACT_LIMIT: u32 = 300;
+
+/// Prunes the claim records and lost bets of the finished rounds older than the round retention,
+/// and replaces the rounds with nothing left to claim by their summary. Goes through up to
+/// `limit` records and resumes from where the previous call stopped
+pub fn execute_compact_rounds(
+    deps: DepsMut,
+    limit: Option<u32>,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.round_retention == 0 {
+        return Err(ContractError::CompactionDisabled {});
+    }
+
+    // The latest `round_retention` finished rounds are kept in full
+    let latest = ROUNDS
+        .keys(deps.storage, None, None, Order::Descending)
+        .next()
+        .transpose()?
+        .unwrap_or_default();
+    let cutoff = (latest + 1).saturating_sub(u128::from(config.round_retention));
+
+    let mut budget = limit
+        .unwrap_or(DEFAULT_COMPACT_LIMIT)
+        .min(MAX_COMPACT_LIMIT) as usize;
+    let mut cursor = match COMPACTION_CURSOR.may_load(deps.storage)? {
+        Some(cursor) => Some(cursor),
+        None => next_round(deps.storage, None, cutoff)?,
+    };
+
+    let mut compacted: Vec<String> = vec![];
+    let mut pruned_bets = 0u64;
+    let mut pruned_claims = 0u64;
+    let mut burned_positions: Vec<String> = vec![];
+    // Losses of the pruned bets, they would never be recorded otherwise
+    let mut elo_deltas: BTreeMap<Addr, EloDelta> = BTreeMap::new();
+    while budget > 0 {
+        let Some(current) = cursor.as_mut() else {
+            break;
+        };
+        let round_id = current.round_id;
+        // Compacted rounds and the ones within the retention since the call that saved the cursor
+        let Some(round) = ROUNDS.may_load(deps.storage, round_id)? else {
+            cursor = next_round(deps.storage, Some(round_id), cutoff)?;
+            continue;
+        };
+        if round_id >= cutoff {
+            cursor = None;
+            break;
+        }
+
+        // Claim records are all removed, so the next call starts from the first one left
+        let claims = claim_info_storage()
+            .idx
+            .round_id
+            .prefix(round_id)
+            .keys(deps.storage, None, None, Order::Ascending)
+            .take(budget)
+            .collect::<StdResult<Vec<_>>>()?;
+        for claim_key in claims {
+            claim_info_storage().remove(deps.storage, claim_key)?;
+            pruned_claims += 1;
+            budget -= 1;
+        }
+        if budget == 0 {
+            break;
+        }
+
+        // Bets left to claim are kept, so the next call starts after the last one checked
+        let bets = bet_info_storage()
+            .idx
+            .round_id
+            .prefix(round_id)
+            .range(
+                deps.storage,
+                current
+                    .last_player
+                    .as_ref()
+                    .map(|player| Bound::exclusive((round_id, player.clone()))),
+                None,
+                Order::Ascending,
+            )
+            .take(budget)
+            .collect::<StdResult<Vec<_>>>()?;
+        let round_checked = bets.len() < budget;
+        for (bet_key, bet) in bets {
+            budget -= 1;
+            current.last_player = Some(bet.player.clone());
+            if !bet.sides().iter().all(|side| is_lost(&round, side)) {
+                current.claimable = true;
+                continue;
+            }
+
+            let elo_delta = elo_deltas.entry(bet.player.clone()).or_default();
+            for side in bet.sides() {
+                // Claims don't move the Elo of fixed odds bets either
+                if side.fixed_payout.is_none() {
+                    record_elo(elo_delta, config.elo_k_factor, &round, &side);
+                }
+            }
+            // A lost position can't pay anything to its holder
+            if let Some(token_id) = &bet.token_id {
+                POSITIONS.remove(deps.storage, token_id.clone())?;
+                burned_positions.push(token_id.clone());
+            }
+            bet_info_storage().remove(deps.storage, bet_key)?;
+            pruned_bets += 1;
+        }
+        if !round_checked {
+            break;
+        }
+
+        if !current.claimable {
+            ROUND_SUMMARIES.save(deps.storage, round_id, &RoundSummary::from(&round))?;
+            ROUNDS.remove(deps.storage, round_id)?;
+            compacted.push(round_id.to_string());
+        }
+        cursor = next_round(deps.storage, Some(round_id), cutoff)?;
+    }
+
+    // Once the last round is done the next call starts over, with the rounds claimed since
+    match &cursor {
+        Some(cursor) => COMPACTION_CURSOR.save(deps.storage, cursor)?,
+        None => COMPACTION_CURSOR.remove(deps.storage),
+    }
+
+    let mut resp = Response::new();
+    for (player, elo_delta) in elo_deltas {
+        if let Some(elo) = elo_delta.into_elo() {
+            resp = resp.add_message(WasmMsg::Execute {
+                contract_addr: config.users_contract.to_string(),
+                msg: to_json_binary(&AddExperienceAndElo {
+                    user: player,
+                    experience: 0,
+                    elo: Some(elo),
+                })?,
+                funds: vec![],
+            });
+        }
+    }
+
+    let mut event = velo_event(VeloAction::CompactRounds)
+        .add_attribute(attr::COMPACTED_ROUNDS, compacted.join(","))
+        .add_attribute(attr::PRUNED_BETS, pruned_bets.to_string())
+        .add_attribute(attr::PRUNED_CLAIMS, pruned_claims.to_string());
+    if !burned_positions.is_empty() {
+        event = event.add_attribute(attr::TOKEN_ID, burned_positions.join(","));
+    }
+
+    Ok(resp.add_event(event))
+}
+
+/// Cursor at the start of the first finished round after `after` and older than the cutoff
+fn next_round(
+    storage: &dyn Storage,
+    after: Option<u128>,
+    cutoff: u128,
+) -> StdResult<Option<CompactionCursor>> {
+    let round_id = ROUNDS
+        .keys(
+            storage,
+            after.map(Bound::exclusive),
+            Some(Bound::exclusive(cutoff)),
+            Order::Ascending,
+        )
+        .next()
+        .transpose()?;
+
+    Ok(round_id.map(|round_id| CompactionCursor {
+        round_id,
+        last_player: None,
+        claimable: false,
+    }))
+}
+
+/// Sides of bets with nothing to claim, on the losing side of a round that had a winner
+fn is_lost(round: &FinishedRound, bet: &BetInfo) -> bool {
+    let Some(winner) = &round.winner else {
+        return false;
+    };
+    if *winner == bet.direction {
+        return false;
+    }
+
+    // Parimutuel pools with money on a single side are refunded even when the round has a winner
+    bet.fixed_payout.is_some() || !find_pool(&round.pools, &bet.collateral).is_refund()
+}
+
+/// Merges a page of finished rounds with a page of summaries of compacted rounds, both sorted
+/// in `order`. Compacted and kept rounds can be interleaved, as rounds with bets left to claim
+/// are never compacted
+pub(crate) fn merge_summaries(
+    mut rounds: Vec<FinishedRound>,
+    summaries: Vec<RoundSummary>,
+    order: Order,
+    limit: usize,
+) -> Vec<FinishedRound> {
+    rounds.extend(summaries.into_iter().map(FinishedRound::from));
+    match order {
+        Order::Ascending => rounds.sort_by_key(|round| round.id),
+        Order::Descending => rounds.sort_by_key(|round| std::cmp::Reverse(round.id)),
+    }
+    rounds.truncate(limit);
+
+    rounds
+}
@@ -1,49 +1,87 @@
-use std::cmp::Ordering;
 use std::vec;
 
+use crate::compaction::{execute_compact_rounds, merge_summaries};
+use crate::emergency::{execute_emergency_exit, execute_emergency_refund};
 use crate::error::ContractError;
+use crate::migrations::{execute_continue_migration, MIGRATIONS};
+use crate::positions::{
+    execute_approve, execute_approve_all, execute_claim_position, execute_revoke,
+    execute_revoke_all, execute_send_nft, execute_transfer_nft, mint_position, query_all_nft_info,
+    query_all_operators, query_all_tokens, query_approval, query_approvals, query_contract_info,
+    query_nft_info, query_num_tokens, query_owner_of, query_tokens,
+};
+use crate::sponsorships::{execute_sponsor, query_active_sponsorships, settle_sponsorships};
 use crate::state::{
-    bet_info_key, bet_info_storage, claim_info_key, claim_info_storage, ADMINS, CONFIG, IS_HALTED,
-    LIVE_ROUND, NEXT_ROUND, NEXT_ROUND_ID, PRICE_TICKERS, ROUNDS, ROUND_DENOMS, TOTALS_SPENT,
+    bet_info_key, bet_info_storage, claim_info_key, claim_info_storage, PendingSwap, CONFIG,
+    FAILED_IBC_PAYOUTS, HOOKS, IBC_DENOMS, IBC_ORIGINS, IBC_PAYOUTS, LIVE_ROUND, NEXT_ROUND,
+    NEXT_ROUND_ID, PAUSE, PENDING_SWAP, POSITIONS, PRICE_TICKERS, ROUNDS, ROUND_DENOMS,
+    ROUND_SUMMARIES, TOTALS_SPENT,
 };
-use chrono::DateTime;
+use crate::unclaimed::{query_unclaimed_rounds, query_unclaimed_total, remove_claimed, track_bet};
+use general::migration;
+use general::rbac::{self, assert_role, Role};
+use general::timelock::{self, PendingChange};
 
+use cw20::{Cw20ReceiveMsg, Denom};
 use cw_utils::one_coin;
-use neutron_sdk::bindings::oracle::query::{GetPriceResponse, OracleQuery};
-use neutron_sdk::bindings::oracle::types::CurrencyPair;
 use neutron_sdk::bindings::query::NeutronQuery;
-use prediction::prediction_game::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
+use prediction::prediction_game::msg::{
+    ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg, ReceiveMsg,
+};
+use prediction::prediction_game::{
+    find_pool, BetReceipt, ClaimReceipt, CloseRoundReceipt, Collateral, CollateralPool,
+    CollateralPosition, Config, ConfigChange, ConfigUpdate, Direction, PauseState, RoundPayout,
+};
 use prediction::prediction_game::{
-    AdminsResponse, BetInfo, ClaimInfo, ClaimInfoResponse, ConfigResponse, MyGameResponse,
-    PendingRefundableAmountResponse, PendingRefundableAmountRoundsResponse, PendingRewardResponse,
-    PendingRewardRoundsResponse, RoundDenomsResponse, RoundUsersResponse, TickersResponse,
-    TotalSpentResponse, WalletInfo,
+    BetInfo, ClaimInfo, ClaimInfoResponse, ConfigResponse, FinishedRoundsResponse, MyGameResponse,
+    OrderBy, PendingRefundableAmountResponse, PendingRefundableAmountRoundsResponse,
+    PendingRewardResponse, PendingRewardRoundsResponse, RoundDenomsResponse, RoundUsersResponse,
+    SimulateBetResponse, TickersResponse, TotalSpentResponse, WalletInfo,
 };
-use prediction::prediction_game::{Config, Direction};
 
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 use cosmwasm_std::{
-    coins, to_json_binary, Addr, BankMsg, Binary, Decimal, Deps, DepsMut, Env, Event, Int128,
-    MessageInfo, Order, Response, StdError, StdResult, Uint128, WasmMsg,
+    from_json, to_json_binary, Addr, Attribute, Binary, Decimal, Deps, DepsMut, Empty, Env, Int128,
+    MessageInfo, Order, Reply, Response, StdError, StdResult, Storage, SubMsg, Timestamp, Uint128,
+    WasmMsg,
 };
 use cw_storage_plus::Bound;
+use general::asset::{query_balance, transfer_msg};
+use general::elo::EloDelta;
+use general::events::{attr, patch_field, VeloAction, VeloContract, VeloEvent};
+use general::experience::{compute_experience, ExperienceAction};
+use general::hooks::{
+    hook_messages, GameHookMsg, HookPayout, HooksResponse, HOOK_REPLY_ID, MAX_HOOKS,
+};
+use general::ibc::{
+    intermediate_sender, payout_msg, sent_packet, IbcDenom, IbcDenomsResponse, IbcHooksSudoMsg,
+    IbcOrigin, IbcPayout, IBC_PAYOUT_REPLY_ID,
+};
+use general::oracle::query_usd_price;
+use general::router::{RouterExecuteMsg, SwapOperation};
 use general::users::ExecuteMsg::AddExperienceAndElo;
 use prediction::prediction_game::{FinishedRound, LiveRound, NextRound};
 use prediction::prediction_game::{MyCurrentPositionResponse, StatusResponse};
+use vault::liquidity_vault::msg::{ExecuteMsg as VaultExecuteMsg, QueryMsg as VaultQueryMsg};
+use vault::liquidity_vault::{CapacityResponse, Config as VaultConfig};
 
 const FEE_PRECISION: u128 = 100;
+// 10%, gaming fees have 2 decimals of precision
+const MAX_GAMING_FEE: u128 = 1_000;
 
 // Pagination info for queries
-const MAX_PAGE_LIMIT: u32 = 250;
+pub(crate) const MAX_PAGE_LIMIT: u32 = 250;
 
 const CONTRACT_NAME: &str = env!("CARGO_PKG_NAME");
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
-const USD_TICKER: &str = "USD";
-const PRICE_DECIMALS: u64 = 18;
 const MAX_OLD_PRICE_TIME: u64 = 10;
 
+// Reply id of the swap router submessage of swap and bet, hooks use general::hooks::HOOK_REPLY_ID
+// and IBC payouts general::ibc::IBC_PAYOUT_REPLY_ID
+const SWAP_REPLY_ID: u64 = 2;
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
     deps: DepsMut,
@@ -53,29 +91,18 @@ pub fn instantiate(
 ) -> Result<Response, ContractError> {
     cw2::set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
 
-    if !msg.config.dev_wallet_list.is_empty() {
-        let mut total_ratio = Decimal::zero();
-        for dev_wallet in msg.config.dev_wallet_list.clone() {
-            total_ratio += dev_wallet.ratio;
-        }
-
-        if total_ratio != Decimal::one() {
-            return Err(ContractError::WrongRatio {});
-        }
-    }
+    validate_config(deps.as_ref(), &msg.config, None)?;
 
     CONFIG.save(deps.storage, &msg.config)?;
     NEXT_ROUND_ID.save(deps.storage, &0u128)?;
-    IS_HALTED.save(deps.storage, &false)?;
-    let mut admins = vec![info.sender];
-    if let Some(admins_list) = msg.extra_admins {
-        for admin in admins_list {
-            deps.api.addr_validate(admin.as_str())?;
-            admins.push(admin);
-        }
-    }
-
-    ADMINS.save(deps.storage, &admins)?;
+    PAUSE.save(deps.storage, &PauseState::default())?;
+    let owner = msg.owner.unwrap_or(info.sender);
+    rbac::initialize(
+        deps.storage,
+        deps.api,
+        owner.as_str(),
+        &msg.extra_admins.unwrap_or_default(),
+    )?;
 
     if msg.denom_tickers.is_empty() {
         return Err(ContractError::DenomsEmpty {});
@@ -89,21 +116,34 @@ pub fn instantiate(
         )?;
     }
 
-    let bet_token_denoms = msg.denom_tickers.iter().map(|x| x.denom.clone()).collect();
+    let bet_token_denoms: Vec<String> = msg.denom_tickers.iter().map(|x| x.denom.clone()).collect();
+    let bet_token_denoms_attr = bet_token_denoms.join(",");
 
     ROUND_DENOMS.save(deps.storage, &bet_token_denoms)?;
 
-    Ok(Response::new().add_attribute("velo_method", "instantiate_prediction_game"))
+    Ok(Response::new().add_event(
+        velo_event(VeloAction::Instantiate).add_attribute(attr::DENOMS, bet_token_denoms_attr),
+    ))
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn migrate(deps: DepsMut, _env: Env, MigrateMsg {}: MigrateMsg) -> StdResult<Response> {
-    let version = cw2::get_contract_version(deps.storage)?;
-    if version.contract != CONTRACT_NAME {
-        return Err(StdError::generic_err("Can only upgrade from same type"));
-    }
-    cw2::set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
-    Ok(Response::default())
+pub fn migrate(deps: DepsMut, env: Env, msg: MigrateMsg) -> Result<Response, ContractError> {
+    let plan = migration::migrate(
+        deps,
+        &env,
+        CONTRACT_NAME,
+        CONTRACT_VERSION,
+        MIGRATIONS,
+        &msg,
+    )?;
+
+    Ok(Response::new().add_event(
+        velo_event(VeloAction::Migrate)
+            .add_attribute(attr::FROM_VERSION, plan.from_version)
+            .add_attribute(attr::CONTRACT_VERSION, plan.to_version)
+            .add_attribute(attr::STEPS, plan.steps.join(","))
+            .add_attribute(attr::REMAINING_STEPS, plan.remaining.join(",")),
+    ))
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
@@ -113,39 +153,122 @@ pub fn execute(
     info: MessageInfo,
     msg: ExecuteMsg,
 ) -> Result<Response, ContractError> {
+    // The state is only partly rewritten until the migration is done
+    if !matches!(msg, ExecuteMsg::ContinueMigration { .. }) {
+        migration::assert_migrated(deps.storage)?;
+    }
+
     match msg {
         ExecuteMsg::UpdateConfig { config } => {
-            execute_update_config(deps.into_empty(), info, config)
+            execute_update_config(deps.into_empty(), env, info, config)
         }
-        ExecuteMsg::BetBear { round_id, amount } => execute_bet(
-            deps.into_empty(),
+        ExecuteMsg::ExecuteChange { id } => execute_change(deps.into_empty(), env, id),
+        ExecuteMsg::CancelChange { id } => execute_cancel_change(deps.into_empty(), info, id),
+        ExecuteMsg::UpdateTimelockDelay { seconds } => {
+            execute_update_timelock_delay(deps.into_empty(), env, info, seconds)
+        }
+        ExecuteMsg::BetBear { round_id, amount } => {
+            execute_bet_native(deps, info, env, round_id, Direction::Bear, amount)
+        }
+        ExecuteMsg::BetBull { round_id, amount } => {
+            execute_bet_native(deps, info, env, round_id, Direction::Bull, amount)
+        }
+        ExecuteMsg::Receive(wrapper) => execute_receive(deps, info, env, wrapper),
+        ExecuteMsg::SwapAndBet {
+            round_id,
+            direction,
+            collateral,
+            operations,
+            minimum_receive,
+            max_spread,
+        } => execute_swap_and_bet(
+            deps,
             info,
             env,
             round_id,
-            Direction::Bear,
-            amount,
+            direction,
+            collateral,
+            operations,
+            minimum_receive,
+            max_spread,
         ),
-        ExecuteMsg::BetBull { round_id, amount } => execute_bet(
-            deps.into_empty(),
+        ExecuteMsg::CloseRound {} => execute_close_round(deps, env),
+        ExecuteMsg::CollectWinnings { collateral } => {
+            execute_collect_winnings(deps, env, info.sender, collateral)
+        }
+        ExecuteMsg::CollectionWinningRound { round_id } => {
+            execute_collect_winning_round(deps, env, info, round_id)
+        }
+        ExecuteMsg::IbcBet {
+            channel,
+            original_sender,
+            round_id,
+            direction,
+            return_to_origin,
+        } => execute_ibc_bet(
+            deps,
             info,
             env,
+            channel,
+            original_sender,
             round_id,
-            Direction::Bull,
-            amount,
+            direction,
+            return_to_origin,
         ),
-        ExecuteMsg::CloseRound {} => execute_close_round(deps, env),
-        ExecuteMsg::CollectWinnings {} => execute_collect_winnings(deps.into_empty(), info),
-        ExecuteMsg::CollectionWinningRound { round_id } => {
-            execute_collect_winning_round(deps.into_empty(), info, round_id)
+        ExecuteMsg::CollectIbcWinnings { player, collateral } => {
+            execute_collect_ibc_winnings(deps, env, player, collateral)
+        }
+        ExecuteMsg::RetryIbcPayout { player, collateral } => {
+            execute_retry_ibc_payout(deps.into_empty(), env, player, collateral)
+        }
+        ExecuteMsg::AddIbcDenom { ibc_denom } => {
+            execute_add_ibc_denom(deps.into_empty(), info, ibc_denom)
+        }
+        ExecuteMsg::RemoveIbcDenom { ibc_denom } => {
+            execute_remove_ibc_denom(deps.into_empty(), info, ibc_denom)
+        }
+        ExecuteMsg::Halt {} => execute_update_halt(deps.into_empty(), env, info, true),
+        ExecuteMsg::Resume {} => execute_update_halt(deps.into_empty(), env, info, false),
+        ExecuteMsg::UpdatePause {
+            betting,
+            rounds,
+            claims,
+            reason,
+            resume_at,
+        } => execute_update_pause(
+            deps.into_empty(),
+            env,
+            info,
+            PauseState {
+                betting,
+                rounds,
+                claims,
+                reason,
+                resume_at,
+                rounds_paused_at: None,
+                emergency_exit: false,
+            },
+            VeloAction::UpdatePause,
+        ),
+        ExecuteMsg::EmergencyExit {} => execute_emergency_exit(deps.into_empty(), env, info),
+        ExecuteMsg::EmergencyRefund { round_id, limit } => {
+            execute_emergency_refund(deps, env, info, round_id, limit)
         }
-        ExecuteMsg::Halt {} => execute_update_halt(deps.into_empty(), info, true),
-        ExecuteMsg::Resume {} => execute_update_halt(deps.into_empty(), info, false),
-        ExecuteMsg::AddAdmin { new_admin } => execute_add_admin(deps.into_empty(), info, new_admin),
-        ExecuteMsg::RemoveAdmin { old_admin } => {
-            execute_remove_admin(deps.into_empty(), info, old_admin)
+        ExecuteMsg::CompactRounds { limit } => execute_compact_rounds(deps.into_empty(), limit),
+        ExecuteMsg::ContinueMigration { limit } => {
+            execute_continue_migration(deps.into_empty(), env, limit)
+        }
+        ExecuteMsg::UpdateOwnership(action) => {
+            update_ownership(deps.into_empty(), env, info, action)
+        }
+        ExecuteMsg::GrantRole { role, address } => {
+            execute_grant_role(deps.into_empty(), info, role, address)
+        }
+        ExecuteMsg::RevokeRole { role, address } => {
+            execute_revoke_role(deps.into_empty(), info, role, address)
         }
         ExecuteMsg::ModifyDevWallet { new_dev_wallets } => {
-            execute_modify_dev_wallets(deps.into_empty(), info, new_dev_wallets)
+            execute_modify_dev_wallets(deps.into_empty(), env, info, new_dev_wallets)
         }
         ExecuteMsg::AddTicker { denom, ticker } => {
             execute_add_ticker(deps.into_empty(), info, denom, ticker)
@@ -153,47 +276,181 @@ pub fn execute(
         ExecuteMsg::ModifyBetArray { denoms } => {
             execute_modify_bet_array(deps.into_empty(), info, denoms)
         }
+        ExecuteMsg::Sponsor {
+            start_round,
+            end_round,
+            denom,
+            amount_per_round,
+        } => execute_sponsor_native(
+            deps.into_empty(),
+            info,
+            start_round,
+            end_round,
+            denom,
+            amount_per_round,
+        ),
+        ExecuteMsg::ClaimPosition { token_id } => execute_claim_position(deps, env, info, token_id),
+        ExecuteMsg::TransferNft {
+            recipient,
+            token_id,
+        } => execute_transfer_nft(deps.into_empty(), env, info, recipient, token_id),
+        ExecuteMsg::SendNft {
+            contract,
+            token_id,
+            msg,
+        } => execute_send_nft(deps.into_empty(), env, info, contract, token_id, msg),
+        ExecuteMsg::Approve {
+            spender,
+            token_id,
+            expires,
+        } => execute_approve(deps.into_empty(), env, info, spender, token_id, expires),
+        ExecuteMsg::Revoke { spender, token_id } => {
+            execute_revoke(deps.into_empty(), env, info, spender, token_id)
+        }
+        ExecuteMsg::ApproveAll { operator, expires } => {
+            execute_approve_all(deps.into_empty(), env, info, operator, expires)
+        }
+        ExecuteMsg::RevokeAll { operator } => execute_revoke_all(deps.into_empty(), info, operator),
+        ExecuteMsg::AddHook { address } => execute_add_hook(deps.into_empty(), info, address),
+        ExecuteMsg::RemoveHook { address } => execute_remove_hook(deps.into_empty(), info, address),
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn reply(deps: DepsMut<NeutronQuery>, env: Env, msg: Reply) -> Result<Response, ContractError> {
+    match msg.id {
+        // The router sent the collateral, bet all of it. Any error reverts the swap too
+        SWAP_REPLY_ID => reply_swap_and_bet(deps, env),
+        // A listener failed, its state changes are reverted but the game action goes through
+        HOOK_REPLY_ID => {
+            let hook: Addr = from_json(&msg.payload)?;
+            let error = msg.result.into_result().err().unwrap_or_default();
+            Ok(Response::new().add_event(
+                velo_event(VeloAction::HookFailed)
+                    .add_attribute(attr::HOOK, hook)
+                    .add_attribute(attr::ERROR, error),
+            ))
+        }
+        // The winnings left for the origin chain, track them until ibc-hooks reports the outcome
+        IBC_PAYOUT_REPLY_ID => {
+            let payout: IbcPayout = from_json(&msg.payload)?;
+            let response = msg.result.into_result().map_err(StdError::generic_err)?;
+            let (channel, sequence) = sent_packet(&response)?;
+            IBC_PAYOUTS.save(deps.storage, (channel.clone(), sequence), &payout)?;
+
+            Ok(Response::new().add_event(
+                velo_event(VeloAction::IbcPayout)
+                    .add_attribute(attr::PLAYER, payout.player)
+                    .add_attribute(attr::COLLATERAL, payout.denom)
+                    .add_attribute(attr::AMOUNT, payout.amount)
+                    .add_attribute(attr::CHANNEL, channel)
+                    .add_attribute(attr::SEQUENCE, sequence.to_string()),
+            ))
+        }
+        id => Err(StdError::generic_err(format!("Unknown reply id: {}", id)).into()),
+    }
+}
+
+/// Outcome of the winnings sent back to origin chains. Failed and timed out transfers are refunded
+/// to the game by the transfer module, so their amount is credited back to the player
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn sudo(deps: DepsMut, _env: Env, msg: IbcHooksSudoMsg) -> Result<Response, ContractError> {
+    let IbcHooksSudoMsg::IbcLifecycleComplete(outcome) = msg;
+    let (channel, sequence, success) = outcome.outcome();
+
+    // Only payouts are tracked, other transfers have nothing to settle
+    let key = (channel.clone(), sequence);
+    let Some(payout) = IBC_PAYOUTS.may_load(deps.storage, key.clone())? else {
+        return Ok(Response::new());
+    };
+    IBC_PAYOUTS.remove(deps.storage, key);
+
+    if success {
+        return Ok(Response::new());
     }
+
+    FAILED_IBC_PAYOUTS.update(
+        deps.storage,
+        (payout.player.clone(), payout.denom.clone()),
+        |failed| -> StdResult<_> { Ok(failed.unwrap_or_default().checked_add(payout.amount)?) },
+    )?;
+
+    Ok(Response::new().add_event(
+        velo_event(VeloAction::IbcPayoutFailed)
+            .add_attribute(attr::PLAYER, payout.player)
+            .add_attribute(attr::COLLATERAL, payout.denom)
+            .add_attribute(attr::AMOUNT, payout.amount)
+            .add_attribute(attr::CHANNEL, channel)
+            .add_attribute(attr::SEQUENCE, sequence.to_string()),
+    ))
 }
 
-fn execute_collect_winnings(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
+fn execute_collect_winnings(
+    deps: DepsMut<NeutronQuery>,
+    env: Env,
+    player: Addr,
+    collateral: String,
+) -> Result<Response, ContractError> {
+    if PAUSE.load(deps.storage)?.claims {
+        return Err(ContractError::ClaimsPaused {});
+    }
+
     let config = CONFIG.load(deps.storage)?;
+    let collateral = load_collateral(&config, &collateral)?;
     let mut winnings = Uint128::zero();
     let mut resp = Response::new();
 
-    let my_game_list = query_my_games_without_limit(deps.as_ref(), info.sender.clone())?;
-    let live_round = LIVE_ROUND.load(deps.storage)?;
-    let next_round = NEXT_ROUND.load(deps.storage)?;
+    let my_game_list = query_my_games_without_limit(deps.as_ref().into_empty(), player.clone())?;
+    // Both are gone after the emergency exit
+    let live_round = LIVE_ROUND.may_load(deps.storage)?;
+    let next_round = NEXT_ROUND.may_load(deps.storage)?;
     let mut amount_commissionable = Uint128::zero();
+    let mut amount_fixed_odds = Uint128::zero();
+    let mut elo_delta = EloDelta::default();
+    let mut payouts: Vec<RoundPayout> = vec![];
+    let mut settled = false;
 
-    for game in my_game_list.my_game_list {
+    // Each side of a hedged bet is settled as a bet of its own
+    for game in my_game_list.my_game_list.iter().flat_map(|bet| bet.sides()) {
         let round_id = game.round_id;
 
-        if live_round.id == round_id || next_round.id == round_id {
+        if live_round
+            .as_ref()
+            .is_some_and(|round| round.id == round_id)
+            || next_round
+                .as_ref()
+                .is_some_and(|round| round.id == round_id)
+            || game.collateral != collateral.key()
+            || game.token_id.is_some()
+        {
             continue;
         }
 
         let round = ROUNDS.load(deps.storage, round_id.u128())?;
 
-        let pool_shares = round.bear_amount + round.bull_amount;
-        let bet_info_key = bet_info_key(round_id.u128(), &info.sender);
+        let pool = find_pool(&round.pools, &game.collateral);
+        let pool_shares = pool.prize();
+        let bet_info_key = bet_info_key(round_id.u128(), &player);
 
         bet_info_storage().remove(deps.storage, bet_info_key.clone())?;
+        remove_claimed(deps.storage, &game)?;
+        settled = true;
 
-        let claim_info_key = claim_info_key(round_id.u128(), &info.sender);
+        // The house edge is already taken from the fixed payout, no gaming fee is charged on it
+        if let Some(fixed_payout) = game.fixed_payout {
+            let round_winnings = fixed_odds_winnings(&round, &game, fixed_payout);
+            winnings += round_winnings;
+            amount_fixed_odds += round_winnings;
+            if round_winnings > Uint128::zero() {
+                record_claim(deps.storage, &mut payouts, &player, &game, round_winnings)?;
+            }
+            continue;
+        }
 
-        if round.bear_amount == Uint128::zero() || round.bull_amount == Uint128::zero() {
+        if pool.is_refund() {
             winnings += game.amount;
             if game.amount > Uint128::zero() {
-                claim_info_storage().save(
-                    deps.storage,
-                    claim_info_key,
-                    &ClaimInfo {
-                        player: info.sender.clone(),
-                        round_id,
-                        claimed_amount: game.amount,
-                    },
-                )?;
+                record_claim(deps.storage, &mut payouts, &player, &game, game.amount)?;
             }
         } else {
             let round_winnings = match round.winner {
@@ -201,8 +458,9 @@ fn execute_collect_winnings(deps: DepsMut, info: MessageInfo) -> Result<Response
                     /* Only claimable once */
                     match game.direction {
                         Direction::Bull => {
-                            let won_shares = game.amount;
-                            pool_shares.multiply_ratio(won_shares, round.bull_amount)
+                            let won_shares = game.shares();
+                            pool_shares
+                                .multiply_ratio(won_shares, pool.side_shares(&Direction::Bull))
                         }
                         Direction::Bear => Uint128::zero(),
                     }
@@ -212,8 +470,9 @@ fn execute_collect_winnings(deps: DepsMut, info: MessageInfo) -> Result<Response
                     match game.direction {
                         Direction::Bull => Uint128::zero(),
                         Direction::Bear => {
-                            let won_shares = game.amount;
-                            pool_shares.multiply_ratio(won_shares, round.bear_amount)
+                            let won_shares = game.shares();
+                            pool_shares
+                                .multiply_ratio(won_shares, pool.side_shares(&Direction::Bear))
                         }
                     }
                 }
@@ -226,22 +485,16 @@ fn execute_collect_winnings(deps: DepsMut, info: MessageInfo) -> Result<Response
             /* Count it up */
             winnings += round_winnings;
             amount_commissionable += round_winnings;
+            record_elo(&mut elo_delta, config.elo_k_factor, &round, &game);
 
             if round_winnings > Uint128::zero() {
-                claim_info_storage().save(
-                    deps.storage,
-                    claim_info_key,
-                    &ClaimInfo {
-                        player: info.sender.clone(),
-                        round_id,
-                        claimed_amount: round_winnings,
-                    },
-                )?;
+                record_claim(deps.storage, &mut payouts, &player, &game, round_winnings)?;
             }
         }
     }
 
-    if winnings == Uint128::zero() {
+    // Lost bets are settled too, so their Elo loss is recorded even with nothing to pay
+    if !settled {
         return Err(ContractError::Std(StdError::generic_err(
             "Nothing to claim",
         )));
@@ -249,23 +502,32 @@ fn execute_collect_winnings(deps: DepsMut, info: MessageInfo) -> Result<Response
 
     let mut dev_fee = Uint128::zero();
     if amount_commissionable != Uint128::zero() {
-        dev_fee = compute_gaming_fee(deps.as_ref(), amount_commissionable)?;
+        dev_fee = compute_gaming_fee(collateral.gaming_fee, amount_commissionable)?;
         let mut messages_dev_fees = Vec::new();
         for dev_wallet in config.clone().dev_wallet_list {
-            let token_transfer_msg = BankMsg::Send {
-                to_address: dev_wallet.address.to_string(),
-                amount: coins(
-                    dev_fee.mul_floor(dev_wallet.ratio).u128(),
-                    &config.token_denom,
-                ),
-            };
-            messages_dev_fees.push(token_transfer_msg)
+            let amount = dev_fee.mul_floor(dev_wallet.ratio);
+            if !amount.is_zero() {
+                let token_transfer_msg =
+                    transfer_msg(&collateral.denom, &dev_wallet.address, amount)?;
+                messages_dev_fees.push(token_transfer_msg)
+            }
         }
 
+        resp = resp.add_messages(messages_dev_fees);
+    }
+
+    let elo = elo_delta.into_elo();
+    let amount_won = amount_commissionable + amount_fixed_odds;
+    if amount_won != Uint128::zero() || elo.is_some() {
         let experience_message = AddExperienceAndElo {
-            user: info.sender.clone(),
-            experience: amount_commissionable.u128() as u64 * config.exp_per_denom_won,
-            elo: None,
+            user: player.clone(),
+            experience: compute_experience(
+                &deps.querier,
+                &collateral.experience,
+                amount_won,
+                ExperienceAction::Won,
+            )?,
+            elo,
         };
 
         let wasm_message = WasmMsg::Execute {
@@ -274,68 +536,141 @@ fn execute_collect_winnings(deps: DepsMut, info: MessageInfo) -> Result<Response
             funds: vec![],
         };
 
-        resp = resp
-            .add_messages(messages_dev_fees)
-            .add_message(wasm_message)
-            .add_attribute("velo_action", "distribute-dev-rewards")
-            .add_attribute("velo_amount", dev_fee);
+        resp = resp.add_message(wasm_message);
     }
 
     let amount_winnings = winnings.u128() - dev_fee.u128();
-    let msg_send_winnings = BankMsg::Send {
-        to_address: info.sender.to_string(),
-        amount: coins(amount_winnings, &config.token_denom),
+    if amount_winnings != 0 {
+        resp = resp.add_submessage(payout_msg(
+            &env,
+            IBC_ORIGINS.may_load(deps.storage, player.clone())?.as_ref(),
+            &collateral.denom,
+            &player,
+            Uint128::from(amount_winnings),
+        )?);
+    }
+
+    let receipt = ClaimReceipt {
+        player: player.clone(),
+        collateral: collateral.key(),
+        payouts,
+        gross: winnings,
+        fee: dev_fee,
+        net: Uint128::from(amount_winnings),
     };
 
+    let hook_msgs = hook_messages(
+        load_hooks(deps.storage)?,
+        GameHookMsg::Claim {
+            player: player.clone(),
+            denom: receipt.collateral.clone(),
+            payouts: receipt
+                .payouts
+                .iter()
+                .map(|payout| HookPayout {
+                    id: payout.round_id,
+                    amount: payout.amount,
+                })
+                .collect(),
+            gross: receipt.gross,
+            fee: receipt.fee,
+            net: receipt.net,
+        },
+    )?;
+
     Ok(resp
-        .add_message(msg_send_winnings)
-        .add_attribute("velo_action", "collect-winnings")
-        .add_attribute("velo_claimer", info.sender)
-        .add_attribute("velo_amount", amount_winnings.to_string()))
+        .set_data(to_json_binary(&receipt)?)
+        .add_submessages(hook_msgs)
+        .add_event(
+            velo_event(VeloAction::Claim)
+                .add_attribute(attr::PLAYER, player)
+                .add_attribute(attr::COLLATERAL, receipt.collateral.clone())
+                .add_attribute(attr::AMOUNT, amount_winnings.to_string())
+                .add_attribute(attr::FEE, dev_fee),
+        ))
 }
 
 fn execute_collect_winning_round(
-    deps: DepsMut,
+    deps: DepsMut<NeutronQuery>,
+    env: Env,
     info: MessageInfo,
     round_id: Uint128,
 ) -> Result<Response, ContractError> {
+    let bet_info_key = bet_info_key(round_id.u128(), &info.sender);
+    if let Some(bet_info) = bet_info_storage().may_load(deps.storage, bet_info_key)? {
+        // Positions are claimed by their holder with ClaimPosition
+        if let Some(token_id) = bet_info.token_id {
+            return Err(ContractError::PositionTokenized { token_id });
+        }
+    }
+
+    settle_round(deps, env, info.sender.clone(), info.sender, round_id)
+}
+
+/// Settles the bet of `bettor` in a round, paying the winnings to `recipient`
+pub(crate) fn settle_round(
+    deps: DepsMut<NeutronQuery>,
+    env: Env,
+    bettor: Addr,
+    recipient: Addr,
+    round_id: Uint128,
+) -> Result<Response, ContractError> {
+    if PAUSE.load(deps.storage)?.claims {
+        return Err(ContractError::ClaimsPaused {});
+    }
+
     let config = CONFIG.load(deps.storage)?;
     let mut winnings = Uint128::zero();
     let mut resp = Response::new();
 
     let mut my_game_list: Vec<BetInfo> = Vec::new();
 
-    let bet_info_key_round = bet_info_key(round_id.u128(), &info.sender);
+    let bet_info_key_round = bet_info_key(round_id.u128(), &bettor);
     let game = bet_info_storage().may_load(deps.storage, bet_info_key_round)?;
     if let Some(_game) = game {
-        my_game_list.push(_game)
+        my_game_list.extend(_game.sides())
     }
 
+    let collateral = match my_game_list.first() {
+        Some(game) => load_collateral(&config, &game.collateral)?,
+        None => {
+            return Err(ContractError::Std(StdError::generic_err(
+                "Nothing to claim",
+            )))
+        }
+    };
+
     let mut amount_commissionable = Uint128::zero();
+    let mut amount_fixed_odds = Uint128::zero();
+    let mut elo_delta = EloDelta::default();
+    let mut payouts: Vec<RoundPayout> = vec![];
 
     for game in my_game_list {
         let round_id = game.round_id;
         let round = ROUNDS.load(deps.storage, round_id.u128())?;
 
-        let pool_shares = round.bear_amount + round.bull_amount;
-        let bet_info_key = bet_info_key(round_id.u128(), &info.sender);
+        let pool = find_pool(&round.pools, &game.collateral);
+        let pool_shares = pool.prize();
+        let bet_info_key = bet_info_key(round_id.u128(), &bettor);
 
         bet_info_storage().remove(deps.storage, bet_info_key.clone())?;
+        remove_claimed(deps.storage, &game)?;
 
-        let claim_info_key = claim_info_key(round_id.u128(), &info.sender);
+        // The house edge is already taken from the fixed payout, no gaming fee is charged on it
+        if let Some(fixed_payout) = game.fixed_payout {
+            let round_winnings = fixed_odds_winnings(&round, &game, fixed_payout);
+            winnings += round_winnings;
+            amount_fixed_odds += round_winnings;
+            if round_winnings > Uint128::zero() {
+                record_claim(deps.storage, &mut payouts, &bettor, &game, round_winnings)?;
+            }
+            continue;
+        }
 
-        if round.bear_amount == Uint128::zero() || round.bull_amount == Uint128::zero() {
+        if pool.is_refund() {
             winnings += game.amount;
             if game.amount > Uint128::zero() {
-                claim_info_storage().save(
-                    deps.storage,
-                    claim_info_key,
-                    &ClaimInfo {
-                        player: info.sender.clone(),
-                        round_id,
-                        claimed_amount: winnings,
-                    },
-                )?;
+                record_claim(deps.storage, &mut payouts, &bettor, &game, game.amount)?;
             }
         } else {
             let round_winnings = match round.winner {
@@ -343,8 +678,9 @@ fn execute_collect_winning_round(
                     /* Only claimable once */
                     match game.direction {
                         Direction::Bull => {
-                            let won_shares = game.amount;
-                            pool_shares.multiply_ratio(won_shares, round.bull_amount)
+                            let won_shares = game.shares();
+                            pool_shares
+                                .multiply_ratio(won_shares, pool.side_shares(&Direction::Bull))
                         }
                         Direction::Bear => Uint128::zero(),
                     }
@@ -354,8 +690,9 @@ fn execute_collect_winning_round(
                     match game.direction {
                         Direction::Bull => Uint128::zero(),
                         Direction::Bear => {
-                            let won_shares = game.amount;
-                            pool_shares.multiply_ratio(won_shares, round.bear_amount)
+                            let won_shares = game.shares();
+                            pool_shares
+                                .multiply_ratio(won_shares, pool.side_shares(&Direction::Bear))
                         }
                     }
                 }
@@ -368,45 +705,41 @@ fn execute_collect_winning_round(
             /* Count it up */
             winnings += round_winnings;
             amount_commissionable += round_winnings;
+            record_elo(&mut elo_delta, config.elo_k_factor, &round, &game);
             if round_winnings > Uint128::zero() {
-                claim_info_storage().save(
-                    deps.storage,
-                    claim_info_key,
-                    &ClaimInfo {
-                        player: info.sender.clone(),
-                        round_id,
-                        claimed_amount: winnings,
-                    },
-                )?;
+                record_claim(deps.storage, &mut payouts, &bettor, &game, round_winnings)?;
             }
         }
     }
 
-    if winnings == Uint128::zero() {
-        return Err(ContractError::Std(StdError::generic_err(
-            "Nothing to claim",
-        )));
-    }
-
     let mut dev_fee = Uint128::zero();
     if amount_commissionable != Uint128::zero() {
-        dev_fee = compute_gaming_fee(deps.as_ref(), amount_commissionable)?;
+        dev_fee = compute_gaming_fee(collateral.gaming_fee, amount_commissionable)?;
         let mut messages_dev_fees = Vec::new();
         for dev_wallet in config.clone().dev_wallet_list {
-            let token_transfer_msg = BankMsg::Send {
-                to_address: dev_wallet.address.to_string(),
-                amount: coins(
-                    dev_fee.mul_floor(dev_wallet.ratio).u128(),
-                    &config.token_denom,
-                ),
-            };
-            messages_dev_fees.push(token_transfer_msg)
+            let amount = dev_fee.mul_floor(dev_wallet.ratio);
+            if !amount.is_zero() {
+                let token_transfer_msg =
+                    transfer_msg(&collateral.denom, &dev_wallet.address, amount)?;
+                messages_dev_fees.push(token_transfer_msg)
+            }
         }
 
+        resp = resp.add_messages(messages_dev_fees);
+    }
+
+    let elo = elo_delta.into_elo();
+    let amount_won = amount_commissionable + amount_fixed_odds;
+    if amount_won != Uint128::zero() || elo.is_some() {
         let experience_message = AddExperienceAndElo {
-            user: info.sender.clone(),
-            experience: amount_commissionable.u128() as u64 * config.exp_per_denom_won,
-            elo: None,
+            user: bettor.clone(),
+            experience: compute_experience(
+                &deps.querier,
+                &collateral.experience,
+                amount_won,
+                ExperienceAction::Won,
+            )?,
+            elo,
         };
 
         let wasm_message = WasmMsg::Execute {
@@ -415,63 +748,390 @@ fn execute_collect_winning_round(
             funds: vec![],
         };
 
-        resp = resp
-            .add_messages(messages_dev_fees)
-            .add_message(wasm_message)
-            .add_attribute("velo_action", "distribute-dev-rewards")
-            .add_attribute("velo_amount", dev_fee);
+        resp = resp.add_message(wasm_message);
     }
 
     let amount_winnings = winnings.u128() - dev_fee.u128();
-    let msg_send_winnings = BankMsg::Send {
-        to_address: info.sender.to_string(),
-        amount: coins(amount_winnings, &config.token_denom),
+    if amount_winnings != 0 {
+        resp = resp.add_submessage(payout_msg(
+            &env,
+            IBC_ORIGINS
+                .may_load(deps.storage, recipient.clone())?
+                .as_ref(),
+            &collateral.denom,
+            &recipient,
+            Uint128::from(amount_winnings),
+        )?);
+    }
+
+    let receipt = ClaimReceipt {
+        player: recipient.clone(),
+        collateral: collateral.key(),
+        payouts,
+        gross: winnings,
+        fee: dev_fee,
+        net: Uint128::from(amount_winnings),
     };
 
+    let hook_msgs = hook_messages(
+        load_hooks(deps.storage)?,
+        GameHookMsg::Claim {
+            player: recipient.clone(),
+            denom: receipt.collateral.clone(),
+            payouts: receipt
+                .payouts
+                .iter()
+                .map(|payout| HookPayout {
+                    id: payout.round_id,
+                    amount: payout.amount,
+                })
+                .collect(),
+            gross: receipt.gross,
+            fee: receipt.fee,
+            net: receipt.net,
+        },
+    )?;
+
     Ok(resp
-        .add_message(msg_send_winnings)
-        .add_attribute("velo_action", "collect-winnings-round")
-        .add_attribute("velo_round_id", round_id)
-        .add_attribute("velo_claimer", info.sender)
-        .add_attribute("velo_amount", amount_winnings.to_string()))
+        .set_data(to_json_binary(&receipt)?)
+        .add_submessages(hook_msgs)
+        .add_event(
+            velo_event(VeloAction::Claim)
+                .add_attribute(attr::ROUND_ID, round_id)
+                .add_attribute(attr::PLAYER, recipient)
+                .add_attribute(attr::COLLATERAL, receipt.collateral.clone())
+                .add_attribute(attr::AMOUNT, amount_winnings.to_string())
+                .add_attribute(attr::FEE, dev_fee),
+        ))
 }
 
-fn execute_bet(
-    deps: DepsMut,
+fn execute_bet_native(
+    deps: DepsMut<NeutronQuery>,
     info: MessageInfo,
     env: Env,
     round_id: Uint128,
     dir: Direction,
     gross: Uint128,
 ) -> Result<Response, ContractError> {
-    assert_not_halted(deps.as_ref())?;
+    let config = CONFIG.load(deps.storage)?;
+    let funds_sent = one_coin(&info)?;
 
-    let mut bet_round = assert_is_current_round(deps.as_ref(), round_id)?;
-    let mut resp = Response::new();
+    // CW20 collaterals only take bets through Receive
+    let collateral = config
+        .collaterals
+        .into_iter()
+        .find(|c| c.denom == Denom::Native(funds_sent.denom.clone()))
+        .ok_or(ContractError::InvalidFunds {})?;
+
+    if funds_sent.amount != gross {
+        return Err(ContractError::NotEnoughFunds {});
+    }
+
+    execute_bet(deps, env, info.sender, collateral, round_id, dir, gross)
+}
+
+fn execute_sponsor_native(
+    deps: DepsMut,
+    info: MessageInfo,
+    start_round: Uint128,
+    end_round: Uint128,
+    denom: Option<String>,
+    amount_per_round: Uint128,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    let funds_sent = one_coin(&info)?;
+
+    // CW20 collaterals sponsor through Receive
+    let collateral = config
+        .collaterals
+        .into_iter()
+        .find(|c| c.denom == Denom::Native(funds_sent.denom.clone()))
+        .ok_or(ContractError::InvalidFunds {})?;
+
+    execute_sponsor(
+        deps,
+        info.sender,
+        collateral,
+        funds_sent.amount,
+        start_round,
+        end_round,
+        denom,
+        amount_per_round,
+    )
+}
+
+fn execute_receive(
+    deps: DepsMut<NeutronQuery>,
+    info: MessageInfo,
+    env: Env,
+    wrapper: Cw20ReceiveMsg,
+) -> Result<Response, ContractError> {
     let config = CONFIG.load(deps.storage)?;
 
+    // Only the CW20 contracts of the collaterals can send tokens
+    let collateral = config
+        .collaterals
+        .into_iter()
+        .find(|c| c.denom == Denom::Cw20(info.sender.clone()))
+        .ok_or(ContractError::InvalidFunds {})?;
+
+    let player = deps.api.addr_validate(&wrapper.sender)?;
+    let (round_id, dir) = match from_json(&wrapper.msg)? {
+        ReceiveMsg::BetBull { round_id } => (round_id, Direction::Bull),
+        ReceiveMsg::BetBear { round_id } => (round_id, Direction::Bear),
+        ReceiveMsg::Sponsor {
+            start_round,
+            end_round,
+            denom,
+            amount_per_round,
+        } => {
+            return execute_sponsor(
+                deps.into_empty(),
+                player,
+                collateral,
+                wrapper.amount,
+                start_round,
+                end_round,
+                denom,
+                amount_per_round,
+            )
+        }
+    };
+
+    execute_bet(deps, env, player, collateral, round_id, dir, wrapper.amount)
+}
+
+#[allow(clippy::too_many_arguments)]
+fn execute_ibc_bet(
+    deps: DepsMut<NeutronQuery>,
+    info: MessageInfo,
+    env: Env,
+    channel: String,
+    original_sender: String,
+    round_id: Uint128,
+    direction: Direction,
+    return_to_origin: bool,
+) -> Result<Response, ContractError> {
+    if info.sender != intermediate_sender(deps.api, &channel, &original_sender)? {
+        return Err(ContractError::InvalidIbcSender {});
+    }
+
+    let config = CONFIG.load(deps.storage)?;
     let funds_sent = one_coin(&info)?;
+    if !IBC_DENOMS.has(deps.storage, funds_sent.denom.clone()) {
+        return Err(ContractError::IbcDenomNotAllowed {
+            denom: funds_sent.denom,
+        });
+    }
+    let collateral = load_collateral(&config, &funds_sent.denom)?;
+
+    IBC_ORIGINS.save(
+        deps.storage,
+        info.sender.clone(),
+        &IbcOrigin {
+            channel: channel.clone(),
+            sender: original_sender.clone(),
+            return_to_origin,
+        },
+    )?;
+
+    let resp = execute_bet(
+        deps,
+        env,
+        info.sender.clone(),
+        collateral,
+        round_id,
+        direction,
+        funds_sent.amount,
+    )?;
+
+    Ok(resp.add_event(
+        velo_event(VeloAction::IbcBet)
+            .add_attribute(attr::ROUND_ID, round_id)
+            .add_attribute(attr::PLAYER, info.sender)
+            .add_attribute(attr::CHANNEL, channel)
+            .add_attribute(attr::ORIGINAL_SENDER, original_sender)
+            .add_attribute(attr::IBC_DENOM, funds_sent.denom),
+    ))
+}
 
-    let totals = TOTALS_SPENT.may_load(deps.storage, info.clone().sender)?;
-    if let Some(totals) = totals {
-        TOTALS_SPENT.save(
-            deps.storage,
-            info.clone().sender,
-            &totals.checked_add(funds_sent.amount)?,
-        )?;
-    } else {
-        TOTALS_SPENT.save(deps.storage, info.clone().sender, &funds_sent.amount)?;
+fn execute_collect_ibc_winnings(
+    deps: DepsMut<NeutronQuery>,
+    env: Env,
+    player: Addr,
+    collateral: String,
+) -> Result<Response, ContractError> {
+    let origin = IBC_ORIGINS.may_load(deps.storage, player.clone())?;
+    if !origin.is_some_and(|origin| origin.return_to_origin) {
+        return Err(ContractError::NotReturnedToOrigin {});
     }
 
-    if funds_sent.denom != config.token_denom {
-        return Err(ContractError::InvalidFunds {});
+    execute_collect_winnings(deps, env, player, collateral)
+}
+
+fn execute_retry_ibc_payout(
+    deps: DepsMut,
+    env: Env,
+    player: Addr,
+    collateral: String,
+) -> Result<Response, ContractError> {
+    let key = (player.clone(), collateral.clone());
+    let amount = FAILED_IBC_PAYOUTS
+        .may_load(deps.storage, key.clone())?
+        .ok_or(ContractError::NoFailedIbcPayout {})?;
+    FAILED_IBC_PAYOUTS.remove(deps.storage, key);
+
+    let collateral = load_collateral(&CONFIG.load(deps.storage)?, &collateral)?;
+
+    // Sent to the origin the player has now, locally if they stopped asking for it
+    Ok(Response::new()
+        .add_submessage(payout_msg(
+            &env,
+            IBC_ORIGINS.may_load(deps.storage, player.clone())?.as_ref(),
+            &collateral.denom,
+            &player,
+            amount,
+        )?)
+        .add_event(
+            velo_event(VeloAction::RetryIbcPayout)
+                .add_attribute(attr::PLAYER, player)
+                .add_attribute(attr::COLLATERAL, collateral.key())
+                .add_attribute(attr::AMOUNT, amount),
+        ))
+}
+
+#[allow(clippy::too_many_arguments)]
+fn execute_swap_and_bet(
+    deps: DepsMut<NeutronQuery>,
+    info: MessageInfo,
+    env: Env,
+    round_id: Uint128,
+    direction: Direction,
+    collateral: String,
+    operations: Vec<SwapOperation>,
+    minimum_receive: Option<Uint128>,
+    max_spread: Option<Decimal>,
+) -> Result<Response, ContractError> {
+    if PAUSE.load(deps.storage)?.betting {
+        return Err(ContractError::BettingPaused {});
     }
 
-    if funds_sent.amount != gross {
-        return Err(ContractError::NotEnoughFunds {});
+    let config = CONFIG.load(deps.storage)?;
+    let router = config
+        .swap_router
+        .clone()
+        .ok_or(ContractError::SwapRouterNotSet {})?;
+    let collateral = load_collateral(&config, &collateral)?;
+    let offer = one_coin(&info)?;
+
+    let bet_round = assert_is_current_round(deps.as_ref().into_empty(), round_id)?;
+    if env.block.time > bet_round.open_time {
+        return Err(ContractError::RoundFinished {
+            round_id,
+            seconds: env.block.time.seconds() - bet_round.open_time.seconds(),
+        });
+    }
+
+    let (Some(first), Some(last)) = (operations.first(), operations.last()) else {
+        return Err(ContractError::InvalidSwapRoute {});
+    };
+    if first.offer_key() != offer.denom
+        || last.ask_key() != collateral.key()
+        || offer.denom == collateral.key()
+    {
+        return Err(ContractError::InvalidSwapRoute {});
+    }
+
+    PENDING_SWAP.save(
+        deps.storage,
+        &PendingSwap {
+            player: info.sender,
+            round_id,
+            direction,
+            collateral: collateral.key(),
+            offer: offer.clone(),
+            balance_before: query_balance(&deps.querier, &collateral.denom, &env.contract.address)?,
+            minimum_receive,
+        },
+    )?;
+
+    let swap_msg = WasmMsg::Execute {
+        contract_addr: router.to_string(),
+        msg: to_json_binary(&RouterExecuteMsg::ExecuteSwapOperations {
+            operations,
+            minimum_receive,
+            to: None,
+            max_spread,
+        })?,
+        funds: vec![offer],
+    };
+
+    Ok(Response::new().add_submessage(SubMsg::reply_on_success(swap_msg, SWAP_REPLY_ID)))
+}
+
+fn reply_swap_and_bet(deps: DepsMut<NeutronQuery>, env: Env) -> Result<Response, ContractError> {
+    let pending = PENDING_SWAP.load(deps.storage)?;
+    PENDING_SWAP.remove(deps.storage);
+
+    let config = CONFIG.load(deps.storage)?;
+    let collateral = load_collateral(&config, &pending.collateral)?;
+    let balance_after = query_balance(&deps.querier, &collateral.denom, &env.contract.address)?;
+    let output = balance_after.checked_sub(pending.balance_before)?;
+
+    if let Some(minimum) = pending.minimum_receive {
+        if output < minimum {
+            return Err(ContractError::SwapOutputTooLow { output, minimum });
+        }
+    }
+
+    let resp = execute_bet(
+        deps,
+        env,
+        pending.player.clone(),
+        collateral,
+        pending.round_id,
+        pending.direction,
+        output,
+    )?;
+
+    Ok(resp.add_event(
+        velo_event(VeloAction::SwapAndBet)
+            .add_attribute(attr::ROUND_ID, pending.round_id)
+            .add_attribute(attr::PLAYER, pending.player)
+            .add_attribute(attr::DENOM, pending.offer.denom)
+            .add_attribute(attr::AMOUNT, pending.offer.amount)
+            .add_attribute(attr::COLLATERAL, pending.collateral)
+            .add_attribute(attr::RECEIVED, output),
+    ))
+}
+
+/// Places a bet of `gross` tokens of `collateral`, already received by the contract, for `player`
+fn execute_bet(
+    deps: DepsMut<NeutronQuery>,
+    env: Env,
+    player: Addr,
+    collateral: Collateral,
+    round_id: Uint128,
+    dir: Direction,
+    gross: Uint128,
+) -> Result<Response, ContractError> {
+    if PAUSE.load(deps.storage)?.betting {
+        return Err(ContractError::BettingPaused {});
+    }
+
+    let mut bet_round = assert_is_current_round(deps.as_ref().into_empty(), round_id)?;
+    let config = CONFIG.load(deps.storage)?;
+    let collateral_key = collateral.key();
+
+    let totals_key = (player.clone(), collateral_key.clone());
+    let totals = TOTALS_SPENT.may_load(deps.storage, totals_key.clone())?;
+    if let Some(totals) = totals {
+        TOTALS_SPENT.save(deps.storage, totals_key, &totals.checked_add(gross)?)?;
+    } else {
+        TOTALS_SPENT.save(deps.storage, totals_key, &gross)?;
     }
 
-    if funds_sent.amount < config.minimum_bet {
+    if gross < collateral.minimum_bet {
         return Err(ContractError::BetUnderMinBetAmount {});
     }
 
@@ -482,21 +1142,101 @@ fn execute_bet(
         });
     }
 
-    let bet_info_key = bet_info_key(round_id.u128(), &info.sender.clone());
+    let bet_info_key = bet_info_key(round_id.u128(), &player);
 
     let bet_info = bet_info_storage().may_load(deps.storage, bet_info_key.clone())?;
 
-    let mut amount_bet = gross;
-    if let Some(bet_info) = bet_info {
-        if bet_info.direction != dir {
-            return Err(ContractError::InvalidDirectionBet {});
+    let shares = early_bet_shares(config.early_bet_bonus, &bet_round, env.block.time, gross);
+    let mut bet = match bet_info {
+        Some(bet_info) => {
+            // A bet is keyed by round and player, so it holds a single collateral
+            if bet_info.collateral != collateral_key {
+                return Err(ContractError::InvalidCollateralBet {
+                    collateral: bet_info.collateral,
+                });
+            }
+            // With hedging the other side is kept apart in the same bet
+            if bet_info.direction != dir && !config.hedging {
+                return Err(ContractError::InvalidDirectionBet {});
+            }
+            // Once sold or gifted the position belongs to its holder, the bettor can't add to it
+            if let Some(existing) = &bet_info.token_id {
+                if POSITIONS.load(deps.storage, existing.clone())?.owner != player {
+                    return Err(ContractError::PositionTransferred {
+                        token_id: existing.clone(),
+                    });
+                }
+            }
+            bet_info
+        }
+        None => {
+            bet_round.players += 1;
+            let token_id = if config.position_nfts {
+                Some(mint_position(deps.storage, &player, round_id)?)
+            } else {
+                None
+            };
+            BetInfo {
+                player: player.clone(),
+                round_id,
+                collateral: collateral_key.clone(),
+                amount: Uint128::zero(),
+                direction: dir.clone(),
+                token_id,
+                fixed_payout: None,
+                weighted_amount: None,
+                hedge: None,
+            }
+        }
+    };
+
+    // The vault takes the other side of fixed odds bets, the stake is forwarded to it
+    let mut vault_msgs = vec![];
+    let mut payout = None;
+    if let Some(fixed_odds) = pool_mut(&mut bet_round.pools, &collateral)
+        .fixed_odds
+        .clone()
+    {
+        let fixed_payout = gross.mul_floor(fixed_odds.odds());
+        let capacity: CapacityResponse = deps.querier.query_wasm_smart(
+            fixed_odds.vault.to_string(),
+            &VaultQueryMsg::Capacity {
+                game: env.contract.address.to_string(),
+                round_id,
+                direction: dir.clone(),
+            },
+        )?;
+        if fixed_payout > capacity.max_payout {
+            return Err(ContractError::VaultCapacityExceeded {
+                max_payout: capacity.max_payout,
+            });
         }
-        amount_bet += bet_info.amount;
+
+        vault_msgs.push(transfer_msg(&collateral.denom, &fixed_odds.vault, gross)?);
+        vault_msgs.push(
+            WasmMsg::Execute {
+                contract_addr: fixed_odds.vault.to_string(),
+                msg: to_json_binary(&VaultExecuteMsg::LockPayout {
+                    round_id,
+                    direction: dir.clone(),
+                    stake: gross,
+                    payout: fixed_payout,
+                })?,
+                funds: vec![],
+            }
+            .into(),
+        );
+        payout = Some(fixed_payout);
     }
 
     let experience_message = AddExperienceAndElo {
-        user: info.sender.clone(),
-        experience: funds_sent.amount.u128() as u64 * config.exp_per_denom_bet,
+        user: player.clone(),
+        experience: compute_experience(
+            &deps.querier,
+            &collateral.experience,
+            gross,
+            ExperienceAction::Bet,
+        )?,
         elo: None,
     };
 
@@ -506,82 +1246,97 @@ fn execute_bet(
         funds: vec![],
     };
 
-    match dir {
-        Direction::Bull => {
-            bet_info_storage().save(
-                deps.storage,
-                bet_info_key.clone(),
-                &BetInfo {
-                    player: info.sender.clone(),
-                    round_id,
-                    amount: amount_bet,
-                    direction: Direction::Bull,
-                },
-            )?;
-            bet_round.bull_amount += gross;
-            NEXT_ROUND.save(deps.storage, &bet_round)?;
-            resp = resp
-                .add_attribute("velo_action", "bet".to_string())
-                .add_attribute("velo_round", round_id.to_string())
-                .add_attribute("velo_direction", "bull".to_string())
-                .add_attribute("velo_amount", gross.to_string())
-                .add_attribute("velo_round_bull_total", bet_round.bull_amount.to_string())
-                .add_attribute("velo_account", info.sender.to_string());
-        }
-        Direction::Bear => {
-            bet_info_storage().save(
-                deps.storage,
-                bet_info_key.clone(),
-                &BetInfo {
-                    player: info.sender.clone(),
-                    round_id,
-                    amount: amount_bet,
-                    direction: Direction::Bear,
-                },
-            )?;
-            bet_round.bear_amount += gross;
-            NEXT_ROUND.save(deps.storage, &bet_round)?;
-            resp = resp
-                .add_attribute("velo_action", "bet".to_string())
-                .add_attribute("velo_round", round_id.to_string())
-                .add_attribute("velo_direction", "bear".to_string())
-                .add_attribute("velo_amount", gross.to_string())
-                .add_attribute("velo_round_bear_total", bet_round.bear_amount.to_string())
-                .add_attribute("velo_account", info.sender.to_string());
-        }
+    let side = bet.add_stake(&dir, gross, shares, payout);
+    bet_info_storage().save(deps.storage, bet_info_key, &bet)?;
+    pool_mut(&mut bet_round.pools, &collateral).add_stake(&dir, gross, shares);
+    NEXT_ROUND.save(deps.storage, &bet_round)?;
+    track_bet(deps.storage, &player, round_id.u128())?;
+
+    let pool = find_pool(&bet_round.pools, &collateral_key);
+    let receipt = BetReceipt {
+        round_id,
+        direction: dir,
+        collateral: collateral_key,
+        stake: gross,
+        total_stake: side.amount,
+        bull_amount: pool.bull_amount,
+        bear_amount: pool.bear_amount,
+        token_id: bet.token_id,
+        fixed_payout: side.fixed_payout,
+        shares,
+    };
+
+    let hook_msgs = hook_messages(
+        load_hooks(deps.storage)?,
+        GameHookMsg::Bet {
+            player: player.clone(),
+            id: round_id,
+            side: receipt.direction.to_string(),
+            denom: receipt.collateral.clone(),
+            amount: gross,
+        },
+    )?;
+
+    let mut event = velo_event(VeloAction::Bet);
+    if let Some(token_id) = &receipt.token_id {
+        event = event.add_attribute(attr::TOKEN_ID, token_id.clone());
+    }
+    if let Some(fixed_payout) = receipt.fixed_payout {
+        event = event.add_attribute(attr::PAYOUT, fixed_payout.to_string());
     }
 
-    Ok(resp.add_message(wasm_message))
+    Ok(Response::new()
+        .set_data(to_json_binary(&receipt)?)
+        .add_messages(vault_msgs)
+        .add_message(wasm_message)
+        .add_submessages(hook_msgs)
+        .add_event(
+            event
+                .add_attribute(attr::ROUND_ID, round_id.to_string())
+                .add_attribute(attr::PLAYER, player.to_string())
+                .add_attribute(attr::DIRECTION, receipt.direction.to_string())
+                .add_attribute(attr::COLLATERAL, receipt.collateral.clone())
+                .add_attribute(attr::AMOUNT, gross.to_string())
+                .add_attribute(attr::BULL_AMOUNT, receipt.bull_amount.to_string())
+                .add_attribute(attr::BEAR_AMOUNT, receipt.bear_amount.to_string()),
+        ))
 }
 
 fn execute_close_round(deps: DepsMut<NeutronQuery>, env: Env) -> Result<Response, ContractError> {
-    assert_not_halted(deps.as_ref().into_empty())?;
+    if PAUSE.load(deps.storage)?.rounds {
+        return Err(ContractError::RoundsPaused {});
+    }
     let now = env.block.time;
     let config = CONFIG.load(deps.storage)?;
     let mut resp: Response = Response::new();
+    let mut receipt = CloseRoundReceipt {
+        finished_round: None,
+        live_round: None,
+        new_round_id: None,
+        voided_round_id: None,
+    };
 
     let maybe_live_round = LIVE_ROUND.may_load(deps.storage)?;
-    match &maybe_live_round {
-        Some(live_round) => {
-            if now >= live_round.close_time {
-                let finished_round =
-                    compute_round_close(deps.as_ref(), env.block.time.seconds(), live_round)?;
-                ROUNDS.save(deps.storage, live_round.id.u128(), &finished_round)?;
-                resp = resp
-                    .add_attribute("velo_action", "finished-round")
-                    .add_attribute("velo_round_id", live_round.id.to_string())
-                    .add_attribute("velo_close_price", finished_round.close_price.to_string())
+    if let Some(live_round) = &maybe_live_round {
+        if now >= live_round.close_time {
+            let mut finished_round =
+                compute_round_close(deps.as_ref(), env.block.time.seconds(), live_round)?;
+            resp = finish_round(deps.storage, &config, &mut finished_round, resp)?;
+            resp = resp.add_event(
+                velo_event(VeloAction::FinishRound)
+                    .add_attribute(attr::ROUND_ID, live_round.id.to_string())
+                    .add_attribute(attr::CLOSE_PRICE, finished_round.close_price.to_string())
                     .add_attribute(
-                        "winner",
-                        match finished_round.winner {
+                        attr::WINNER,
+                        match &finished_round.winner {
                             Some(w) => w.to_string(),
                             None => "everybody".to_string(),
                         },
-                    );
-                LIVE_ROUND.remove(deps.storage);
-            }
+                    ),
+            );
+            LIVE_ROUND.remove(deps.storage);
+            receipt.finished_round = Some(finished_round);
         }
-        None => {}
     }
 
     /* Close the bidding round if it is finished
@@ -607,13 +1362,18 @@ fn execute_close_round(deps: DepsMut<NeutronQuery>, env: Env) -> Result<Response
         NEXT_ROUND.save(
             deps.storage,
             &NextRound {
-                bear_amount: Uint128::zero(),
-                bull_amount: Uint128::zero(),
+                pools: config
+                    .collaterals
+                    .iter()
+                    .map(|collateral| collateral.pool())
+                    .collect(),
                 bid_time: env.block.time,
                 close_time,
                 open_time,
                 id,
                 denom: denom.to_string(),
+                players: 0,
+                extensions: 0,
             },
         )?;
         NEXT_ROUND_ID.save(deps.storage, &(id.u128() + 1u128))?;
@@ -624,42 +1384,283 @@ fn execute_close_round(deps: DepsMut<NeutronQuery>, env: Env) -> Result<Response
     match &maybe_open_round {
         Some(open_round) => {
             if LIVE_ROUND.may_load(deps.storage)?.is_none() && now >= open_round.open_time {
-                let live_round = compute_round_open(deps.as_ref(), env.clone(), open_round)?;
-                resp = resp
-                    .add_attribute("velo_action", "bidding_close")
-                    .add_attribute("velo_round_id", live_round.id.to_string())
-                    .add_attribute("velo_open_price", live_round.open_price.to_string())
-                    .add_attribute("velo_bear_amount", live_round.bear_amount.to_string())
-                    .add_attribute("velo_bull_amount", live_round.bull_amount.to_string());
-                LIVE_ROUND.save(deps.storage, &live_round)?;
-                NEXT_ROUND.remove(deps.storage);
-                let new_round_id = new_bid_round(deps.into_empty(), env)?;
-                resp = resp
-                    .add_attribute("velo_action", "new_round")
-                    .add_attribute("velo_round_id", new_round_id);
+                if meets_open_minimums(&config, open_round) {
+                    let live_round = compute_round_open(deps.as_ref(), env.clone(), open_round)?;
+                    resp = resp.add_event(
+                        velo_event(VeloAction::StartRound)
+                            .add_attribute(attr::ROUND_ID, live_round.id.to_string())
+                            .add_attribute(attr::DENOM, live_round.denom.clone())
+                            .add_attribute(attr::OPEN_PRICE, live_round.open_price.to_string()),
+                    );
+                    LIVE_ROUND.save(deps.storage, &live_round)?;
+                    NEXT_ROUND.remove(deps.storage);
+                    receipt.live_round = Some(live_round);
+                    let new_round_id = new_bid_round(deps.into_empty(), env)?;
+                    resp = resp.add_event(
+                        velo_event(VeloAction::NewRound)
+                            .add_attribute(attr::ROUND_ID, new_round_id),
+                    );
+                    receipt.new_round_id = Some(new_round_id);
+                } else if open_round.extensions < config.max_bid_extensions {
+                    // Give players more time to join, the round opens once the minimums are met
+                    let mut extended_round = open_round.clone();
+                    extended_round.extensions += 1;
+                    extended_round.open_time =
+                        now.plus_seconds(config.bid_extension_seconds.u128() as u64);
+                    extended_round.close_time = extended_round
+                        .open_time
+                        .plus_seconds(config.next_round_seconds.u128() as u64);
+                    NEXT_ROUND.save(deps.storage, &extended_round)?;
+                    resp = resp.add_event(
+                        velo_event(VeloAction::ExtendRound)
+                            .add_attribute(attr::ROUND_ID, extended_round.id.to_string())
+                            .add_attribute(
+                                attr::OPEN_TIME,
+                                extended_round.open_time.seconds().to_string(),
+                            )
+                            .add_attribute(attr::EXTENSION, extended_round.extensions.to_string()),
+                    );
+                } else {
+                    let mut voided_round = void_round(open_round, now);
+                    resp = finish_round(deps.storage, &config, &mut voided_round, resp)?;
+                    resp = resp.add_event(
+                        velo_event(VeloAction::VoidRound)
+                            .add_attribute(attr::ROUND_ID, voided_round.id.to_string()),
+                    );
+                    NEXT_ROUND.remove(deps.storage);
+                    receipt.voided_round_id = Some(voided_round.id);
+                    let new_round_id = new_bid_round(deps.into_empty(), env)?;
+                    resp = resp.add_event(
+                        velo_event(VeloAction::NewRound)
+                            .add_attribute(attr::ROUND_ID, new_round_id),
+                    );
+                    receipt.new_round_id = Some(new_round_id);
+                }
             }
         }
         None => {
             let new_round_id = new_bid_round(deps.into_empty(), env)?;
-            resp = resp
-                .add_attribute("velo_action", "new_round")
-                .add_attribute("velo_round_id", new_round_id);
+            resp = resp.add_event(
+                velo_event(VeloAction::NewRound).add_attribute(attr::ROUND_ID, new_round_id),
+            );
+            receipt.new_round_id = Some(new_round_id);
         }
     }
 
-    Ok(resp)
+    Ok(resp.set_data(to_json_binary(&receipt)?))
 }
 
 fn execute_update_config(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    update: ConfigUpdate,
+) -> Result<Response, ContractError> {
+    assert_role(deps.storage, Role::ConfigAdmin, &info.sender)?;
+
+    // Fail now rather than when the change is executed
+    let old_config = CONFIG.load(deps.storage)?;
+    let mut config = old_config.clone();
+    patch_config(&mut config, update.clone())?;
+    validate_config(deps.as_ref(), &config, Some(&old_config))?;
+
+    // Only the fields moving funds wait for the timelock, the rest applies at once
+    let (timelocked, immediate) = update.split_timelocked();
+    let mut resp = Response::new();
+    if immediate != ConfigUpdate::default() {
+        resp = apply_change(deps.branch(), ConfigChange::Config { config: immediate })?;
+    }
+    if timelocked != ConfigUpdate::default() {
+        let queued = queue_or_apply_change(
+            deps,
+            env,
+            info.sender,
+            ConfigChange::Config { config: timelocked },
+        )?;
+        resp = resp.add_events(queued.events);
+    }
+
+    Ok(resp)
+}
+
+/// Sets the fields of the update, returning the old and new value of each one as attributes
+fn patch_config(config: &mut Config, update: ConfigUpdate) -> StdResult<Vec<Attribute>> {
+    let mut attributes = vec![];
+    patch_field(
+        &mut attributes,
+        "next_round_seconds",
+        &mut config.next_round_seconds,
+        update.next_round_seconds,
+    )?;
+    patch_field(
+        &mut attributes,
+        "collaterals",
+        &mut config.collaterals,
+        update.collaterals,
+    )?;
+    patch_field(
+        &mut attributes,
+        "users_contract",
+        &mut config.users_contract,
+        update.users_contract,
+    )?;
+    patch_field(
+        &mut attributes,
+        "elo_k_factor",
+        &mut config.elo_k_factor,
+        update.elo_k_factor,
+    )?;
+    patch_field(
+        &mut attributes,
+        "dev_wallet_list",
+        &mut config.dev_wallet_list,
+        update.dev_wallet_list,
+    )?;
+    patch_field(
+        &mut attributes,
+        "swap_router",
+        &mut config.swap_router,
+        update.swap_router.map(Some),
+    )?;
+    patch_field(
+        &mut attributes,
+        "position_nfts",
+        &mut config.position_nfts,
+        update.position_nfts,
+    )?;
+    patch_field(
+        &mut attributes,
+        "early_bet_bonus",
+        &mut config.early_bet_bonus,
+        update.early_bet_bonus,
+    )?;
+    patch_field(
+        &mut attributes,
+        "min_players_to_open",
+        &mut config.min_players_to_open,
+        update.min_players_to_open,
+    )?;
+    patch_field(
+        &mut attributes,
+        "bid_extension_seconds",
+        &mut config.bid_extension_seconds,
+        update.bid_extension_seconds,
+    )?;
+    patch_field(
+        &mut attributes,
+        "max_bid_extensions",
+        &mut config.max_bid_extensions,
+        update.max_bid_extensions,
+    )?;
+    patch_field(
+        &mut attributes,
+        "round_retention",
+        &mut config.round_retention,
+        update.round_retention,
+    )?;
+    patch_field(
+        &mut attributes,
+        "hedging",
+        &mut config.hedging,
+        update.hedging,
+    )?;
+
+    Ok(attributes)
+}
+
+/// Applies the change at once when there is no timelock delay, otherwise queues it
+fn queue_or_apply_change(
+    deps: DepsMut,
+    env: Env,
+    proposer: Addr,
+    change: ConfigChange,
+) -> Result<Response, ContractError> {
+    if timelock::delay(deps.storage)? == 0 {
+        return apply_change(deps, change);
+    }
+
+    let pending = timelock::queue(deps.storage, proposer, env.block.time, change)?;
+    Ok(Response::new().add_event(
+        velo_event(VeloAction::QueueChange)
+            .add_attribute(attr::CHANGE_ID, pending.id.to_string())
+            .add_attribute(attr::CHANGE, pending.change.as_str())
+            .add_attribute(
+                attr::EXECUTABLE_AT,
+                pending.executable_at.seconds().to_string(),
+            ),
+    ))
+}
+
+fn apply_change(deps: DepsMut, change: ConfigChange) -> Result<Response, ContractError> {
+    match change {
+        ConfigChange::Config { config: update } => {
+            // The vault or the current config could have changed while queued
+            let old_config = CONFIG.load(deps.storage)?;
+            let mut config = old_config.clone();
+            let attributes = patch_config(&mut config, update)?;
+            validate_config(deps.as_ref(), &config, Some(&old_config))?;
+            CONFIG.save(deps.storage, &config)?;
+
+            Ok(Response::new()
+                .add_event(velo_event(VeloAction::UpdateConfig).add_attributes(attributes)))
+        }
+        ConfigChange::DevWallets { dev_wallets } => {
+            let mut config = CONFIG.load(deps.storage)?;
+            config.dev_wallet_list = dev_wallets;
+            CONFIG.save(deps.storage, &config)?;
+
+            Ok(Response::new().add_event(velo_event(VeloAction::ModifyDevWallets)))
+        }
+        ConfigChange::TimelockDelay { seconds } => {
+            timelock::set_delay(deps.storage, seconds)?;
+
+            Ok(Response::new().add_event(
+                velo_event(VeloAction::UpdateTimelockDelay)
+                    .add_attribute(attr::DELAY, seconds.to_string()),
+            ))
+        }
+    }
+}
+
+fn execute_change(deps: DepsMut, env: Env, id: u64) -> Result<Response, ContractError> {
+    let pending: PendingChange<ConfigChange> =
+        timelock::take_ready(deps.storage, id, env.block.time)?;
+    let res = apply_change(deps, pending.change)?;
+
+    Ok(res.add_event(
+        velo_event(VeloAction::ExecuteChange).add_attribute(attr::CHANGE_ID, id.to_string()),
+    ))
+}
+
+fn execute_cancel_change(
+    deps: DepsMut,
+    info: MessageInfo,
+    id: u64,
+) -> Result<Response, ContractError> {
+    assert_role(deps.storage, Role::Owner, &info.sender)?;
+    let pending: PendingChange<ConfigChange> = timelock::cancel(deps.storage, id)?;
+
+    Ok(Response::new().add_event(
+        velo_event(VeloAction::CancelChange)
+            .add_attribute(attr::CHANGE_ID, id.to_string())
+            .add_attribute(attr::CHANGE, pending.change.as_str()),
+    ))
+}
+
+fn execute_update_timelock_delay(
     deps: DepsMut,
+    env: Env,
     info: MessageInfo,
-    u_config: Config,
+    seconds: u64,
 ) -> Result<Response, ContractError> {
-    assert_is_admin(deps.as_ref(), info)?;
+    assert_role(deps.storage, Role::Owner, &info.sender)?;
 
-    CONFIG.save(deps.storage, &u_config)?;
+    let change = ConfigChange::TimelockDelay { seconds };
+    if seconds >= timelock::delay(deps.storage)? {
+        return apply_change(deps, change);
+    }
 
-    Ok(Response::new().add_attribute("velo_action", "update_config"))
+    queue_or_apply_change(deps, env, info.sender, change)
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
@@ -667,29 +1668,76 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
         QueryMsg::Config {} => to_json_binary(&query_config(deps)?),
         QueryMsg::Status {} => to_json_binary(&query_status(deps, env)?),
+        QueryMsg::PauseStatus {} => to_json_binary(&PAUSE.load(deps.storage)?),
+        QueryMsg::PendingChanges { start_after, limit } => to_json_binary(
+            &timelock::query_pending_changes::<ConfigChange>(deps.storage, start_after, limit)?,
+        ),
+        QueryMsg::MigrationPlan { from_version } => {
+            to_json_binary(&migration::query_migration_plan(
+                deps.storage,
+                CONTRACT_VERSION,
+                MIGRATIONS,
+                from_version,
+            )?)
+        }
         QueryMsg::MyCurrentPosition { address } => {
             to_json_binary(&query_my_current_position(deps, address)?)
         }
         QueryMsg::FinishedRound { round_id } => {
             to_json_binary(&query_finished_round(deps, round_id)?)
         }
+        QueryMsg::FinishedRounds {
+            start_after,
+            limit,
+            order,
+        } => to_json_binary(&query_finished_rounds(deps, start_after, limit, order)?),
+        QueryMsg::FinishedRoundsByDenom {
+            denom,
+            start_after,
+            limit,
+            order,
+        } => to_json_binary(&query_finished_rounds_by_denom(
+            deps,
+            denom,
+            start_after,
+            limit,
+            order,
+        )?),
         QueryMsg::MyGameList {
             player,
             start_after,
             limit,
         } => to_json_binary(&query_my_games(deps, player, start_after, limit)?),
-        QueryMsg::MyPendingReward { player } => {
-            to_json_binary(&query_my_pending_reward(deps, player)?)
-        }
-        QueryMsg::MyPendingRewardRounds { player } => {
-            to_json_binary(&query_my_pending_reward_rounds(deps, player)?)
+        QueryMsg::MyPendingReward { player, collateral } => {
+            to_json_binary(&query_my_pending_reward(deps, player, collateral)?)
         }
-        QueryMsg::MyRefundableAmount { player } => {
-            to_json_binary(&query_my_refundable_amount(deps, player)?)
-        }
-        QueryMsg::MyRefundableAmountRounds { player } => {
-            to_json_binary(&query_my_refundable_amount_rounds(deps, player)?)
+        QueryMsg::MyPendingRewardRounds {
+            player,
+            collateral,
+            start_after,
+            limit,
+        } => to_json_binary(&query_my_pending_reward_rounds(
+            deps,
+            player,
+            collateral,
+            start_after,
+            limit,
+        )?),
+        QueryMsg::MyRefundableAmount { player, collateral } => {
+            to_json_binary(&query_my_refundable_amount(deps, player, collateral)?)
         }
+        QueryMsg::MyRefundableAmountRounds {
+            player,
+            collateral,
+            start_after,
+            limit,
+        } => to_json_binary(&query_my_refundable_amount_rounds(
+            deps,
+            player,
+            collateral,
+            start_after,
+            limit,
+        )?),
         QueryMsg::GetUsersPerRound {
             round_id,
             start_after,
@@ -713,62 +1761,230 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
             start_after,
             limit,
         } => to_json_binary(&query_claim_info_by_user(deps, player, start_after, limit)?),
-        QueryMsg::TotalSpent { player } => to_json_binary(&query_total_spent(deps, player)?),
-        QueryMsg::GetAdmins {} => to_json_binary(&query_get_admins(deps)?),
+        QueryMsg::TotalSpent { player, collateral } => {
+            to_json_binary(&query_total_spent(deps, player, collateral)?)
+        }
+        QueryMsg::Ownership {} => to_json_binary(&cw_ownable::get_ownership(deps.storage)?),
+        QueryMsg::Roles { address } => to_json_binary(&rbac::query_roles(deps.storage, &address)?),
+        QueryMsg::RoleMembers {
+            role,
+            start_after,
+            limit,
+        } => to_json_binary(&rbac::query_role_members(
+            deps.storage,
+            role,
+            start_after,
+            limit,
+        )?),
         QueryMsg::GetRoundDenoms {} => to_json_binary(&query_get_round_denoms(deps)?),
         QueryMsg::GetTickers {} => to_json_binary(&query_get_tickers(deps)?),
+        QueryMsg::SimulateBet {
+            round_id,
+            direction,
+            collateral,
+            amount,
+            player,
+        } => to_json_binary(&query_simulate_bet(
+            deps, env, round_id, direction, collateral, amount, player,
+        )?),
+        QueryMsg::GetHooks { start_after, limit } => {
+            to_json_binary(&query_get_hooks(deps, start_after, limit)?)
+        }
+        QueryMsg::GetIbcOrigin { player } => {
+            to_json_binary(&IBC_ORIGINS.may_load(deps.storage, player)?)
+        }
+        QueryMsg::GetIbcDenoms {} => to_json_binary(&query_get_ibc_denoms(deps)?),
+        QueryMsg::GetFailedIbcPayout { player, collateral } => to_json_binary(
+            &FAILED_IBC_PAYOUTS
+                .may_load(deps.storage, (player, collateral))?
+                .unwrap_or_default(),
+        ),
+        QueryMsg::OwnerOf {
+            token_id,
+            include_expired,
+        } => to_json_binary(&query_owner_of(deps, env, token_id, include_expired)?),
+        QueryMsg::Approval {
+            token_id,
+            spender,
+            include_expired,
+        } => to_json_binary(&query_approval(
+            deps,
+            env,
+            token_id,
+            spender,
+            include_expired,
+        )?),
+        QueryMsg::Approvals {
+            token_id,
+            include_expired,
+        } => to_json_binary(&query_approvals(deps, env, token_id, include_expired)?),
+        QueryMsg::AllOperators {
+            owner,
+            include_expired,
+            start_after,
+            limit,
+        } => to_json_binary(&query_all_operators(
+            deps,
+            env,
+            owner,
+            include_expired,
+            start_after,
+            limit,
+        )?),
+        QueryMsg::NumTokens {} => to_json_binary(&query_num_tokens(deps)?),
+        QueryMsg::ContractInfo {} => to_json_binary(&query_contract_info()),
+        QueryMsg::NftInfo { token_id } => to_json_binary(&query_nft_info(deps, token_id)?),
+        QueryMsg::AllNftInfo {
+            token_id,
+            include_expired,
+        } => to_json_binary(&query_all_nft_info(deps, env, token_id, include_expired)?),
+        QueryMsg::Tokens {
+            owner,
+            start_after,
+            limit,
+        } => to_json_binary(&query_tokens(deps, owner, start_after, limit)?),
+        QueryMsg::AllTokens { start_after, limit } => {
+            to_json_binary(&query_all_tokens(deps, start_after, limit)?)
+        }
     }
 }
 
 fn query_finished_round(deps: Deps, round_id: Uint128) -> StdResult<FinishedRound> {
-    let round = ROUNDS.load(deps.storage, round_id.u128())?;
-    Ok(round)
+    match ROUNDS.may_load(deps.storage, round_id.u128())? {
+        Some(round) => Ok(round),
+        None => Ok(ROUND_SUMMARIES.load(deps.storage, round_id.u128())?.into()),
+    }
 }
 
-fn query_my_current_position(deps: Deps, address: String) -> StdResult<MyCurrentPositionResponse> {
-    let round_id = NEXT_ROUND_ID.load(deps.storage)?;
-    let next_bet_key = (round_id - 1, deps.api.addr_validate(&address)?);
+fn query_finished_rounds(
+    deps: Deps,
+    start_after: Option<Uint128>,
+    limit: Option<u32>,
+    order: Option<OrderBy>,
+) -> StdResult<FinishedRoundsResponse> {
+    let limit = limit.unwrap_or(MAX_PAGE_LIMIT).min(MAX_PAGE_LIMIT) as usize;
+    let order: Order = order.unwrap_or(OrderBy::Asc).into();
+    let (min, max) = round_bounds(start_after, order);
 
-    let next_bet_info = bet_info_storage().may_load(deps.storage, next_bet_key)?;
+    let rounds = ROUNDS
+        .range(deps.storage, min.clone(), max.clone(), order)
+        .take(limit)
+        .map(|res| res.map(|item| item.1))
+        .collect::<StdResult<Vec<_>>>()?;
+    let summaries = ROUND_SUMMARIES
+        .range(deps.storage, min, max, order)
+        .take(limit)
+        .map(|res| res.map(|item| item.1))
+        .collect::<StdResult<Vec<_>>>()?;
 
-    let mut next_bull_amount = Uint128::zero();
-    let mut next_bear_amount = Uint128::zero();
+    Ok(FinishedRoundsResponse {
+        rounds: merge_summaries(rounds, summaries, order, limit),
+    })
+}
 
-    if let Some(bet_info) = next_bet_info {
-        match bet_info.direction {
-            Direction::Bull => {
-                next_bull_amount = bet_info.amount;
-            }
-            Direction::Bear => {
-                next_bear_amount = bet_info.amount;
-            }
-        }
-    }
+fn query_finished_rounds_by_denom(
+    deps: Deps,
+    denom: String,
+    start_after: Option<Uint128>,
+    limit: Option<u32>,
+    order: Option<OrderBy>,
+) -> StdResult<FinishedRoundsResponse> {
+    let limit = limit.unwrap_or(MAX_PAGE_LIMIT).min(MAX_PAGE_LIMIT) as usize;
+    let order: Order = order.unwrap_or(OrderBy::Asc).into();
+    let (min, max) = round_bounds(start_after, order);
 
-    let mut live_bull_amount: Uint128 = Uint128::zero();
-    let mut live_bear_amount: Uint128 = Uint128::zero();
-    if round_id > 1 {
-        let live_bet_key = (round_id - 2, deps.api.addr_validate(&address)?);
-        let live_bet_info = bet_info_storage().may_load(deps.storage, live_bet_key)?;
+    let rounds = ROUNDS
+        .idx
+        .denom
+        .prefix(denom.clone())
+        .range(deps.storage, min.clone(), max.clone(), order)
+        .take(limit)
+        .map(|res| res.map(|item| item.1))
+        .collect::<StdResult<Vec<_>>>()?;
+    let summaries = ROUND_SUMMARIES
+        .idx
+        .denom
+        .prefix(denom)
+        .range(deps.storage, min, max, order)
+        .take(limit)
+        .map(|res| res.map(|item| item.1))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(FinishedRoundsResponse {
+        rounds: merge_summaries(rounds, summaries, order, limit),
+    })
+}
 
-        if let Some(bet_info) = live_bet_info {
-            match bet_info.direction {
+/// Pagination bounds over round ids, start_after is excluded in both directions
+fn round_bounds(
+    start_after: Option<Uint128>,
+    order: Order,
+) -> (Option<Bound<'static, u128>>, Option<Bound<'static, u128>>) {
+    let start = start_after.map(|round_id| Bound::exclusive(round_id.u128()));
+    match order {
+        Order::Ascending => (start, None),
+        Order::Descending => (None, start),
+    }
+}
+
+fn query_my_current_position(deps: Deps, address: String) -> StdResult<MyCurrentPositionResponse> {
+    let round_id = NEXT_ROUND_ID.load(deps.storage)?;
+    let player = deps.api.addr_validate(&address)?;
+    let mut positions: Vec<CollateralPosition> = vec![];
+
+    let next_bet_key = (round_id - 1, player.clone());
+    if let Some(bet_info) = bet_info_storage().may_load(deps.storage, next_bet_key)? {
+        let position = position_mut(&mut positions, &bet_info.collateral);
+        for side in bet_info.sides() {
+            match side.direction {
                 Direction::Bull => {
-                    live_bull_amount = bet_info.amount;
+                    position.next_bull_amount = side.amount;
                 }
                 Direction::Bear => {
-                    live_bear_amount = bet_info.amount;
+                    position.next_bear_amount = side.amount;
                 }
             }
         }
     }
 
-    Ok(MyCurrentPositionResponse {
-        next_bear_amount,
-        next_bull_amount,
-        live_bear_amount,
-        live_bull_amount,
-    })
+    if round_id > 1 {
+        let live_bet_key = (round_id - 2, player);
+        if let Some(bet_info) = bet_info_storage().may_load(deps.storage, live_bet_key)? {
+            let position = position_mut(&mut positions, &bet_info.collateral);
+            for side in bet_info.sides() {
+                match side.direction {
+                    Direction::Bull => {
+                        position.live_bull_amount = side.amount;
+                    }
+                    Direction::Bear => {
+                        position.live_bear_amount = side.amount;
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(MyCurrentPositionResponse { positions })
+}
+
+fn position_mut<'a>(
+    positions: &'a mut Vec<CollateralPosition>,
+    collateral: &str,
+) -> &'a mut CollateralPosition {
+    let index = match positions.iter().position(|p| p.collateral == collateral) {
+        Some(index) => index,
+        None => {
+            positions.push(CollateralPosition {
+                collateral: collateral.to_string(),
+                live_bear_amount: Uint128::zero(),
+                live_bull_amount: Uint128::zero(),
+                next_bear_amount: Uint128::zero(),
+                next_bull_amount: Uint128::zero(),
+            });
+            positions.len() - 1
+        }
+    };
+    &mut positions[index]
 }
 
 fn query_status(deps: Deps, env: Env) -> StdResult<StatusResponse> {
@@ -780,6 +1996,7 @@ fn query_status(deps: Deps, env: Env) -> StdResult<StatusResponse> {
         bidding_round,
         live_round,
         current_time,
+        sponsorships: query_active_sponsorships(deps)?,
     })
 }
 
@@ -895,121 +2112,41 @@ pub fn query_claim_info_by_user(
     Ok(ClaimInfoResponse { claim_info })
 }
 
-pub fn query_my_pending_reward(deps: Deps, player: Addr) -> StdResult<PendingRewardResponse> {
-    let my_game_list = query_my_games_without_limit(deps, player.clone())?;
-    let mut winnings = Uint128::zero();
-
-    for game in my_game_list.my_game_list {
-        let round_id = game.round_id;
-        let round = ROUNDS.may_load(deps.storage, round_id.u128())?;
-
-        if round.is_none() {
-            continue;
-        }
-        let round = round.unwrap();
-
-        let pool_shares = round.bear_amount + round.bull_amount;
-
-        if round.bear_amount == Uint128::zero() || round.bull_amount == Uint128::zero() {
-            continue;
-        } else {
-            let round_winnings = match round.winner {
-                Some(Direction::Bull) => {
-                    /* Only claimable once */
-                    match game.direction {
-                        Direction::Bull => {
-                            let won_shares = game.amount;
-                            pool_shares.multiply_ratio(won_shares, round.bull_amount)
-                        }
-                        Direction::Bear => Uint128::zero(),
-                    }
-                }
-                Some(Direction::Bear) => {
-                    /* Only claimable once */
-                    match game.direction {
-                        Direction::Bull => Uint128::zero(),
-                        Direction::Bear => {
-                            let won_shares = game.amount;
-                            pool_shares.multiply_ratio(won_shares, round.bear_amount)
-                        }
-                    }
-                }
-                None => {
-                    /* Only claimable once */
-                    game.amount
-                }
-            };
-
-            /* Count it up */
-            winnings += round_winnings;
-        }
-    }
+pub fn query_my_pending_reward(
+    deps: Deps,
+    player: Addr,
+    collateral: String,
+) -> StdResult<PendingRewardResponse> {
+    let unclaimed = query_unclaimed_total(deps.storage, &player, &collateral)?;
 
     Ok(PendingRewardResponse {
-        pending_reward: winnings,
+        pending_reward: unclaimed.reward,
     })
 }
 
 pub fn query_my_pending_reward_rounds(
     deps: Deps,
     player: Addr,
+    collateral: String,
+    start_after: Option<Uint128>,
+    limit: Option<u32>,
 ) -> StdResult<PendingRewardRoundsResponse> {
-    let my_game_list = query_my_games_without_limit(deps, player.clone())?;
-    let mut winnings = Uint128::zero();
-    let mut winnings_per_round: Vec<(Uint128, Uint128)> = vec![];
-
-    for game in my_game_list.my_game_list {
-        let round_id = game.round_id;
-        let round = ROUNDS.may_load(deps.storage, round_id.u128())?;
-
-        if round.is_none() {
-            continue;
-        }
-        let round = round.unwrap();
-
-        let pool_shares = round.bear_amount + round.bull_amount;
-
-        if round.bear_amount == Uint128::zero() || round.bull_amount == Uint128::zero() {
-            continue;
-        } else {
-            let round_winnings = match round.winner {
-                Some(Direction::Bull) => {
-                    /* Only claimable once */
-                    match game.direction {
-                        Direction::Bull => {
-                            let won_shares = game.amount;
-                            pool_shares.multiply_ratio(won_shares, round.bull_amount)
-                        }
-                        Direction::Bear => Uint128::zero(),
-                    }
-                }
-                Some(Direction::Bear) => {
-                    /* Only claimable once */
-                    match game.direction {
-                        Direction::Bull => Uint128::zero(),
-                        Direction::Bear => {
-                            let won_shares = game.amount;
-                            pool_shares.multiply_ratio(won_shares, round.bear_amount)
-                        }
-                    }
-                }
-                None => {
-                    /* Only claimable once */
-                    game.amount
-                }
-            };
-
-            /* Count it up */
-            winnings += round_winnings;
-            if round_winnings != Uint128::zero() {
-                winnings_per_round.push((round_id, round_winnings))
-            }
-        }
-    }
+    let pending_reward_rounds = query_unclaimed_rounds(
+        deps.storage,
+        &player,
+        &collateral,
+        start_after,
+        limit,
+        |unclaimed| !unclaimed.reward.is_zero(),
+    )?
+    .into_iter()
+    .map(|(round_id, unclaimed)| (round_id, unclaimed.reward))
+    .collect();
+    let unclaimed = query_unclaimed_total(deps.storage, &player, &collateral)?;
 
     Ok(PendingRewardRoundsResponse {
-        pending_reward_rounds: winnings_per_round,
-        pending_reward_total: winnings,
+        pending_reward_rounds,
+        pending_reward_total: unclaimed.reward,
     })
 }
 
@@ -1024,7 +2161,7 @@ pub fn query_my_pending_reward_round(
     let bet_info_key = bet_info_key(round_id.u128(), &player);
     let game = bet_info_storage().may_load(deps.storage, bet_info_key)?;
     if let Some(_game) = game {
-        my_game_list.push(_game)
+        my_game_list.extend(_game.sides())
     }
 
     for game in my_game_list {
@@ -1036,9 +2173,15 @@ pub fn query_my_pending_reward_round(
         }
         let round = round.unwrap();
 
-        let pool_shares = round.bear_amount + round.bull_amount;
+        if let Some(fixed_payout) = game.fixed_payout {
+            winnings += fixed_odds_winnings(&round, &game, fixed_payout);
+            continue;
+        }
+
+        let pool = find_pool(&round.pools, &game.collateral);
+        let pool_shares = pool.prize();
 
-        if round.bear_amount == Uint128::zero() || round.bull_amount == Uint128::zero() {
+        if pool.is_refund() {
             winnings += game.amount;
         } else {
             let round_winnings = match round.winner {
@@ -1046,8 +2189,9 @@ pub fn query_my_pending_reward_round(
                     /* Only claimable once */
                     match game.direction {
                         Direction::Bull => {
-                            let won_shares = game.amount;
-                            pool_shares.multiply_ratio(won_shares, round.bull_amount)
+                            let won_shares = game.shares();
+                            pool_shares
+                                .multiply_ratio(won_shares, pool.side_shares(&Direction::Bull))
                         }
                         Direction::Bear => Uint128::zero(),
                     }
@@ -1057,8 +2201,9 @@ pub fn query_my_pending_reward_round(
                     match game.direction {
                         Direction::Bull => Uint128::zero(),
                         Direction::Bear => {
-                            let won_shares = game.amount;
-                            pool_shares.multiply_ratio(won_shares, round.bear_amount)
+                            let won_shares = game.shares();
+                            pool_shares
+                                .multiply_ratio(won_shares, pool.side_shares(&Direction::Bear))
                         }
                     }
                 }
@@ -1081,55 +2226,38 @@ pub fn query_my_pending_reward_round(
 pub fn query_my_refundable_amount(
     deps: Deps,
     player: Addr,
+    collateral: String,
 ) -> StdResult<PendingRefundableAmountResponse> {
-    let my_game_list = query_my_games_without_limit(deps, player.clone())?;
-    let mut refundable_amount = Uint128::zero();
-
-    for game in my_game_list.my_game_list {
-        let round_id = game.round_id;
-        let round = ROUNDS.may_load(deps.storage, round_id.u128())?;
-
-        if round.is_none() {
-            continue;
-        }
-        let round = round.unwrap();
-
-        if round.bear_amount == Uint128::zero() || round.bull_amount == Uint128::zero() {
-            refundable_amount += game.amount;
-        }
-    }
+    let unclaimed = query_unclaimed_total(deps.storage, &player, &collateral)?;
 
     Ok(PendingRefundableAmountResponse {
-        pending_refundable_amount: refundable_amount,
+        pending_refundable_amount: unclaimed.refund,
     })
 }
 
 pub fn query_my_refundable_amount_rounds(
     deps: Deps,
     player: Addr,
+    collateral: String,
+    start_after: Option<Uint128>,
+    limit: Option<u32>,
 ) -> StdResult<PendingRefundableAmountRoundsResponse> {
-    let my_game_list = query_my_games_without_limit(deps, player.clone())?;
-    let mut refundable_amount = Uint128::zero();
-    let mut refundable_amount_per_rounds: Vec<(Uint128, Uint128)> = vec![];
-
-    for game in my_game_list.my_game_list {
-        let round_id = game.round_id;
-        let round = ROUNDS.may_load(deps.storage, round_id.u128())?;
-
-        if round.is_none() {
-            continue;
-        }
-        let round = round.unwrap();
-
-        if round.bear_amount == Uint128::zero() || round.bull_amount == Uint128::zero() {
-            refundable_amount += game.amount;
-            refundable_amount_per_rounds.push((round_id, game.amount))
-        }
-    }
+    let pending_refundable_amount_rounds = query_unclaimed_rounds(
+        deps.storage,
+        &player,
+        &collateral,
+        start_after,
+        limit,
+        |unclaimed| !unclaimed.refund.is_zero(),
+    )?
+    .into_iter()
+    .map(|(round_id, unclaimed)| (round_id, unclaimed.refund))
+    .collect();
+    let unclaimed = query_unclaimed_total(deps.storage, &player, &collateral)?;
 
     Ok(PendingRefundableAmountRoundsResponse {
-        pending_refundable_amount_rounds: refundable_amount_per_rounds,
-        pending_refundable_amount_total: refundable_amount,
+        pending_refundable_amount_rounds,
+        pending_refundable_amount_total: unclaimed.refund,
     })
 }
 
@@ -1144,20 +2272,18 @@ pub fn query_my_games_without_limit(deps: Deps, player: Addr) -> StdResult<MyGam
     Ok(MyGameResponse { my_game_list })
 }
 
-pub fn query_total_spent(deps: Deps, player: Addr) -> StdResult<TotalSpentResponse> {
-    let total = TOTALS_SPENT.may_load(deps.storage, player)?;
+pub fn query_total_spent(
+    deps: Deps,
+    player: Addr,
+    collateral: String,
+) -> StdResult<TotalSpentResponse> {
+    let total = TOTALS_SPENT.may_load(deps.storage, (player, collateral))?;
 
     Ok(TotalSpentResponse {
         total_spent: total.unwrap_or(Uint128::zero()),
     })
 }
 
-pub fn query_get_admins(deps: Deps) -> StdResult<AdminsResponse> {
-    let admins = ADMINS.load(deps.storage)?;
-
-    Ok(AdminsResponse { admins })
-}
-
 pub fn query_get_round_denoms(deps: Deps) -> StdResult<RoundDenomsResponse> {
     let denoms = ROUND_DENOMS.load(deps.storage)?;
 
@@ -1174,6 +2300,153 @@ pub fn query_get_tickers(deps: Deps) -> StdResult<TickersResponse> {
     Ok(TickersResponse { tickers })
 }
 
+pub fn query_get_hooks(
+    deps: Deps,
+    start_after: Option<Addr>,
+    limit: Option<u32>,
+) -> StdResult<HooksResponse> {
+    let limit = limit.unwrap_or(MAX_PAGE_LIMIT).min(MAX_PAGE_LIMIT) as usize;
+    let hooks = HOOKS
+        .keys(
+            deps.storage,
+            start_after.map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .take(limit)
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(HooksResponse { hooks })
+}
+
+pub fn query_get_ibc_denoms(deps: Deps) -> StdResult<IbcDenomsResponse> {
+    let ibc_denoms = IBC_DENOMS
+        .range(deps.storage, None, None, Order::Ascending)
+        .map(|res| {
+            res.map(|(ibc_denom, _)| IbcDenom {
+                denom: ibc_denom.clone(),
+                ibc_denom,
+            })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+
+    Ok(IbcDenomsResponse { ibc_denoms })
+}
+
+pub fn query_simulate_bet(
+    deps: Deps,
+    env: Env,
+    round_id: Uint128,
+    direction: Direction,
+    collateral: String,
+    amount: Uint128,
+    player: Option<Addr>,
+) -> StdResult<SimulateBetResponse> {
+    let rejected = |e: ContractError| StdError::generic_err(e.to_string());
+
+    if amount.is_zero() {
+        return Err(StdError::generic_err(
+            "Amount to simulate must be higher than 0",
+        ));
+    }
+
+    if PAUSE.load(deps.storage)?.betting {
+        return Err(rejected(ContractError::BettingPaused {}));
+    }
+
+    let config = CONFIG.load(deps.storage)?;
+    let accepted = config.collateral(&collateral).ok_or_else(|| {
+        StdError::generic_err(format!("Collateral {} is not accepted", collateral))
+    })?;
+    if amount < accepted.minimum_bet {
+        return Err(rejected(ContractError::BetUnderMinBetAmount {}));
+    }
+
+    let round = assert_is_current_round(deps, round_id)?;
+    if env.block.time > round.open_time {
+        return Err(rejected(ContractError::RoundFinished {
+            round_id,
+            seconds: env.block.time.seconds() - round.open_time.seconds(),
+        }));
+    }
+
+    // The bet the player already has in the round, checked the same way placing it would be
+    let existing = match &player {
+        Some(player) => {
+            bet_info_storage().may_load(deps.storage, bet_info_key(round_id.u128(), player))?
+        }
+        None => None,
+    };
+    let mut bet = match existing {
+        Some(bet_info) => {
+            if bet_info.collateral != collateral {
+                return Err(rejected(ContractError::InvalidCollateralBet {
+                    collateral: bet_info.collateral,
+                }));
+            }
+            if bet_info.direction != direction && !config.hedging {
+                return Err(rejected(ContractError::InvalidDirectionBet {}));
+            }
+            if let (Some(player), Some(token_id)) = (&player, &bet_info.token_id) {
+                if POSITIONS.load(deps.storage, token_id.clone())?.owner != *player {
+                    return Err(rejected(ContractError::PositionTransferred {
+                        token_id: token_id.clone(),
+                    }));
+                }
+            }
+            bet_info
+        }
+        None => BetInfo {
+            player: player.unwrap_or_else(|| env.contract.address.clone()),
+            round_id,
+            collateral: collateral.clone(),
+            amount: Uint128::zero(),
+            direction: direction.clone(),
+            token_id: None,
+            fixed_payout: None,
+            weighted_amount: None,
+            hedge: None,
+        },
+    };
+
+    let shares = early_bet_shares(config.early_bet_bonus, &round, env.block.time, amount);
+    let mut pool = find_pool(&round.pools, &collateral);
+    pool.add_stake(&direction, amount, shares);
+    let fixed_payout = pool
+        .fixed_odds
+        .as_ref()
+        .map(|fixed_odds| amount.mul_floor(fixed_odds.odds()));
+    let side = bet.add_stake(&direction, amount, shares, fixed_payout);
+
+    /* Fixed odds are paid by the vault without fees,
+     * otherwise if nobody is on the other side the round gets refunded without fees */
+    let (gross_payout, fee) = if let Some(fixed_payout) = side.fixed_payout {
+        (fixed_payout, Uint128::zero())
+    } else if pool.is_refund() {
+        (side.amount, Uint128::zero())
+    } else {
+        let gross_payout = pool
+            .prize()
+            .multiply_ratio(side.shares(), pool.side_shares(&direction));
+        (
+            gross_payout,
+            compute_gaming_fee(accepted.gaming_fee, gross_payout)?,
+        )
+    };
+
+    Ok(SimulateBetResponse {
+        bull_amount: pool.bull_amount,
+        bear_amount: pool.bear_amount,
+        shares,
+        total_stake: side.amount,
+        multiplier: Decimal::checked_from_ratio(gross_payout, side.amount)
+            .map_err(|e| StdError::generic_err(e.to_string()))?,
+        gross_payout,
+        fee,
+        net_payout: gross_payout.checked_sub(fee)?,
+    })
+}
+
 fn assert_is_current_round(deps: Deps, round_id: Uint128) -> StdResult<NextRound> {
     let open_round = NEXT_ROUND.load(deps.storage)?;
 
@@ -1187,14 +2460,337 @@ fn assert_is_current_round(deps: Deps, round_id: Uint128) -> StdResult<NextRound
     Ok(open_round)
 }
 
-fn compute_gaming_fee(deps: Deps, gross: Uint128) -> StdResult<Uint128> {
-    let staker_fee = CONFIG.load(deps.storage)?.gaming_fee;
-
-    staker_fee
+fn compute_gaming_fee(gaming_fee: Uint128, gross: Uint128) -> StdResult<Uint128> {
+    gaming_fee
         .checked_multiply_ratio(gross, FEE_PRECISION * 100)
         .map_err(|e| StdError::generic_err(e.to_string()))
 }
 
+fn load_hooks(storage: &dyn Storage) -> StdResult<Vec<Addr>> {
+    HOOKS.keys(storage, None, None, Order::Ascending).collect()
+}
+
+pub(crate) fn load_collateral(
+    config: &Config,
+    collateral: &str,
+) -> Result<Collateral, ContractError> {
+    config
+        .collateral(collateral)
+        .cloned()
+        .ok_or(ContractError::CollateralNotAccepted {
+            collateral: collateral.to_string(),
+        })
+}
+
+/// Pool of `collateral` in the round, created if the collateral was added after the round started
+fn pool_mut<'a>(
+    pools: &'a mut Vec<CollateralPool>,
+    collateral: &Collateral,
+) -> &'a mut CollateralPool {
+    let key = collateral.key();
+    let index = match pools.iter().position(|pool| pool.collateral == key) {
+        Some(index) => index,
+        None => {
+            pools.push(collateral.pool());
+            pools.len() - 1
+        }
+    };
+    &mut pools[index]
+}
+
+/// Collaterals can be added and tuned but never removed, claims of old rounds need their config
+fn validate_config(
+    deps: Deps,
+    config: &Config,
+    old_config: Option<&Config>,
+) -> Result<(), ContractError> {
+    if config.next_round_seconds.is_zero() {
+        return Err(ContractError::InvalidRoundDuration {});
+    }
+    deps.api.addr_validate(config.users_contract.as_str())?;
+    validate_dev_wallets(&config.dev_wallet_list)?;
+    validate_collaterals(config, old_config)?;
+    validate_fixed_odds(deps, config)?;
+    if config.early_bet_bonus > Decimal::one() {
+        return Err(ContractError::InvalidEarlyBetBonus {});
+    }
+    if config.max_bid_extensions > 0 && config.bid_extension_seconds.is_zero() {
+        return Err(ContractError::InvalidBidExtension {});
+    }
+    if let Some(swap_router) = &config.swap_router {
+        deps.api.addr_validate(swap_router.as_str())?;
+    }
+
+    Ok(())
+}
+
+/// Dev fees are split between the wallets, their ratios must add up to 1 unless there are none
+fn validate_dev_wallets(dev_wallets: &[WalletInfo]) -> Result<(), ContractError> {
+    if dev_wallets.is_empty() {
+        return Ok(());
+    }
+
+    let total_ratio = dev_wallets
+        .iter()
+        .fold(Decimal::zero(), |total, dev_wallet| {
+            total + dev_wallet.ratio
+        });
+    if total_ratio != Decimal::one() {
+        return Err(ContractError::WrongRatio {});
+    }
+
+    Ok(())
+}
+
+fn validate_collaterals(config: &Config, old_config: Option<&Config>) -> Result<(), ContractError> {
+    if config.collaterals.is_empty() {
+        return Err(ContractError::CollateralsEmpty {});
+    }
+
+    let mut keys: Vec<String> = vec![];
+    for collateral in config.collaterals.iter() {
+        let key = collateral.key();
+        if keys.contains(&key) {
+            return Err(ContractError::DuplicatedCollateral { collateral: key });
+        }
+        if collateral.minimum_bet.is_zero() {
+            return Err(ContractError::InvalidMinimumBet { collateral: key });
+        }
+        if collateral.gaming_fee > Uint128::new(MAX_GAMING_FEE) {
+            return Err(ContractError::GamingFeeTooHigh {
+                collateral: key,
+                max: Uint128::new(MAX_GAMING_FEE),
+            });
+        }
+        collateral.experience.validate()?;
+        keys.push(key);
+    }
+
+    if let Some(old_config) = old_config {
+        for collateral in old_config.collaterals.iter() {
+            if !keys.contains(&collateral.key()) {
+                return Err(ContractError::CollateralRemoved {
+                    collateral: collateral.key(),
+                });
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Pool shares of a stake placed at `now`. The bonus decreases linearly from `early_bet_bonus`
+/// when the bidding window opens to nothing at open time
+fn early_bet_shares(
+    early_bet_bonus: Decimal,
+    round: &NextRound,
+    now: Timestamp,
+    amount: Uint128,
+) -> Uint128 {
+    let window = round
+        .open_time
+        .seconds()
+        .saturating_sub(round.bid_time.seconds());
+    if early_bet_bonus.is_zero() || window == 0 {
+        return amount;
+    }
+
+    let remaining = round
+        .open_time
+        .seconds()
+        .saturating_sub(now.seconds())
+        .min(window);
+    let bonus = early_bet_bonus * Decimal::from_ratio(remaining, window);
+    amount.mul_floor(Decimal::one() + bonus)
+}
+
+/// Vaults of fixed odds collaterals must hold the collateral and pay at least the stake back
+fn validate_fixed_odds(deps: Deps, config: &Config) -> Result<(), ContractError> {
+    for collateral in config.collaterals.iter() {
+        let Some(fixed_odds) = &collateral.fixed_odds else {
+            continue;
+        };
+        if fixed_odds.house_edge > Decimal::percent(50) {
+            return Err(ContractError::InvalidHouseEdge {});
+        }
+
+        deps.api.addr_validate(fixed_odds.vault.as_str())?;
+        let vault_config: VaultConfig = deps
+            .querier
+            .query_wasm_smart(fixed_odds.vault.to_string(), &VaultQueryMsg::Config {})?;
+        if vault_config.denom != collateral.denom {
+            return Err(ContractError::VaultDenomMismatch {
+                collateral: collateral.key(),
+            });
+        }
+    }
+
+    Ok(())
+}
+
+/// Winnings of a bet taken by a vault: the fixed payout and its share of the sponsored boost
+/// if it won, the stake if nobody won
+pub(crate) fn fixed_odds_winnings(
+    round: &FinishedRound,
+    game: &BetInfo,
+    fixed_payout: Uint128,
+) -> Uint128 {
+    match &round.winner {
+        Some(winner) if *winner == game.direction => {
+            let pool = find_pool(&round.pools, &game.collateral);
+            fixed_payout
+                + pool
+                    .boost
+                    .multiply_ratio(game.amount, pool.side_amount(winner))
+        }
+        Some(_) => Uint128::zero(),
+        None => game.amount,
+    }
+}
+
+/// Records the payout of a round in the claim, both sides of a hedged bet add up to a single
+/// payout and claim record
+fn record_claim(
+    storage: &mut dyn Storage,
+    payouts: &mut Vec<RoundPayout>,
+    player: &Addr,
+    game: &BetInfo,
+    amount: Uint128,
+) -> StdResult<()> {
+    match payouts
+        .iter_mut()
+        .find(|payout| payout.round_id == game.round_id)
+    {
+        Some(payout) => payout.amount += amount,
+        None => payouts.push(RoundPayout {
+            round_id: game.round_id,
+            amount,
+        }),
+    }
+
+    let claim_info_key = claim_info_key(game.round_id.u128(), player);
+    let claimed_amount = claim_info_storage()
+        .may_load(storage, claim_info_key.clone())?
+        .map(|claim| claim.claimed_amount)
+        .unwrap_or_default();
+    claim_info_storage().save(
+        storage,
+        claim_info_key,
+        &ClaimInfo {
+            player: player.clone(),
+            round_id: game.round_id,
+            collateral: game.collateral.clone(),
+            claimed_amount: claimed_amount + amount,
+        },
+    )
+}
+
+pub(crate) fn velo_event(action: VeloAction) -> VeloEvent {
+    VeloEvent::new(VeloContract::PredictionGame, action)
+}
+
+/// Elo only moves on rounds that had a winner and money on both sides
+pub(crate) fn record_elo(
+    elo_delta: &mut EloDelta,
+    k_factor: u64,
+    round: &FinishedRound,
+    game: &BetInfo,
+) {
+    let Some(winner) = &round.winner else {
+        return;
+    };
+
+    let pool = find_pool(&round.pools, &game.collateral);
+    elo_delta.record(
+        k_factor,
+        pool.side_amount(&game.direction),
+        pool.total(),
+        *winner == game.direction,
+    );
+}
+
+/// Saves a finished round, settles its sponsorships and asks the vaults and hooks to settle it
+pub(crate) fn finish_round(
+    storage: &mut dyn Storage,
+    config: &Config,
+    finished_round: &mut FinishedRound,
+    mut resp: Response,
+) -> Result<Response, ContractError> {
+    resp = settle_sponsorships(storage, config, finished_round, resp)?;
+    ROUNDS.save(storage, finished_round.id.u128(), finished_round)?;
+
+    // Vaults send what the winners of their rounds are owed, claims are paid from it
+    for pool in finished_round.pools.iter() {
+        if let Some(fixed_odds) = &pool.fixed_odds {
+            if !pool.total().is_zero() {
+                resp = resp.add_message(WasmMsg::Execute {
+                    contract_addr: fixed_odds.vault.to_string(),
+                    msg: to_json_binary(&VaultExecuteMsg::SettleRound {
+                        round_id: finished_round.id,
+                        winner: finished_round.winner.clone(),
+                    })?,
+                    funds: vec![],
+                });
+            }
+        }
+    }
+
+    Ok(resp.add_submessages(hook_messages(
+        load_hooks(storage)?,
+        GameHookMsg::Settle {
+            id: finished_round.id,
+            result: finished_round.winner.as_ref().map(|w| w.to_string()),
+        },
+    )?))
+}
+
+/// A round opens when it has enough players and one of its pools reaches the minimum of its
+/// collateral, unless no collateral sets one
+fn meets_open_minimums(config: &Config, round: &NextRound) -> bool {
+    if round.players < config.min_players_to_open {
+        return false;
+    }
+
+    let mut minimums = config
+        .collaterals
+        .iter()
+        .filter(|collateral| !collateral.min_pool_to_open.is_zero())
+        .peekable();
+    if minimums.peek().is_none() {
+        return true;
+    }
+    minimums.any(|collateral| {
+        find_pool(&round.pools, &collateral.key()).total() >= collateral.min_pool_to_open
+    })
+}
+
+/// Finished round without a winner where every stake is returned
+pub(crate) fn void_round(round: &NextRound, now: Timestamp) -> FinishedRound {
+    FinishedRound {
+        id: round.id,
+        bid_time: round.bid_time,
+        open_time: now,
+        close_time: now,
+        open_price: Int128::zero(),
+        close_price: Int128::zero(),
+        winner: None,
+        pools: void_pools(&round.pools),
+        denom: round.denom.clone(),
+        compacted: false,
+    }
+}
+
+pub(crate) fn void_pools(pools: &[CollateralPool]) -> Vec<CollateralPool> {
+    pools
+        .iter()
+        .cloned()
+        .map(|pool| CollateralPool {
+            voided: true,
+            ..pool
+        })
+        .collect()
+}
+
 fn compute_round_open(
     deps: Deps<NeutronQuery>,
     env: Env,
@@ -1212,8 +2808,7 @@ fn compute_round_open(
             .time
             .plus_seconds(config.next_round_seconds.u128() as u64),
         open_price,
-        bull_amount: round.bull_amount,
-        bear_amount: round.bear_amount,
+        pools: round.pools.clone(),
         denom: round.denom.to_string(),
     })
 }
@@ -1224,38 +2819,11 @@ fn get_current_price(
     denom: String,
 ) -> Result<Int128, ContractError> {
     let ticker = PRICE_TICKERS.load(deps.storage, denom)?;
-    let oracle_query = OracleQuery::GetPrice {
-        currency_pair: CurrencyPair {
-            base: ticker,
-            quote: USD_TICKER.to_string(),
-        },
-    };
-    let price_response: GetPriceResponse = deps.querier.query(&oracle_query.into())?;
-    let dt = DateTime::parse_from_rfc3339(&price_response.price.block_timestamp)
-        .expect("Failed to parse timestamp");
-    // Convert to a Unix timestamp (seconds since the Unix epoch)
-    let unix_timestamp = dt.timestamp();
+    let oracle_price = query_usd_price(&deps.querier, ticker)?;
 
-    assert_price_not_too_old(current_timestamp, unix_timestamp as u64)?;
-    let normalized_price = normalize_price(price_response)?;
-
-    Ok(normalized_price)
-}
+    assert_price_not_too_old(current_timestamp, oracle_price.timestamp)?;
 
-fn normalize_price(price_response: GetPriceResponse) -> Result<Int128, ContractError> {
-    let price = price_response.price.price;
-    let normalized_price = match price_response.decimals.cmp(&PRICE_DECIMALS) {
-        Ordering::Greater => {
-            let divisor = i128::pow(10, (price_response.decimals - PRICE_DECIMALS) as u32);
-            price.checked_div(Int128::from(divisor))?
-        }
-        Ordering::Less => {
-            let multiplier = i128::pow(10, (PRICE_DECIMALS - price_response.decimals) as u32);
-            price.checked_mul(Int128::from(multiplier))?
-        }
-        Ordering::Equal => price,
-    };
-    Ok(normalized_price)
+    Ok(oracle_price.price)
 }
 
 fn compute_round_close(
@@ -1289,22 +2857,14 @@ fn compute_round_close(
         open_time: round.open_time,
         close_time: round.close_time,
         open_price: round.open_price,
-        bear_amount: round.bear_amount,
-        bull_amount: round.bull_amount,
+        pools: round.pools.clone(),
         winner,
         close_price,
         denom: round.denom.to_string(),
+        compacted: false,
     })
 }
 
-fn assert_not_halted(deps: Deps) -> StdResult<bool> {
-    let is_halted = IS_HALTED.load(deps.storage)?;
-    if is_halted {
-        return Err(StdError::generic_err("Contract is halted"));
-    }
-    Ok(true)
-}
-
 fn assert_price_not_too_old(
     current_timestamp: u64,
     price_timestamp: u64,
@@ -1318,79 +2878,135 @@ fn assert_price_not_too_old(
 
 fn execute_update_halt(
     deps: DepsMut,
+    env: Env,
     info: MessageInfo,
     is_halted: bool,
 ) -> Result<Response, ContractError> {
-    assert_is_admin(deps.as_ref(), info)?;
-    IS_HALTED.save(deps.storage, &is_halted)?;
-    Ok(Response::new().add_event(Event::new("predictiona").add_attribute("halt_games", "true")))
+    let action = if is_halted {
+        VeloAction::Halt
+    } else {
+        VeloAction::Resume
+    };
+    // Players can still claim while halted
+    let pause = PauseState {
+        betting: is_halted,
+        rounds: is_halted,
+        ..PauseState::default()
+    };
+    execute_update_pause(deps, env, info, pause, action)
 }
 
-fn assert_is_admin(deps: Deps, info: MessageInfo) -> StdResult<bool> {
-    let admins = ADMINS.load(deps.storage)?;
-    if !admins.contains(&info.sender) {
-        return Err(StdError::generic_err(format!(
-            "Only an admin can execute this function. Sender: {}",
-            info.sender
-        )));
-    }
+/// Saves the operations paused. Rounds are frozen while paused: on resume, the bidding and live
+/// rounds are delayed by the time paused so they keep the time they had left
+fn execute_update_pause(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    mut pause: PauseState,
+    action: VeloAction,
+) -> Result<Response, ContractError> {
+    assert_role(deps.storage, Role::Pauser, &info.sender)?;
 
-    Ok(true)
+    let old_pause = PAUSE.load(deps.storage)?;
+    if old_pause.emergency_exit {
+        return Err(ContractError::EmergencyExitActive {});
+    }
+    let now = env.block.time;
+    match (old_pause.rounds_paused_at, pause.rounds) {
+        (Some(paused_at), true) => pause.rounds_paused_at = Some(paused_at),
+        (Some(paused_at), false) => {
+            let paused_seconds = now.seconds().saturating_sub(paused_at.seconds());
+            if let Some(mut live_round) = LIVE_ROUND.may_load(deps.storage)? {
+                live_round.close_time = live_round.close_time.plus_seconds(paused_seconds);
+                LIVE_ROUND.save(deps.storage, &live_round)?;
+            }
+            if let Some(mut next_round) = NEXT_ROUND.may_load(deps.storage)? {
+                next_round.open_time = next_round.open_time.plus_seconds(paused_seconds);
+                next_round.close_time = next_round.close_time.plus_seconds(paused_seconds);
+                NEXT_ROUND.save(deps.storage, &next_round)?;
+            }
+            pause.rounds_paused_at = None;
+        }
+        (None, true) => pause.rounds_paused_at = Some(now),
+        (None, false) => pause.rounds_paused_at = None,
+    }
+    PAUSE.save(deps.storage, &pause)?;
+
+    let is_halted = pause.betting && pause.rounds;
+    let mut event = velo_event(action)
+        .add_attribute(attr::HALTED, is_halted.to_string())
+        .add_attribute(attr::BETTING, pause.betting.to_string())
+        .add_attribute(attr::ROUNDS, pause.rounds.to_string())
+        .add_attribute(attr::CLAIMS, pause.claims.to_string());
+    if let Some(reason) = &pause.reason {
+        event = event.add_attribute(attr::REASON, reason);
+    }
+    if let Some(resume_at) = pause.resume_at {
+        event = event.add_attribute(attr::RESUME_AT, resume_at.seconds().to_string());
+    }
+    Ok(Response::new().add_event(event))
 }
 
-fn execute_add_admin(
+fn update_ownership(
     deps: DepsMut,
+    env: Env,
     info: MessageInfo,
-    new_admin: Addr,
+    action: cw_ownable::Action,
 ) -> Result<Response, ContractError> {
-    assert_is_admin(deps.as_ref(), info)?;
-    deps.api.addr_validate(new_admin.as_ref())?;
-    let mut admins = ADMINS.load(deps.storage)?;
-
-    admins.push(new_admin.clone());
-
-    ADMINS.save(deps.storage, &admins)?;
-
-    Ok(Response::new().add_attribute("add_admin", new_admin.to_string()))
+    let ownership = cw_ownable::update_ownership(deps, &env.block, &info.sender, action)?;
+    Ok(Response::new().add_event(
+        velo_event(VeloAction::UpdateOwnership).add_attributes(ownership.into_attributes()),
+    ))
 }
 
-fn execute_remove_admin(
+fn execute_grant_role(
     deps: DepsMut,
     info: MessageInfo,
-    old_admin: Addr,
+    role: Role,
+    address: String,
 ) -> Result<Response, ContractError> {
-    assert_is_admin(deps.as_ref(), info)?;
-    let mut admins = ADMINS.load(deps.storage)?;
-    admins.retain(|admin| admin != old_admin);
-
-    if admins.is_empty() {
-        return Err(ContractError::NeedOneAdmin {});
-    }
+    let member = rbac::grant_role(deps.storage, deps.api, &info.sender, role, &address)?;
+    Ok(Response::new().add_event(
+        velo_event(VeloAction::GrantRole)
+            .add_attribute(attr::ROLE, role.as_str())
+            .add_attribute(attr::MEMBER, member),
+    ))
+}
 
-    ADMINS.save(deps.storage, &admins)?;
-    Ok(Response::new().add_attribute("remove_admin", old_admin.to_string()))
+fn execute_revoke_role(
+    deps: DepsMut,
+    info: MessageInfo,
+    role: Role,
+    address: String,
+) -> Result<Response, ContractError> {
+    let member = rbac::revoke_role(deps.storage, deps.api, &info.sender, role, &address)?;
+    Ok(Response::new().add_event(
+        velo_event(VeloAction::RevokeRole)
+            .add_attribute(attr::ROLE, role.as_str())
+            .add_attribute(attr::MEMBER, member),
+    ))
 }
 
 fn execute_modify_dev_wallets(
     deps: DepsMut,
+    env: Env,
     info: MessageInfo,
     new_wallets: Vec<WalletInfo>,
 ) -> Result<Response, ContractError> {
-    assert_is_admin(deps.as_ref(), info)?;
-    let mut total_ratio = Decimal::zero();
-    for dev_wallet in new_wallets.clone() {
-        total_ratio += dev_wallet.ratio;
-    }
-
-    if total_ratio != Decimal::one() {
+    assert_role(deps.storage, Role::ConfigAdmin, &info.sender)?;
+    if new_wallets.is_empty() {
         return Err(ContractError::WrongRatio {});
     }
-
-    let mut config = CONFIG.load(deps.storage)?;
-    config.dev_wallet_list = new_wallets;
-    CONFIG.save(deps.storage, &config)?;
-
-    Ok(Response::new().add_attribute("velo_action", "new_dev_wallets"))
+    validate_dev_wallets(&new_wallets)?;
+
+    queue_or_apply_change(
+        deps,
+        env,
+        info.sender,
+        ConfigChange::DevWallets {
+            dev_wallets: new_wallets,
+        },
+    )
 }
 
 fn execute_add_ticker(
@@ -1399,10 +3015,14 @@ fn execute_add_ticker(
     denom: String,
     ticker: String,
 ) -> Result<Response, ContractError> {
-    assert_is_admin(deps.as_ref(), info)?;
-    PRICE_TICKERS.save(deps.storage, denom, &ticker)?;
-
-    Ok(Response::new().add_attribute("velo_action", "add_identifier"))
+    assert_role(deps.storage, Role::OracleManager, &info.sender)?;
+    PRICE_TICKERS.save(deps.storage, denom.clone(), &ticker)?;
+
+    Ok(Response::new().add_event(
+        velo_event(VeloAction::AddTicker)
+            .add_attribute(attr::DENOM, denom)
+            .add_attribute(attr::TICKER, ticker),
+    ))
 }
 
 fn execute_modify_bet_array(
@@ -1410,7 +3030,7 @@ fn execute_modify_bet_array(
     info: MessageInfo,
     denoms: Vec<String>,
 ) -> Result<Response, ContractError> {
-    assert_is_admin(deps.as_ref(), info)?;
+    assert_role(deps.storage, Role::OracleManager, &info.sender)?;
 
     if denoms.is_empty() {
         return Err(ContractError::DenomsEmpty {});
@@ -1424,5 +3044,87 @@ fn execute_modify_bet_array(
 
     ROUND_DENOMS.save(deps.storage, &denoms)?;
 
-    Ok(Response::new().add_attribute("velo_action", "modify_bet_array"))
+    Ok(Response::new().add_event(
+        velo_event(VeloAction::ModifyBetArray).add_attribute(attr::DENOMS, denoms.join(",")),
+    ))
+}
+
+fn execute_add_hook(
+    deps: DepsMut,
+    info: MessageInfo,
+    address: Addr,
+) -> Result<Response, ContractError> {
+    assert_role(deps.storage, Role::ConfigAdmin, &info.sender)?;
+    deps.api.addr_validate(address.as_str())?;
+
+    if HOOKS.has(deps.storage, address.clone()) {
+        return Err(ContractError::HookAlreadyRegistered {});
+    }
+
+    if load_hooks(deps.storage)?.len() >= MAX_HOOKS {
+        return Err(ContractError::TooManyHooks { max: MAX_HOOKS });
+    }
+
+    HOOKS.save(deps.storage, address.clone(), &Empty {})?;
+
+    Ok(Response::new()
+        .add_event(velo_event(VeloAction::AddHook).add_attribute(attr::HOOK, address)))
+}
+
+fn execute_remove_hook(
+    deps: DepsMut,
+    info: MessageInfo,
+    address: Addr,
+) -> Result<Response, ContractError> {
+    assert_role(deps.storage, Role::ConfigAdmin, &info.sender)?;
+
+    if !HOOKS.has(deps.storage, address.clone()) {
+        return Err(ContractError::HookNotRegistered {});
+    }
+
+    HOOKS.remove(deps.storage, address.clone());
+
+    Ok(Response::new()
+        .add_event(velo_event(VeloAction::RemoveHook).add_attribute(attr::HOOK, address)))
+}
+
+fn execute_add_ibc_denom(
+    deps: DepsMut,
+    info: MessageInfo,
+    ibc_denom: String,
+) -> Result<Response, ContractError> {
+    assert_role(deps.storage, Role::ConfigAdmin, &info.sender)?;
+
+    // The pools and winnings are in the collateral, so only its own native token can be accepted
+    let config = CONFIG.load(deps.storage)?;
+    if !config
+        .collaterals
+        .iter()
+        .any(|collateral| collateral.denom == Denom::Native(ibc_denom.clone()))
+    {
+        return Err(ContractError::IbcDenomNotCollateral { ibc_denom });
+    }
+
+    IBC_DENOMS.save(deps.storage, ibc_denom.clone(), &Empty {})?;
+
+    Ok(Response::new()
+        .add_event(velo_event(VeloAction::AddIbcDenom).add_attribute(attr::IBC_DENOM, ibc_denom)))
+}
+
+fn execute_remove_ibc_denom(
+    deps: DepsMut,
+    info: MessageInfo,
+    ibc_denom: String,
+) -> Result<Response, ContractError> {
+    assert_role(deps.storage, Role::ConfigAdmin, &info.sender)?;
+
+    if !IBC_DENOMS.has(deps.storage, ibc_denom.clone()) {
+        return Err(ContractError::IbcDenomNotAllowed { denom: ibc_denom });
+    }
+
+    IBC_DENOMS.remove(deps.storage, ibc_denom.clone());
+
+    Ok(Response::new().add_event(
+        velo_event(VeloAction::RemoveIbcDenom).add_attribute(attr::IBC_DENOM, ibc_denom),
+    ))
 }
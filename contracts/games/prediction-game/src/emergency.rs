@@ -0,0 +1,130 @@
+use cosmwasm_std::{DepsMut, Env, MessageInfo, Order, Response, StdResult, Timestamp, Uint128};
+use general::events::{attr, VeloAction};
+use general::rbac::{assert_role, Role};
+use neutron_sdk::bindings::query::NeutronQuery;
+use prediction::prediction_game::{BetInfo, FinishedRound, LiveRound};
+
+use crate::contract::{finish_round, settle_round, velo_event, void_pools, void_round};
+use crate::error::ContractError;
+use crate::sponsorships::refund_sponsorships;
+use crate::state::{bet_info_storage, CONFIG, LIVE_ROUND, NEXT_ROUND, PAUSE, POSITIONS, ROUNDS};
+
+const DEFAULT_REFUND_LIMIT: u32 = 10;
+// Every refund sends funds, experience and hooks, keep pages small
+const MAX_REFUND_LIMIT: u32 = 30;
+
+/// Voids the bidding and live rounds of a halted game, settling them without a price, and
+/// refunds what is left of the sponsorships. Betting and rounds can't be resumed afterwards
+pub fn execute_emergency_exit(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    assert_role(deps.storage, Role::Owner, &info.sender)?;
+
+    let mut pause = PAUSE.load(deps.storage)?;
+    if pause.emergency_exit {
+        return Err(ContractError::EmergencyExitActive {});
+    }
+    if !pause.rounds {
+        return Err(ContractError::RoundsNotPaused {});
+    }
+
+    let config = CONFIG.load(deps.storage)?;
+    let now = env.block.time;
+    let mut resp = Response::new();
+
+    let mut voided_rounds = vec![];
+    if let Some(live_round) = LIVE_ROUND.may_load(deps.storage)? {
+        voided_rounds.push(void_live_round(&live_round, now));
+        LIVE_ROUND.remove(deps.storage);
+    }
+    if let Some(next_round) = NEXT_ROUND.may_load(deps.storage)? {
+        voided_rounds.push(void_round(&next_round, now));
+        NEXT_ROUND.remove(deps.storage);
+    }
+    for mut voided_round in voided_rounds {
+        resp = finish_round(deps.storage, &config, &mut voided_round, resp)?;
+        resp = resp.add_event(
+            velo_event(VeloAction::VoidRound)
+                .add_attribute(attr::ROUND_ID, voided_round.id.to_string()),
+        );
+    }
+    resp = refund_sponsorships(deps.storage, &config, resp)?;
+
+    pause.betting = true;
+    pause.rounds = true;
+    pause.claims = false;
+    pause.emergency_exit = true;
+    pause.rounds_paused_at = pause.rounds_paused_at.or(Some(now));
+    PAUSE.save(deps.storage, &pause)?;
+
+    Ok(resp.add_event(velo_event(VeloAction::EmergencyExit)))
+}
+
+/// Pushes the stakes of players that didn't claim a voided round, paying the holder of
+/// tokenized bets. Claimed bets are removed, so each call takes the next ones
+pub fn execute_emergency_refund(
+    mut deps: DepsMut<NeutronQuery>,
+    env: Env,
+    info: MessageInfo,
+    round_id: Uint128,
+    limit: Option<u32>,
+) -> Result<Response, ContractError> {
+    assert_role(deps.storage, Role::Owner, &info.sender)?;
+
+    let round = ROUNDS.load(deps.storage, round_id.u128())?;
+    if !round.pools.iter().all(|pool| pool.voided) {
+        return Err(ContractError::RoundNotVoided { round_id });
+    }
+
+    let limit = limit.unwrap_or(DEFAULT_REFUND_LIMIT).min(MAX_REFUND_LIMIT) as usize;
+    let bets = bet_info_storage()
+        .prefix(round_id.u128())
+        .range(deps.storage, None, None, Order::Ascending)
+        .take(limit)
+        .map(|item| item.map(|(_, bet)| bet))
+        .collect::<StdResult<Vec<BetInfo>>>()?;
+
+    let mut resp = Response::new();
+    for bet in bets {
+        let recipient = match &bet.token_id {
+            Some(token_id) => {
+                let position = POSITIONS.load(deps.storage, token_id.clone())?;
+                POSITIONS.remove(deps.storage, token_id.clone())?;
+                position.owner
+            }
+            None => bet.player.clone(),
+        };
+        let refund = settle_round(
+            deps.branch(),
+            env.clone(),
+            bet.player.clone(),
+            recipient,
+            round_id,
+        )?;
+        resp = resp
+            .add_submessages(refund.messages)
+            .add_events(refund.events);
+    }
+
+    Ok(resp.add_event(
+        velo_event(VeloAction::EmergencyRefund).add_attribute(attr::ROUND_ID, round_id.to_string()),
+    ))
+}
+
+/// Live round settled without a closing price, every stake is returned
+fn void_live_round(round: &LiveRound, now: Timestamp) -> FinishedRound {
+    FinishedRound {
+        id: round.id,
+        bid_time: round.bid_time,
+        open_time: round.open_time,
+        close_time: now,
+        open_price: round.open_price,
+        close_price: round.open_price,
+        winner: None,
+        pools: void_pools(&round.pools),
+        denom: round.denom.clone(),
+        compacted: false,
+    }
+}
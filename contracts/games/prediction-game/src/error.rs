@@ -1,4 +1,8 @@
+use cw_ownable::OwnershipError;
 use cw_utils::PaymentError;
+use general::migration::MigrationError;
+use general::rbac::RbacError;
+use general::timelock::TimelockError;
 use thiserror::Error;
 
 use cosmwasm_std::{DivisionError, OverflowError, StdError, Uint128};
@@ -17,6 +21,18 @@ pub enum ContractError {
     #[error("{0}")]
     DivisionError(#[from] DivisionError),
 
+    #[error(transparent)]
+    Ownership(#[from] OwnershipError),
+
+    #[error(transparent)]
+    Rbac(#[from] RbacError),
+
+    #[error(transparent)]
+    Timelock(#[from] TimelockError),
+
+    #[error(transparent)]
+    Migration(#[from] MigrationError),
+
     #[error("Wrong denom sent")]
     InvalidFunds {},
 
@@ -36,15 +52,30 @@ pub enum ContractError {
     #[error("You cannot bet in both directions")]
     InvalidDirectionBet {},
 
+    #[error("You already bet with {collateral} in this round, a player bets with one collateral per round")]
+    InvalidCollateralBet { collateral: String },
+
+    #[error("Collaterals can not be empty")]
+    CollateralsEmpty {},
+
+    #[error("Collateral {} is not accepted", collateral)]
+    CollateralNotAccepted { collateral: String },
+
+    #[error("Collateral {} is duplicated", collateral)]
+    DuplicatedCollateral { collateral: String },
+
+    #[error(
+        "Collateral {} can't be removed, players may still have bets with it",
+        collateral
+    )]
+    CollateralRemoved { collateral: String },
+
     #[error("Unauthorized")]
     Unauthorized {},
 
     #[error("The sum of wallet ratio is not equal to 1")]
     WrongRatio {},
 
-    #[error("At least one admin must remain")]
-    NeedOneAdmin {},
-
     #[error("Denoms can not be empty")]
     DenomsEmpty {},
 
@@ -53,4 +84,118 @@ pub enum ContractError {
 
     #[error("Price is too old, try again")]
     PriceTooOld {},
+
+    #[error("Swap router is not set")]
+    SwapRouterNotSet {},
+
+    #[error("Swap route must start with the coin sent and end with the collateral")]
+    InvalidSwapRoute {},
+
+    #[error("Swap output {} is lower than the minimum {}", output, minimum)]
+    SwapOutputTooLow { output: Uint128, minimum: Uint128 },
+
+    #[error("Sender is not the ibc-hooks address of the original sender and channel")]
+    InvalidIbcSender {},
+
+    #[error("Denom {} received through IBC is not accepted", denom)]
+    IbcDenomNotAllowed { denom: String },
+
+    #[error("Winnings of this player are not sent back to another chain")]
+    NotReturnedToOrigin {},
+
+    #[error("IBC denom {} is not the denom of a native collateral", ibc_denom)]
+    IbcDenomNotCollateral { ibc_denom: String },
+
+    #[error("No failed IBC payout to send again")]
+    NoFailedIbcPayout {},
+
+    #[error(
+        "Bet is held as position {}, its holder claims it with claim_position",
+        token_id
+    )]
+    PositionTokenized { token_id: String },
+
+    #[error("Position {} was transferred, the bet can't be increased", token_id)]
+    PositionTransferred { token_id: String },
+
+    #[error("Position {} can't be claimed until its round is finished", token_id)]
+    PositionPending { token_id: String },
+
+    #[error("House edge can't be higher than 0.5, winners would get less than their stake")]
+    InvalidHouseEdge {},
+
+    #[error("Early bet bonus can't be higher than 1")]
+    InvalidEarlyBetBonus {},
+
+    #[error("Bid extensions need a duration")]
+    InvalidBidExtension {},
+
+    #[error("Round duration can't be 0")]
+    InvalidRoundDuration {},
+
+    #[error("Gaming fee of collateral {} can't be higher than {}", collateral, max)]
+    GamingFeeTooHigh { collateral: String, max: Uint128 },
+
+    #[error("Minimum bet of collateral {} must be higher than 0", collateral)]
+    InvalidMinimumBet { collateral: String },
+
+    #[error("Betting is paused")]
+    BettingPaused {},
+
+    #[error("Rounds are paused")]
+    RoundsPaused {},
+
+    #[error("Claims are paused")]
+    ClaimsPaused {},
+
+    #[error("Rounds must be paused before the emergency exit")]
+    RoundsNotPaused {},
+
+    #[error("The game is in emergency exit mode")]
+    EmergencyExitActive {},
+
+    #[error("Round {} was not voided", round_id)]
+    RoundNotVoided { round_id: Uint128 },
+
+    #[error("Round retention is not set, rounds are not compacted")]
+    CompactionDisabled {},
+
+    #[error("Vault of collateral {} holds another token", collateral)]
+    VaultDenomMismatch { collateral: String },
+
+    #[error("Not enough liquidity in the vault, max payout: {}", max_payout)]
+    VaultCapacityExceeded { max_payout: Uint128 },
+
+    #[error(
+        "Sponsored rounds must start at round {} or later and end after they start",
+        first_round
+    )]
+    InvalidSponsorRounds { first_round: Uint128 },
+
+    #[error(
+        "Sponsorship funds must be the amount per round times the rounds: {}",
+        expected
+    )]
+    InvalidSponsorFunds { expected: Uint128 },
+
+    #[error(
+        "Amount per round must be at least the minimum bet of the collateral: {}",
+        minimum
+    )]
+    SponsorAmountTooLow { minimum: Uint128 },
+
+    #[error("A sponsorship can boost at most {} rounds", max)]
+    TooManySponsoredRounds { max: u128 },
+
+    #[error("There can't be more than {} active sponsorships", max)]
+    TooManySponsorships { max: usize },
+
+    #[error("Hook is already registered")]
+    HookAlreadyRegistered {},
+
+    #[error("Hook is not registered")]
+    HookNotRegistered {},
+
+    #[error("Can't register more than {} hooks", max)]
+    TooManyHooks { max: usize },
 }
@@ -1,3 +1,9 @@
+pub mod compaction;
 pub mod contract;
+pub mod emergency;
 pub mod error;
+pub mod migrations;
+pub mod positions;
+pub mod sponsorships;
 pub mod state;
+pub mod unclaimed;
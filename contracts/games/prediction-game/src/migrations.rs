@@ -0,0 +1,563 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{
+    from_json, Addr, Binary, Decimal, DepsMut, Env, Int128, Order, Response, StdError, StdResult,
+    Storage, Timestamp, Uint128,
+};
+use cw20::Denom;
+use cw_storage_plus::Bound;
+use general::events::{attr, VeloAction};
+use general::migration::{self, next_cursor, MigrationStep, StepRun};
+use general::rbac;
+use general::timelock;
+use prediction::prediction_game::msg::MigrateMsg;
+use prediction::prediction_game::{
+    BetInfo, ClaimInfo, Collateral, CollateralPool, Config, Direction, FinishedRound, LiveRound,
+    NextRound, PauseState, WalletInfo,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::contract::velo_event;
+use crate::error::ContractError;
+use crate::state::{
+    bet_info_storage, ADMINS, CONFIG, IS_HALTED, LEGACY_TOTALS_SPENT, LIVE_ROUND, NEXT_ROUND,
+    PAUSE, ROUNDS, STORED_BETS, STORED_CLAIMS, STORED_ROUNDS, TOTALS_SPENT, UNSETTLED_ROUNDS,
+};
+use crate::unclaimed::{add_unclaimed, unclaimed_amounts};
+
+/// State rewrites run by `migrate`, by the version that introduced them
+pub const MIGRATIONS: &[MigrationStep<MigrateMsg>] = &[
+    MigrationStep {
+        version: "0.2.0",
+        name: "config_collaterals",
+        run: StepRun::Once(config_collaterals),
+    },
+    MigrationStep {
+        version: "0.2.0",
+        name: "running_rounds_to_pools",
+        run: StepRun::Once(running_rounds_to_pools),
+    },
+    MigrationStep {
+        version: "0.2.0",
+        name: "finished_rounds_to_pools",
+        run: StepRun::Paged(finished_rounds_to_pools),
+    },
+    MigrationStep {
+        version: "0.2.0",
+        name: "bets_to_collaterals",
+        run: StepRun::Paged(bets_to_collaterals),
+    },
+    MigrationStep {
+        version: "0.2.0",
+        name: "claims_to_collaterals",
+        run: StepRun::Paged(claims_to_collaterals),
+    },
+    MigrationStep {
+        version: "0.2.0",
+        name: "totals_spent_by_collateral",
+        run: StepRun::Paged(totals_spent_by_collateral),
+    },
+    MigrationStep {
+        version: "0.2.0",
+        name: "index_rounds_by_denom",
+        run: StepRun::Paged(index_rounds_by_denom),
+    },
+    MigrationStep {
+        version: "0.2.0",
+        name: "admins_to_roles",
+        run: StepRun::Once(admins_to_roles),
+    },
+    MigrationStep {
+        version: "0.2.0",
+        name: "halt_to_pause",
+        run: StepRun::Once(halt_to_pause),
+    },
+    MigrationStep {
+        version: "0.2.0",
+        name: "timelock_delay",
+        run: StepRun::Once(timelock_delay),
+    },
+    MigrationStep {
+        version: "0.2.0",
+        name: "index_unclaimed_bets",
+        run: StepRun::Paged(index_unclaimed_bets),
+    },
+];
+
+/// Config stored before collaterals, with a single native token to bet with
+#[cw_serde]
+struct LegacyConfig {
+    next_round_seconds: Uint128,
+    minimum_bet: Uint128,
+    gaming_fee: Uint128,
+    token_denom: String,
+    users_contract: Addr,
+    exp_per_denom_bet: u64,
+    exp_per_denom_won: u64,
+    dev_wallet_list: Vec<WalletInfo>,
+}
+
+/// Record stored with the current layout, or with the one before collaterals
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Stored<T, L> {
+    Current(T),
+    Legacy(L),
+}
+
+#[cw_serde]
+struct LegacyNextRound {
+    id: Uint128,
+    bid_time: Timestamp,
+    open_time: Timestamp,
+    close_time: Timestamp,
+    bull_amount: Uint128,
+    bear_amount: Uint128,
+    denom: String,
+}
+
+#[cw_serde]
+struct LegacyLiveRound {
+    id: Uint128,
+    bid_time: Timestamp,
+    open_time: Timestamp,
+    close_time: Timestamp,
+    open_price: Int128,
+    bull_amount: Uint128,
+    bear_amount: Uint128,
+    denom: String,
+}
+
+#[cw_serde]
+pub struct LegacyFinishedRound {
+    id: Uint128,
+    bid_time: Timestamp,
+    open_time: Timestamp,
+    close_time: Timestamp,
+    open_price: Int128,
+    close_price: Int128,
+    winner: Option<Direction>,
+    bull_amount: Uint128,
+    bear_amount: Uint128,
+    denom: String,
+}
+
+#[cw_serde]
+pub struct LegacyBetInfo {
+    player: Addr,
+    round_id: Uint128,
+    amount: Uint128,
+    direction: Direction,
+}
+
+#[cw_serde]
+pub struct LegacyClaimInfo {
+    player: Addr,
+    round_id: Uint128,
+    claimed_amount: Uint128,
+}
+
+/// Rewrites up to `limit` records of the migration in progress
+pub fn execute_continue_migration(
+    deps: DepsMut,
+    env: Env,
+    limit: Option<u32>,
+) -> Result<Response, ContractError> {
+    let remaining = migration::continue_migration(deps, &env, MIGRATIONS, limit)?;
+
+    Ok(Response::new().add_event(
+        velo_event(VeloAction::ContinueMigration)
+            .add_attribute(attr::REMAINING_STEPS, remaining.join(",")),
+    ))
+}
+
+// The single token becomes the only collateral, with the XP per USD of the migrate message since
+// XP per token can't be converted
+fn config_collaterals(deps: DepsMut, _env: &Env, msg: &MigrateMsg) -> StdResult<()> {
+    let Some(raw) = deps.storage.get(CONFIG.as_slice()) else {
+        return Ok(());
+    };
+    if from_json::<Config>(&raw).is_ok() {
+        return Ok(());
+    }
+    let legacy: LegacyConfig = from_json(&raw)?;
+
+    let experience = msg.experience.clone().ok_or_else(|| {
+        StdError::generic_err("experience is required to migrate a config without collaterals")
+    })?;
+    experience.validate()?;
+
+    CONFIG.save(
+        deps.storage,
+        &Config {
+            next_round_seconds: legacy.next_round_seconds,
+            collaterals: vec![Collateral {
+                denom: Denom::Native(legacy.token_denom),
+                minimum_bet: legacy.minimum_bet,
+                gaming_fee: legacy.gaming_fee,
+                experience,
+                fixed_odds: None,
+                min_pool_to_open: Uint128::zero(),
+            }],
+            users_contract: legacy.users_contract,
+            elo_k_factor: 0,
+            dev_wallet_list: legacy.dev_wallet_list,
+            swap_router: None,
+            position_nfts: false,
+            early_bet_bonus: Decimal::zero(),
+            min_players_to_open: 0,
+            bid_extension_seconds: Uint128::zero(),
+            max_bid_extensions: 0,
+            round_retention: 0,
+            hedging: false,
+        },
+    )
+}
+
+// Everything bet before collaterals was bet with the single token, the only collateral of the
+// converted config
+fn legacy_collateral(storage: &dyn Storage) -> StdResult<Collateral> {
+    CONFIG
+        .load(storage)?
+        .collaterals
+        .into_iter()
+        .next()
+        .ok_or_else(|| StdError::generic_err("No collateral to migrate the bets to"))
+}
+
+fn legacy_pools(
+    collateral: &Collateral,
+    bull_amount: Uint128,
+    bear_amount: Uint128,
+) -> Vec<CollateralPool> {
+    vec![CollateralPool {
+        bull_amount,
+        bear_amount,
+        ..collateral.pool()
+    }]
+}
+
+// The amounts of the bidding and live rounds become the pool of the collateral. The players of
+// the bidding round are left at 0, they only gate opening rounds with min_players_to_open which
+// the converted config disables
+fn running_rounds_to_pools(deps: DepsMut, _env: &Env, _msg: &MigrateMsg) -> StdResult<()> {
+    let next = deps.storage.get(NEXT_ROUND.as_slice());
+    let live = deps.storage.get(LIVE_ROUND.as_slice());
+    if next.is_none() && live.is_none() {
+        return Ok(());
+    }
+    let collateral = legacy_collateral(deps.storage)?;
+
+    if let Some(raw) = next.filter(|raw| from_json::<NextRound>(raw).is_err()) {
+        let legacy: LegacyNextRound = from_json(&raw)?;
+        NEXT_ROUND.save(
+            deps.storage,
+            &NextRound {
+                id: legacy.id,
+                bid_time: legacy.bid_time,
+                open_time: legacy.open_time,
+                close_time: legacy.close_time,
+                pools: legacy_pools(&collateral, legacy.bull_amount, legacy.bear_amount),
+                denom: legacy.denom,
+                players: 0,
+                extensions: 0,
+            },
+        )?;
+    }
+
+    if let Some(raw) = live.filter(|raw| from_json::<LiveRound>(raw).is_err()) {
+        let legacy: LegacyLiveRound = from_json(&raw)?;
+        LIVE_ROUND.save(
+            deps.storage,
+            &LiveRound {
+                id: legacy.id,
+                bid_time: legacy.bid_time,
+                open_time: legacy.open_time,
+                close_time: legacy.close_time,
+                open_price: legacy.open_price,
+                pools: legacy_pools(&collateral, legacy.bull_amount, legacy.bear_amount),
+                denom: legacy.denom,
+            },
+        )?;
+    }
+
+    Ok(())
+}
+
+// Finished rounds get the pool of the collateral. They are written without the denom index,
+// index_rounds_by_denom adds it
+fn finished_rounds_to_pools(
+    deps: DepsMut,
+    _env: &Env,
+    _msg: &MigrateMsg,
+    cursor: Option<Binary>,
+    limit: u32,
+) -> StdResult<Option<Binary>> {
+    let start = cursor
+        .map(|cursor| from_json::<u128>(&cursor))
+        .transpose()?;
+    let rounds = STORED_ROUNDS
+        .range(
+            deps.storage,
+            start.map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .take(limit as usize)
+        .collect::<StdResult<Vec<_>>>()?;
+    if rounds.is_empty() {
+        return Ok(None);
+    }
+    let collateral = legacy_collateral(deps.storage)?;
+
+    for (round_id, round) in &rounds {
+        let Stored::Legacy(legacy) = round else {
+            continue;
+        };
+        let round = FinishedRound {
+            id: legacy.id,
+            bid_time: legacy.bid_time,
+            open_time: legacy.open_time,
+            close_time: legacy.close_time,
+            open_price: legacy.open_price,
+            close_price: legacy.close_price,
+            winner: legacy.winner.clone(),
+            pools: legacy_pools(&collateral, legacy.bull_amount, legacy.bear_amount),
+            denom: legacy.denom.clone(),
+            compacted: false,
+        };
+        STORED_ROUNDS.save(deps.storage, *round_id, &Stored::Current(round))?;
+    }
+
+    next_cursor(&rounds, limit)
+}
+
+// Bets get the collateral, the player and round indexes don't change
+fn bets_to_collaterals(
+    deps: DepsMut,
+    _env: &Env,
+    _msg: &MigrateMsg,
+    cursor: Option<Binary>,
+    limit: u32,
+) -> StdResult<Option<Binary>> {
+    let start = cursor
+        .map(|cursor| from_json::<(u128, Addr)>(&cursor))
+        .transpose()?;
+    let bets = STORED_BETS
+        .range(
+            deps.storage,
+            start.map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .take(limit as usize)
+        .collect::<StdResult<Vec<_>>>()?;
+    if bets.is_empty() {
+        return Ok(None);
+    }
+    let collateral = legacy_collateral(deps.storage)?.key();
+
+    for (key, bet) in &bets {
+        let Stored::Legacy(legacy) = bet else {
+            continue;
+        };
+        let bet = BetInfo {
+            player: legacy.player.clone(),
+            round_id: legacy.round_id,
+            collateral: collateral.clone(),
+            amount: legacy.amount,
+            direction: legacy.direction.clone(),
+            token_id: None,
+            fixed_payout: None,
+            weighted_amount: None,
+            hedge: None,
+        };
+        STORED_BETS.save(deps.storage, key.clone(), &Stored::Current(bet))?;
+    }
+
+    next_cursor(&bets, limit)
+}
+
+// Claim records get the collateral, the player and round indexes don't change
+fn claims_to_collaterals(
+    deps: DepsMut,
+    _env: &Env,
+    _msg: &MigrateMsg,
+    cursor: Option<Binary>,
+    limit: u32,
+) -> StdResult<Option<Binary>> {
+    let start = cursor
+        .map(|cursor| from_json::<(u128, Addr)>(&cursor))
+        .transpose()?;
+    let claims = STORED_CLAIMS
+        .range(
+            deps.storage,
+            start.map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .take(limit as usize)
+        .collect::<StdResult<Vec<_>>>()?;
+    if claims.is_empty() {
+        return Ok(None);
+    }
+    let collateral = legacy_collateral(deps.storage)?.key();
+
+    for (key, claim) in &claims {
+        let Stored::Legacy(legacy) = claim else {
+            continue;
+        };
+        let claim = ClaimInfo {
+            player: legacy.player.clone(),
+            round_id: legacy.round_id,
+            collateral: collateral.clone(),
+            claimed_amount: legacy.claimed_amount,
+        };
+        STORED_CLAIMS.save(deps.storage, key.clone(), &Stored::Current(claim))?;
+    }
+
+    next_cursor(&claims, limit)
+}
+
+// Totals spent keyed by player move to (player, collateral). The legacy keys are the raw address
+// while the new ones start with its length, so the legacy ones are ranged from the first byte an
+// address can start with
+fn totals_spent_by_collateral(
+    deps: DepsMut,
+    _env: &Env,
+    _msg: &MigrateMsg,
+    cursor: Option<Binary>,
+    limit: u32,
+) -> StdResult<Option<Binary>> {
+    let start = match cursor {
+        Some(cursor) => Bound::exclusive(from_json::<Addr>(&cursor)?),
+        None => Bound::inclusive(Addr::unchecked("\u{1}")),
+    };
+    let totals = LEGACY_TOTALS_SPENT
+        .range(deps.storage, Some(start), None, Order::Ascending)
+        .take(limit as usize)
+        .collect::<StdResult<Vec<_>>>()?;
+    if totals.is_empty() {
+        return Ok(None);
+    }
+    let collateral = legacy_collateral(deps.storage)?.key();
+
+    for (player, total) in &totals {
+        LEGACY_TOTALS_SPENT.remove(deps.storage, player.clone());
+        TOTALS_SPENT.save(deps.storage, (player.clone(), collateral.clone()), total)?;
+    }
+
+    next_cursor(&totals, limit)
+}
+
+// Re-save finished rounds so the ones stored before the denom index existed get indexed
+fn index_rounds_by_denom(
+    deps: DepsMut,
+    _env: &Env,
+    _msg: &MigrateMsg,
+    cursor: Option<Binary>,
+    limit: u32,
+) -> StdResult<Option<Binary>> {
+    let start = cursor
+        .map(|cursor| from_json::<u128>(&cursor))
+        .transpose()?;
+    let rounds = ROUNDS
+        .range(
+            deps.storage,
+            start.map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .take(limit as usize)
+        .collect::<StdResult<Vec<_>>>()?;
+    for (round_id, round) in &rounds {
+        ROUNDS.save(deps.storage, *round_id, round)?;
+    }
+
+    next_cursor(&rounds, limit)
+}
+
+// The owner of the message, or the first admin, becomes the owner and the rest get every other
+// role
+fn admins_to_roles(deps: DepsMut, _env: &Env, msg: &MigrateMsg) -> StdResult<()> {
+    let Some(admins) = ADMINS.may_load(deps.storage)? else {
+        return Ok(());
+    };
+
+    match &msg.owner {
+        Some(owner) => rbac::initialize(deps.storage, deps.api, owner.as_str(), &admins)?,
+        None => {
+            if let Some((owner, admins)) = admins.split_first() {
+                rbac::initialize(deps.storage, deps.api, owner.as_str(), admins)?;
+            }
+        }
+    }
+    ADMINS.remove(deps.storage);
+
+    Ok(())
+}
+
+// The single halt switch becomes a pause of betting and rounds, claims were never halted
+fn halt_to_pause(deps: DepsMut, env: &Env, _msg: &MigrateMsg) -> StdResult<()> {
+    let Some(is_halted) = IS_HALTED.may_load(deps.storage)? else {
+        return Ok(());
+    };
+
+    PAUSE.save(
+        deps.storage,
+        &PauseState {
+            betting: is_halted,
+            rounds: is_halted,
+            rounds_paused_at: is_halted.then_some(env.block.time),
+            ..PauseState::default()
+        },
+    )?;
+    IS_HALTED.remove(deps.storage);
+
+    Ok(())
+}
+
+// Sensitive config changes are timelocked from now on, the delay can be set while migrating
+fn timelock_delay(deps: DepsMut, _env: &Env, msg: &MigrateMsg) -> StdResult<()> {
+    if let Some(seconds) = msg.timelock_delay {
+        timelock::set_delay(deps.storage, seconds)?;
+    }
+
+    Ok(())
+}
+
+// Bets placed before the unclaimed index existed are added to it, or tracked until their round
+// is finished
+fn index_unclaimed_bets(
+    deps: DepsMut,
+    _env: &Env,
+    _msg: &MigrateMsg,
+    cursor: Option<Binary>,
+    limit: u32,
+) -> StdResult<Option<Binary>> {
+    let start = cursor
+        .map(|cursor| from_json::<(u128, Addr)>(&cursor))
+        .transpose()?;
+    let bets = bet_info_storage()
+        .range(
+            deps.storage,
+            start.map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .take(limit as usize)
+        .collect::<StdResult<Vec<_>>>()?;
+    for (_, bet) in bets.iter().filter(|(_, bet)| bet.token_id.is_none()) {
+        match ROUNDS.may_load(deps.storage, bet.round_id.u128())? {
+            Some(round) => add_unclaimed(deps.storage, bet, &unclaimed_amounts(&round, bet))?,
+            None => {
+                let mut running = UNSETTLED_ROUNDS
+                    .may_load(deps.storage, bet.player.clone())?
+                    .unwrap_or_default();
+                running.push(bet.round_id.u128());
+                UNSETTLED_ROUNDS.save(deps.storage, bet.player.clone(), &running)?;
+            }
+        }
+    }
+
+    next_cursor(&bets, limit)
+}
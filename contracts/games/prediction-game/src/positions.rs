@@ -0,0 +1,498 @@
+use cosmwasm_std::{
+    Addr, Binary, Deps, DepsMut, Env, MessageInfo, Order, Response, StdError, StdResult, Storage,
+    Uint128,
+};
+use cw_storage_plus::Bound;
+use cw_utils::Expiration;
+use general::events::{attr, VeloAction};
+use neutron_sdk::bindings::query::NeutronQuery;
+use prediction::positions::{
+    AllNftInfoResponse, Approval, ApprovalResponse, ApprovalsResponse, ContractInfoResponse,
+    Cw721ReceiveMsg, NftInfoResponse, NumTokensResponse, OperatorsResponse, OwnerOfResponse,
+    Position, PositionMetadata, PositionStatus, TokensResponse, POSITIONS_NAME, POSITIONS_SYMBOL,
+};
+use prediction::prediction_game::find_pool;
+
+use crate::contract::{settle_round, velo_event, MAX_PAGE_LIMIT};
+use crate::error::ContractError;
+use crate::state::{
+    bet_info_key, bet_info_storage, NEXT_POSITION_ID, OPERATORS, POSITIONS, ROUNDS,
+};
+
+/// Mints the position of a new bet to the bettor and returns its token id
+pub fn mint_position(
+    storage: &mut dyn Storage,
+    bettor: &Addr,
+    round_id: Uint128,
+) -> StdResult<String> {
+    let id = NEXT_POSITION_ID.may_load(storage)?.unwrap_or_default() + 1;
+    NEXT_POSITION_ID.save(storage, &id)?;
+
+    let token_id = id.to_string();
+    POSITIONS.save(
+        storage,
+        token_id.clone(),
+        &Position {
+            owner: bettor.clone(),
+            bettor: bettor.clone(),
+            round_id,
+            approvals: vec![],
+        },
+    )?;
+
+    Ok(token_id)
+}
+
+/// Settles the bet behind a position, paying the holder, and burns the token
+pub fn execute_claim_position(
+    deps: DepsMut<NeutronQuery>,
+    env: Env,
+    info: MessageInfo,
+    token_id: String,
+) -> Result<Response, ContractError> {
+    let position = POSITIONS.load(deps.storage, token_id.clone())?;
+    check_can_send(deps.as_ref().into_empty(), &env, &info.sender, &position)?;
+
+    if !ROUNDS.has(deps.storage, position.round_id.u128()) {
+        return Err(ContractError::PositionPending { token_id });
+    }
+
+    POSITIONS.remove(deps.storage, token_id.clone())?;
+
+    let resp = settle_round(
+        deps,
+        env,
+        position.bettor.clone(),
+        position.owner.clone(),
+        position.round_id,
+    )?;
+
+    Ok(resp.add_event(
+        velo_event(VeloAction::ClaimPosition)
+            .add_attribute(attr::TOKEN_ID, token_id)
+            .add_attribute(attr::PLAYER, position.owner.to_string())
+            .add_attribute(attr::ROUND_ID, position.round_id.to_string()),
+    ))
+}
+
+pub fn execute_transfer_nft(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    recipient: String,
+    token_id: String,
+) -> Result<Response, ContractError> {
+    let recipient = deps.api.addr_validate(&recipient)?;
+    transfer_position(deps, &env, &info, &recipient, &token_id)?;
+
+    Ok(Response::new().add_event(
+        velo_event(VeloAction::TransferPosition)
+            .add_attribute(attr::TOKEN_ID, token_id)
+            .add_attribute(attr::SENDER, info.sender.to_string())
+            .add_attribute(attr::RECIPIENT, recipient.to_string()),
+    ))
+}
+
+pub fn execute_send_nft(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    contract: String,
+    token_id: String,
+    msg: Binary,
+) -> Result<Response, ContractError> {
+    let contract = deps.api.addr_validate(&contract)?;
+    transfer_position(deps, &env, &info, &contract, &token_id)?;
+
+    let receive_msg = Cw721ReceiveMsg {
+        sender: info.sender.to_string(),
+        token_id: token_id.clone(),
+        msg,
+    }
+    .into_cosmos_msg(contract.to_string())?;
+
+    Ok(Response::new().add_message(receive_msg).add_event(
+        velo_event(VeloAction::SendPosition)
+            .add_attribute(attr::TOKEN_ID, token_id)
+            .add_attribute(attr::SENDER, info.sender.to_string())
+            .add_attribute(attr::RECIPIENT, contract.to_string()),
+    ))
+}
+
+pub fn execute_approve(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    spender: String,
+    token_id: String,
+    expires: Option<Expiration>,
+) -> Result<Response, ContractError> {
+    let spender = deps.api.addr_validate(&spender)?;
+    let expires = expires.unwrap_or_default();
+    if expires.is_expired(&env.block) {
+        return Err(ContractError::Std(StdError::generic_err(
+            "Approval already expired",
+        )));
+    }
+
+    let mut position = POSITIONS.load(deps.storage, token_id.clone())?;
+    check_can_approve(deps.as_ref(), &env, &info.sender, &position)?;
+
+    position
+        .approvals
+        .retain(|approval| approval.spender != spender);
+    position.approvals.push(Approval {
+        spender: spender.clone(),
+        expires,
+    });
+    POSITIONS.save(deps.storage, token_id.clone(), &position)?;
+
+    Ok(Response::new().add_event(
+        velo_event(VeloAction::ApprovePosition)
+            .add_attribute(attr::TOKEN_ID, token_id)
+            .add_attribute(attr::SENDER, info.sender.to_string())
+            .add_attribute(attr::SPENDER, spender.to_string()),
+    ))
+}
+
+pub fn execute_revoke(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    spender: String,
+    token_id: String,
+) -> Result<Response, ContractError> {
+    let spender = deps.api.addr_validate(&spender)?;
+    let mut position = POSITIONS.load(deps.storage, token_id.clone())?;
+    check_can_approve(deps.as_ref(), &env, &info.sender, &position)?;
+
+    position
+        .approvals
+        .retain(|approval| approval.spender != spender);
+    POSITIONS.save(deps.storage, token_id.clone(), &position)?;
+
+    Ok(Response::new().add_event(
+        velo_event(VeloAction::RevokePosition)
+            .add_attribute(attr::TOKEN_ID, token_id)
+            .add_attribute(attr::SENDER, info.sender.to_string())
+            .add_attribute(attr::SPENDER, spender.to_string()),
+    ))
+}
+
+pub fn execute_approve_all(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    operator: String,
+    expires: Option<Expiration>,
+) -> Result<Response, ContractError> {
+    let operator = deps.api.addr_validate(&operator)?;
+    let expires = expires.unwrap_or_default();
+    if expires.is_expired(&env.block) {
+        return Err(ContractError::Std(StdError::generic_err(
+            "Approval already expired",
+        )));
+    }
+
+    OPERATORS.save(
+        deps.storage,
+        (info.sender.clone(), operator.clone()),
+        &expires,
+    )?;
+
+    Ok(Response::new().add_event(
+        velo_event(VeloAction::ApproveAllPositions)
+            .add_attribute(attr::SENDER, info.sender.to_string())
+            .add_attribute(attr::OPERATOR, operator.to_string()),
+    ))
+}
+
+pub fn execute_revoke_all(
+    deps: DepsMut,
+    info: MessageInfo,
+    operator: String,
+) -> Result<Response, ContractError> {
+    let operator = deps.api.addr_validate(&operator)?;
+    OPERATORS.remove(deps.storage, (info.sender.clone(), operator.clone()));
+
+    Ok(Response::new().add_event(
+        velo_event(VeloAction::RevokeAllPositions)
+            .add_attribute(attr::SENDER, info.sender.to_string())
+            .add_attribute(attr::OPERATOR, operator.to_string()),
+    ))
+}
+
+/// Moves a position to `recipient`, approvals don't survive a transfer
+fn transfer_position(
+    deps: DepsMut,
+    env: &Env,
+    info: &MessageInfo,
+    recipient: &Addr,
+    token_id: &str,
+) -> Result<(), ContractError> {
+    let mut position = POSITIONS.load(deps.storage, token_id.to_string())?;
+    check_can_send(deps.as_ref(), env, &info.sender, &position)?;
+
+    position.owner = recipient.clone();
+    position.approvals = vec![];
+    POSITIONS.save(deps.storage, token_id.to_string(), &position)?;
+
+    Ok(())
+}
+
+/// The owner and its operators can approve spenders of a position
+fn check_can_approve(
+    deps: Deps,
+    env: &Env,
+    sender: &Addr,
+    position: &Position,
+) -> Result<(), ContractError> {
+    if position.owner == sender || is_operator(deps, env, &position.owner, sender)? {
+        return Ok(());
+    }
+
+    Err(ContractError::Unauthorized {})
+}
+
+/// The owner, its operators and approved spenders can move or claim a position
+fn check_can_send(
+    deps: Deps,
+    env: &Env,
+    sender: &Addr,
+    position: &Position,
+) -> Result<(), ContractError> {
+    if position
+        .approvals
+        .iter()
+        .any(|approval| approval.spender == sender && !approval.expires.is_expired(&env.block))
+    {
+        return Ok(());
+    }
+
+    check_can_approve(deps, env, sender, position)
+}
+
+fn is_operator(deps: Deps, env: &Env, owner: &Addr, operator: &Addr) -> StdResult<bool> {
+    Ok(OPERATORS
+        .may_load(deps.storage, (owner.clone(), operator.clone()))?
+        .is_some_and(|expires| !expires.is_expired(&env.block)))
+}
+
+fn active_approvals(env: &Env, position: &Position, include_expired: bool) -> Vec<Approval> {
+    position
+        .approvals
+        .iter()
+        .filter(|approval| include_expired || !approval.expires.is_expired(&env.block))
+        .cloned()
+        .collect()
+}
+
+pub fn query_owner_of(
+    deps: Deps,
+    env: Env,
+    token_id: String,
+    include_expired: Option<bool>,
+) -> StdResult<OwnerOfResponse> {
+    let position = POSITIONS.load(deps.storage, token_id)?;
+
+    Ok(OwnerOfResponse {
+        owner: position.owner.to_string(),
+        approvals: active_approvals(&env, &position, include_expired.unwrap_or(false)),
+    })
+}
+
+pub fn query_approval(
+    deps: Deps,
+    env: Env,
+    token_id: String,
+    spender: String,
+    include_expired: Option<bool>,
+) -> StdResult<ApprovalResponse> {
+    let position = POSITIONS.load(deps.storage, token_id)?;
+
+    // The owner is always allowed to move its own position
+    if position.owner.as_str() == spender {
+        return Ok(ApprovalResponse {
+            approval: Approval {
+                spender: position.owner,
+                expires: Expiration::Never {},
+            },
+        });
+    }
+
+    active_approvals(&env, &position, include_expired.unwrap_or(false))
+        .into_iter()
+        .find(|approval| approval.spender.as_str() == spender)
+        .map(|approval| ApprovalResponse { approval })
+        .ok_or_else(|| StdError::not_found("Approval not found"))
+}
+
+pub fn query_approvals(
+    deps: Deps,
+    env: Env,
+    token_id: String,
+    include_expired: Option<bool>,
+) -> StdResult<ApprovalsResponse> {
+    let position = POSITIONS.load(deps.storage, token_id)?;
+
+    Ok(ApprovalsResponse {
+        approvals: active_approvals(&env, &position, include_expired.unwrap_or(false)),
+    })
+}
+
+pub fn query_all_operators(
+    deps: Deps,
+    env: Env,
+    owner: String,
+    include_expired: Option<bool>,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<OperatorsResponse> {
+    let limit = limit.unwrap_or(MAX_PAGE_LIMIT).min(MAX_PAGE_LIMIT) as usize;
+    let owner = deps.api.addr_validate(&owner)?;
+    let start_after = start_after
+        .map(|operator| deps.api.addr_validate(&operator))
+        .transpose()?;
+    let include_expired = include_expired.unwrap_or(false);
+
+    let operators = OPERATORS
+        .prefix(owner)
+        .range(
+            deps.storage,
+            start_after.map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .filter(|item| {
+            item.as_ref().map_or(true, |(_, expires)| {
+                include_expired || !expires.is_expired(&env.block)
+            })
+        })
+        .take(limit)
+        .map(|item| item.map(|(spender, expires)| Approval { spender, expires }))
+        .collect::<StdResult<Vec<Approval>>>()?;
+
+    Ok(OperatorsResponse { operators })
+}
+
+pub fn query_num_tokens(deps: Deps) -> StdResult<NumTokensResponse> {
+    let count = POSITIONS
+        .keys(deps.storage, None, None, Order::Ascending)
+        .count() as u64;
+
+    Ok(NumTokensResponse { count })
+}
+
+pub fn query_contract_info() -> ContractInfoResponse {
+    ContractInfoResponse {
+        name: POSITIONS_NAME.to_string(),
+        symbol: POSITIONS_SYMBOL.to_string(),
+    }
+}
+
+pub fn query_nft_info(deps: Deps, token_id: String) -> StdResult<NftInfoResponse> {
+    let position = POSITIONS.load(deps.storage, token_id)?;
+
+    Ok(NftInfoResponse {
+        token_uri: None,
+        extension: position_metadata(deps, &position)?,
+    })
+}
+
+pub fn query_all_nft_info(
+    deps: Deps,
+    env: Env,
+    token_id: String,
+    include_expired: Option<bool>,
+) -> StdResult<AllNftInfoResponse> {
+    let position = POSITIONS.load(deps.storage, token_id)?;
+
+    Ok(AllNftInfoResponse {
+        access: OwnerOfResponse {
+            owner: position.owner.to_string(),
+            approvals: active_approvals(&env, &position, include_expired.unwrap_or(false)),
+        },
+        info: NftInfoResponse {
+            token_uri: None,
+            extension: position_metadata(deps, &position)?,
+        },
+    })
+}
+
+pub fn query_tokens(
+    deps: Deps,
+    owner: String,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<TokensResponse> {
+    let limit = limit.unwrap_or(MAX_PAGE_LIMIT).min(MAX_PAGE_LIMIT) as usize;
+    let owner = deps.api.addr_validate(&owner)?;
+
+    let tokens = POSITIONS
+        .idx
+        .owner
+        .prefix(owner)
+        .keys(
+            deps.storage,
+            start_after.map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .take(limit)
+        .collect::<StdResult<Vec<String>>>()?;
+
+    Ok(TokensResponse { tokens })
+}
+
+pub fn query_all_tokens(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<TokensResponse> {
+    let limit = limit.unwrap_or(MAX_PAGE_LIMIT).min(MAX_PAGE_LIMIT) as usize;
+
+    let tokens = POSITIONS
+        .keys(
+            deps.storage,
+            start_after.map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .take(limit)
+        .collect::<StdResult<Vec<String>>>()?;
+
+    Ok(TokensResponse { tokens })
+}
+
+/// Metadata of the bet behind a position, the status follows the round it was placed in
+fn position_metadata(deps: Deps, position: &Position) -> StdResult<PositionMetadata> {
+    let bet = bet_info_storage().load(
+        deps.storage,
+        bet_info_key(position.round_id.u128(), &position.bettor),
+    )?;
+
+    let status = match ROUNDS.may_load(deps.storage, position.round_id.u128())? {
+        None => PositionStatus::Pending,
+        Some(round) => {
+            let pool = find_pool(&round.pools, &bet.collateral);
+            match round.winner {
+                _ if pool.is_refund() => PositionStatus::Refunded,
+                None => PositionStatus::Refunded,
+                // One of the sides of a hedged position always wins
+                Some(winner) if winner == bet.direction || bet.hedge.is_some() => {
+                    PositionStatus::Won
+                }
+                Some(_) => PositionStatus::Lost,
+            }
+        }
+    };
+
+    Ok(PositionMetadata {
+        round_id: position.round_id,
+        direction: bet.direction,
+        collateral: bet.collateral,
+        stake: bet.amount,
+        hedge_stake: bet.hedge.map(|hedge| hedge.amount),
+        status,
+    })
+}
@@ -0,0 +1,228 @@
+use cosmwasm_std::{Addr, Deps, DepsMut, Empty, Order, Response, StdResult, Storage, Uint128};
+use general::asset::transfer_msg;
+use general::events::{attr, VeloAction};
+use prediction::prediction_game::{Collateral, Config, FinishedRound, Sponsorship};
+
+use crate::contract::{load_collateral, velo_event, MAX_PAGE_LIMIT};
+use crate::error::ContractError;
+use crate::state::{
+    NEXT_ROUND, NEXT_ROUND_ID, NEXT_SPONSORSHIP_ID, PAUSE, PRICE_TICKERS, ROUND_SPONSORSHIPS,
+    SPONSORSHIPS,
+};
+
+// Every finished round settles the sponsorships boosting it, so both are capped
+const MAX_ACTIVE_SPONSORSHIPS: usize = 20;
+const MAX_SPONSORED_ROUNDS: u128 = 100;
+
+/// Escrows `amount` as the prize of a range of rounds, `amount_per_round` for each round
+#[allow(clippy::too_many_arguments)]
+pub fn execute_sponsor(
+    deps: DepsMut,
+    sponsor: Addr,
+    collateral: Collateral,
+    amount: Uint128,
+    start_round: Uint128,
+    end_round: Uint128,
+    denom: Option<String>,
+    amount_per_round: Uint128,
+) -> Result<Response, ContractError> {
+    // No round will be played again
+    if PAUSE.load(deps.storage)?.emergency_exit {
+        return Err(ContractError::EmergencyExitActive {});
+    }
+
+    // Rounds that stopped taking bets can't be boosted anymore
+    let first_round = match NEXT_ROUND.may_load(deps.storage)? {
+        Some(next_round) => next_round.id,
+        None => Uint128::from(NEXT_ROUND_ID.load(deps.storage)?),
+    };
+    if start_round < first_round || end_round < start_round {
+        return Err(ContractError::InvalidSponsorRounds { first_round });
+    }
+
+    if let Some(denom) = &denom {
+        if !PRICE_TICKERS.has(deps.storage, denom.clone()) {
+            return Err(ContractError::DenomNotRegistered {
+                denom: denom.clone(),
+            });
+        }
+    }
+
+    let rounds = end_round - start_round + Uint128::one();
+    if rounds.u128() > MAX_SPONSORED_ROUNDS {
+        return Err(ContractError::TooManySponsoredRounds {
+            max: MAX_SPONSORED_ROUNDS,
+        });
+    }
+
+    if amount_per_round < collateral.minimum_bet {
+        return Err(ContractError::SponsorAmountTooLow {
+            minimum: collateral.minimum_bet,
+        });
+    }
+
+    let expected = amount_per_round.checked_mul(rounds)?;
+    if amount_per_round.is_zero() || amount != expected {
+        return Err(ContractError::InvalidSponsorFunds { expected });
+    }
+
+    let active = SPONSORSHIPS
+        .keys(deps.storage, None, None, Order::Ascending)
+        .take(MAX_ACTIVE_SPONSORSHIPS)
+        .count();
+    if active >= MAX_ACTIVE_SPONSORSHIPS {
+        return Err(ContractError::TooManySponsorships {
+            max: MAX_ACTIVE_SPONSORSHIPS,
+        });
+    }
+
+    let id = NEXT_SPONSORSHIP_ID
+        .may_load(deps.storage)?
+        .unwrap_or_default()
+        + 1;
+    NEXT_SPONSORSHIP_ID.save(deps.storage, &id)?;
+
+    let sponsorship = Sponsorship {
+        id,
+        sponsor,
+        collateral: collateral.key(),
+        start_round,
+        end_round,
+        denom,
+        amount_per_round,
+    };
+    SPONSORSHIPS.save(deps.storage, id, &sponsorship)?;
+    for round_id in start_round.u128()..=end_round.u128() {
+        ROUND_SPONSORSHIPS.save(deps.storage, (round_id, id), &Empty {})?;
+    }
+
+    let mut event = velo_event(VeloAction::Sponsor)
+        .add_attribute(attr::SPONSORSHIP_ID, id.to_string())
+        .add_attribute(attr::SPONSOR, sponsorship.sponsor.to_string())
+        .add_attribute(attr::COLLATERAL, sponsorship.collateral.clone())
+        .add_attribute(attr::START_ROUND, start_round.to_string())
+        .add_attribute(attr::END_ROUND, end_round.to_string())
+        .add_attribute(attr::AMOUNT, amount.to_string());
+    if let Some(denom) = &sponsorship.denom {
+        event = event.add_attribute(attr::DENOM, denom.clone());
+    }
+
+    Ok(Response::new().add_event(event))
+}
+
+/// Adds the sponsorships of a finished round to the prize of its pools. Sponsors get their
+/// share back when nobody won the pool of their collateral or the round bet on another asset
+pub fn settle_sponsorships(
+    storage: &mut dyn Storage,
+    config: &Config,
+    round: &mut FinishedRound,
+    mut resp: Response,
+) -> Result<Response, ContractError> {
+    let ids = ROUND_SPONSORSHIPS
+        .prefix(round.id.u128())
+        .keys(storage, None, None, Order::Ascending)
+        .collect::<StdResult<Vec<u64>>>()?;
+
+    for id in ids {
+        ROUND_SPONSORSHIPS.remove(storage, (round.id.u128(), id));
+        let sponsorship = SPONSORSHIPS.load(storage, id)?;
+        if sponsorship.end_round <= round.id {
+            SPONSORSHIPS.remove(storage, sponsorship.id);
+        }
+
+        let matches_denom = sponsorship
+            .denom
+            .as_ref()
+            .is_none_or(|denom| *denom == round.denom);
+        let winner = round.winner.clone();
+        let pool = round.pools.iter_mut().find(|pool| {
+            pool.collateral == sponsorship.collateral
+                && !pool.is_refund()
+                && winner
+                    .as_ref()
+                    .is_some_and(|winner| !pool.side_amount(winner).is_zero())
+        });
+
+        match pool {
+            Some(pool) if matches_denom => {
+                pool.boost += sponsorship.amount_per_round;
+                resp = resp.add_event(
+                    velo_event(VeloAction::BoostRound)
+                        .add_attribute(attr::SPONSORSHIP_ID, sponsorship.id.to_string())
+                        .add_attribute(attr::ROUND_ID, round.id.to_string())
+                        .add_attribute(attr::COLLATERAL, sponsorship.collateral.clone())
+                        .add_attribute(attr::AMOUNT, sponsorship.amount_per_round.to_string()),
+                );
+            }
+            _ => {
+                let collateral = load_collateral(config, &sponsorship.collateral)?;
+                resp = resp
+                    .add_message(transfer_msg(
+                        &collateral.denom,
+                        &sponsorship.sponsor,
+                        sponsorship.amount_per_round,
+                    )?)
+                    .add_event(
+                        velo_event(VeloAction::RefundSponsor)
+                            .add_attribute(attr::SPONSORSHIP_ID, sponsorship.id.to_string())
+                            .add_attribute(attr::ROUND_ID, round.id.to_string())
+                            .add_attribute(attr::SPONSOR, sponsorship.sponsor.to_string())
+                            .add_attribute(attr::AMOUNT, sponsorship.amount_per_round.to_string()),
+                    );
+            }
+        }
+    }
+
+    Ok(resp)
+}
+
+/// Returns what is left of every sponsorship to its sponsor, for the rounds that will never
+/// be played after the emergency exit
+pub fn refund_sponsorships(
+    storage: &mut dyn Storage,
+    config: &Config,
+    mut resp: Response,
+) -> Result<Response, ContractError> {
+    let first_round = Uint128::from(NEXT_ROUND_ID.load(storage)?);
+    let sponsorships = SPONSORSHIPS
+        .range(storage, None, None, Order::Ascending)
+        .map(|item| item.map(|(_, sponsorship)| sponsorship))
+        .collect::<StdResult<Vec<Sponsorship>>>()?;
+
+    for sponsorship in sponsorships {
+        SPONSORSHIPS.remove(storage, sponsorship.id);
+
+        let start_round = sponsorship.start_round.max(first_round);
+        if sponsorship.end_round < start_round {
+            continue;
+        }
+        for round_id in start_round.u128()..=sponsorship.end_round.u128() {
+            ROUND_SPONSORSHIPS.remove(storage, (round_id, sponsorship.id));
+        }
+        let rounds = sponsorship.end_round - start_round + Uint128::one();
+        let amount = sponsorship.amount_per_round.checked_mul(rounds)?;
+        let collateral = load_collateral(config, &sponsorship.collateral)?;
+        resp = resp
+            .add_message(transfer_msg(
+                &collateral.denom,
+                &sponsorship.sponsor,
+                amount,
+            )?)
+            .add_event(
+                velo_event(VeloAction::RefundSponsor)
+                    .add_attribute(attr::SPONSORSHIP_ID, sponsorship.id.to_string())
+                    .add_attribute(attr::SPONSOR, sponsorship.sponsor.to_string())
+                    .add_attribute(attr::AMOUNT, amount.to_string()),
+            );
+    }
+
+    Ok(resp)
+}
+
+pub fn query_active_sponsorships(deps: Deps) -> StdResult<Vec<Sponsorship>> {
+    SPONSORSHIPS
+        .range(deps.storage, None, None, Order::Ascending)
+        .take(MAX_PAGE_LIMIT as usize)
+        .map(|item| item.map(|(_, sponsorship)| sponsorship))
+        .collect()
+}
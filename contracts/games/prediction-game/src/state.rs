@@ -1,7 +1,15 @@
-use cosmwasm_std::{Addr, Uint128};
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, Coin, Empty, Uint128};
 use cw_storage_plus::{Index, IndexList, IndexedMap, Item, Map, MultiIndex};
+use cw_utils::Expiration;
+use general::ibc::{IbcOrigin, IbcPayout};
+use prediction::positions::Position;
 use prediction::prediction_game::{BetInfo, BetInfoKey, ClaimInfo, ClaimInfoKey};
-use prediction::prediction_game::{Config, FinishedRound, LiveRound, NextRound};
+use prediction::prediction_game::{
+    Config, Direction, FinishedRound, LiveRound, NextRound, PauseState, RoundSummary, Sponsorship,
+};
+
+use crate::migrations::{LegacyBetInfo, LegacyClaimInfo, LegacyFinishedRound, Stored};
 
 /// Top level storage key. Values must not conflict.
 /// Each key is only one byte long to ensure we use the smallest possible storage keys.
@@ -17,6 +25,24 @@ pub enum TopKey {
     TotalsSpent = b't',
     PriceTickers = b'p',
     RoundDenoms = b'd',
+    Hooks = b'h',
+    PendingSwap = b's',
+    IbcOrigins = b'o',
+    IbcDenoms = b'i',
+    Positions = b'T',
+    NextPositionId = b'x',
+    Operators = b'O',
+    Sponsorships = b'P',
+    NextSponsorshipId = b'y',
+    Pause = b'H',
+    RoundSummaries = b'R',
+    UnsettledRounds = b'b',
+    UnclaimedRounds = b'c',
+    UnclaimedTotals = b'u',
+    IbcPayouts = b'f',
+    FailedIbcPayouts = b'F',
+    RoundSponsorships = b'S',
+    CompactionCursor = b'k',
 }
 
 impl TopKey {
@@ -29,7 +55,9 @@ impl TopKey {
     }
 }
 
+/* Single switch replaced by PAUSE, only read when migrating */
 pub const IS_HALTED: Item<bool> = Item::new(TopKey::IsHalted.as_str());
+pub const PAUSE: Item<PauseState> = Item::new(TopKey::Pause.as_str());
 pub const CONFIG: Item<Config> = Item::new(TopKey::Config.as_str());
 pub const NEXT_ROUND_ID: Item<u128> = Item::new(TopKey::NextRoundId.as_str());
 /* The round that's open for betting */
@@ -37,18 +65,176 @@ pub const NEXT_ROUND: Item<NextRound> = Item::new(TopKey::NextRound.as_str());
 /* The live round; not accepting bets */
 pub const LIVE_ROUND: Item<LiveRound> = Item::new(TopKey::LiveRound.as_str());
 
-pub const ROUNDS: Map<u128, FinishedRound> = Map::new(TopKey::Rounds.as_str());
+/// Defines indexes for accessing finished rounds
+pub struct RoundIndexes<'a> {
+    pub denom: MultiIndex<'a, String, FinishedRound, u128>,
+}
+
+impl<'a> IndexList<FinishedRound> for RoundIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<FinishedRound>> + '_> {
+        let v: Vec<&dyn Index<FinishedRound>> = vec![&self.denom];
+        Box::new(v.into_iter())
+    }
+}
+
+pub const ROUNDS: IndexedMap<u128, FinishedRound, RoundIndexes> = IndexedMap::new(
+    TopKey::Rounds.as_str(),
+    RoundIndexes {
+        denom: MultiIndex::new(
+            |_pk, round| round.denom.clone(),
+            TopKey::Rounds.as_str(),
+            "rounds__denom",
+        ),
+    },
+);
+
+/// Defines indexes for accessing the summaries of compacted rounds
+pub struct RoundSummaryIndexes<'a> {
+    pub denom: MultiIndex<'a, String, RoundSummary, u128>,
+}
+
+impl<'a> IndexList<RoundSummary> for RoundSummaryIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<RoundSummary>> + '_> {
+        let v: Vec<&dyn Index<RoundSummary>> = vec![&self.denom];
+        Box::new(v.into_iter())
+    }
+}
 
+// Finished rounds moved out of ROUNDS once compacted
+pub const ROUND_SUMMARIES: IndexedMap<u128, RoundSummary, RoundSummaryIndexes> = IndexedMap::new(
+    TopKey::RoundSummaries.as_str(),
+    RoundSummaryIndexes {
+        denom: MultiIndex::new(
+            |_pk, summary| summary.denom.clone(),
+            TopKey::RoundSummaries.as_str(),
+            "round_summaries__denom",
+        ),
+    },
+);
+
+/* Replaced by the roles of general::rbac, only read when migrating */
 pub const ADMINS: Item<Vec<Addr>> = Item::new(TopKey::Admins.as_str());
 
-pub const TOTALS_SPENT: Map<Addr, Uint128> = Map::new(TopKey::TotalsSpent.as_str());
+// Map (player, collateral) -> total amount bet
+pub const TOTALS_SPENT: Map<(Addr, String), Uint128> = Map::new(TopKey::TotalsSpent.as_str());
+
+/* Layouts before collaterals, only read and rewritten when migrating */
+pub const STORED_ROUNDS: Map<u128, Stored<FinishedRound, LegacyFinishedRound>> =
+    Map::new(TopKey::Rounds.as_str());
+pub const STORED_BETS: Map<BetInfoKey, Stored<BetInfo, LegacyBetInfo>> = Map::new("bet_info");
+pub const STORED_CLAIMS: Map<ClaimInfoKey, Stored<ClaimInfo, LegacyClaimInfo>> =
+    Map::new("claim_info");
+// Map player -> total amount bet with the single token
+pub const LEGACY_TOTALS_SPENT: Map<Addr, Uint128> = Map::new(TopKey::TotalsSpent.as_str());
+
+/// Amounts left to claim from settled rounds, as counted by the pending reward and refundable
+/// amount queries
+#[cw_serde]
+#[derive(Default)]
+pub struct Unclaimed {
+    pub reward: Uint128,
+    pub refund: Uint128,
+}
+
+// Map player -> rounds the player bet on that aren't in UNCLAIMED_ROUNDS yet. They are moved
+// there on the next bet of the player once finished, so it never holds more than the live and
+// the bidding rounds
+pub const UNSETTLED_ROUNDS: Map<Addr, Vec<u128>> = Map::new(TopKey::UnsettledRounds.as_str());
+// Map (player, collateral, round id) -> amounts left to claim from the round, tokenized bets
+// and bets with nothing to claim are not kept
+pub const UNCLAIMED_ROUNDS: Map<(Addr, String, u128), Unclaimed> =
+    Map::new(TopKey::UnclaimedRounds.as_str());
+// Map (player, collateral) -> sum of UNCLAIMED_ROUNDS
+pub const UNCLAIMED_TOTALS: Map<(Addr, String), Unclaimed> =
+    Map::new(TopKey::UnclaimedTotals.as_str());
 
 // Map denom -> Skip Go ticker
 pub const PRICE_TICKERS: Map<String, String> = Map::new(TopKey::PriceTickers.as_str());
 
 pub const ROUND_DENOMS: Item<Vec<String>> = Item::new(TopKey::RoundDenoms.as_str());
 
-/// Convenience bid key constructor
+// Listener contracts notified of bets, settlements and claims
+pub const HOOKS: Map<Addr, Empty> = Map::new(TopKey::Hooks.as_str());
+
+/// Bet waiting for the output of the swap router, only lives between the swap and its reply
+#[cw_serde]
+pub struct PendingSwap {
+    pub player: Addr,
+    pub round_id: Uint128,
+    pub direction: Direction,
+    pub collateral: String,
+    pub offer: Coin,
+    // Collateral held by the game before the swap, the difference after it is the bet
+    pub balance_before: Uint128,
+    pub minimum_receive: Option<Uint128>,
+}
+
+pub const PENDING_SWAP: Item<PendingSwap> = Item::new(TopKey::PendingSwap.as_str());
+
+// Map player -> origin of the player's latest bet placed from another chain
+pub const IBC_ORIGINS: Map<Addr, IbcOrigin> = Map::new(TopKey::IbcOrigins.as_str());
+
+// Denoms of the native collaterals that are accepted through IBC
+pub const IBC_DENOMS: Map<String, Empty> = Map::new(TopKey::IbcDenoms.as_str());
+
+// Map (channel, sequence) -> winnings sent back to an origin chain, until the transfer is acknowledged
+pub const IBC_PAYOUTS: Map<(String, u64), IbcPayout> = Map::new(TopKey::IbcPayouts.as_str());
+
+// Map (player, collateral) -> winnings whose transfer back failed, to be sent again
+pub const FAILED_IBC_PAYOUTS: Map<(Addr, String), Uint128> =
+    Map::new(TopKey::FailedIbcPayouts.as_str());
+
+/// Defines indexes for accessing position NFTs
+pub struct PositionIndexes<'a> {
+    pub owner: MultiIndex<'a, Addr, Position, String>,
+}
+
+impl<'a> IndexList<Position> for PositionIndexes<'a> {
+    fn get_indexes(&'_ self) -> Box<dyn Iterator<Item = &'_ dyn Index<Position>> + '_> {
+        let v: Vec<&dyn Index<Position>> = vec![&self.owner];
+        Box::new(v.into_iter())
+    }
+}
+
+// Map token id -> position NFT
+pub const POSITIONS: IndexedMap<String, Position, PositionIndexes> = IndexedMap::new(
+    TopKey::Positions.as_str(),
+    PositionIndexes {
+        owner: MultiIndex::new(
+            |_pk, position| position.owner.clone(),
+            TopKey::Positions.as_str(),
+            "positions__owner",
+        ),
+    },
+);
+
+pub const NEXT_POSITION_ID: Item<u64> = Item::new(TopKey::NextPositionId.as_str());
+
+// Map (owner, operator) -> expiration of the operator approval on all positions of the owner
+pub const OPERATORS: Map<(Addr, Addr), Expiration> = Map::new(TopKey::Operators.as_str());
+
+/// Where compact_rounds stopped, the next call resumes from there
+#[cw_serde]
+pub struct CompactionCursor {
+    pub round_id: u128,
+    // Last bet of the round checked, bets are pruned or kept in player order
+    pub last_player: Option<Addr>,
+    // A bet of the round is left to claim, so the round is not replaced by its summary
+    pub claimable: bool,
+}
+
+pub const COMPACTION_CURSOR: Item<CompactionCursor> = Item::new(TopKey::CompactionCursor.as_str());
+
+// Map sponsorship id -> sponsorship, removed once its last round is settled
+pub const SPONSORSHIPS: Map<u64, Sponsorship> = Map::new(TopKey::Sponsorships.as_str());
+pub const NEXT_SPONSORSHIP_ID: Item<u64> = Item::new(TopKey::NextSponsorshipId.as_str());
+
+// Map (round id, sponsorship id) of every round a sponsorship boosts, removed once the round is settled
+pub const ROUND_SPONSORSHIPS: Map<(u128, u64), Empty> =
+    Map::new(TopKey::RoundSponsorships.as_str());
+
+/// Convenience bid key constructor. The collateral is not part of the key, a player bets with a
+/// single collateral per round
 pub fn bet_info_key(round_id: u128, player: &Addr) -> BetInfoKey {
     (round_id, player.clone())
 }
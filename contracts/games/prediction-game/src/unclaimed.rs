@@ -0,0 +1,207 @@
+use cosmwasm_std::{Addr, Order, StdResult, Storage, Uint128};
+use cw_storage_plus::Bound;
+use prediction::prediction_game::{find_pool, BetInfo, FinishedRound};
+
+use crate::contract::{fixed_odds_winnings, MAX_PAGE_LIMIT};
+use crate::state::{
+    bet_info_key, bet_info_storage, Unclaimed, ROUNDS, UNCLAIMED_ROUNDS, UNCLAIMED_TOTALS,
+    UNSETTLED_ROUNDS,
+};
+
+/// Amounts of a bet of a finished round. The reward is the winnings before the gaming fee, or
+/// the stake when the price didn't move, and refunded pools only count as refund
+pub fn unclaimed_amounts(round: &FinishedRound, bet: &BetInfo) -> Unclaimed {
+    let mut amounts = Unclaimed::default();
+    for side in bet.sides() {
+        let side_amounts = side_amounts(round, &side);
+        amounts.reward += side_amounts.reward;
+        amounts.refund += side_amounts.refund;
+    }
+    amounts
+}
+
+fn side_amounts(round: &FinishedRound, bet: &BetInfo) -> Unclaimed {
+    let pool = find_pool(&round.pools, &bet.collateral);
+    let refund = if pool.is_refund() {
+        bet.amount
+    } else {
+        Uint128::zero()
+    };
+
+    // A refunded pool only returns the stake, fixed odds bets included
+    let reward = match bet.fixed_payout {
+        _ if pool.is_refund() => Uint128::zero(),
+        Some(fixed_payout) => fixed_odds_winnings(round, bet, fixed_payout),
+        None => match &round.winner {
+            Some(winner) if *winner == bet.direction => pool
+                .prize()
+                .multiply_ratio(bet.shares(), pool.side_shares(winner)),
+            Some(_) => Uint128::zero(),
+            None => bet.amount,
+        },
+    };
+
+    Unclaimed { reward, refund }
+}
+
+// Bets of finished rounds with their amounts, and ids of the rounds still running
+type SettledBets = (Vec<(BetInfo, Unclaimed)>, Vec<u128>);
+
+/// Splits the unsettled rounds of a player into the bets of the finished ones and the ones
+/// still running. Bets only go in the bidding round, so after `track_bet` only the live and the
+/// bidding rounds are left: the queries scan at most 2 bets besides the index
+fn settled_bets(storage: &dyn Storage, player: &Addr) -> StdResult<SettledBets> {
+    let mut settled = vec![];
+    let mut running = vec![];
+    for round_id in UNSETTLED_ROUNDS
+        .may_load(storage, player.clone())?
+        .unwrap_or_default()
+    {
+        // Claimed, or pruned by the compaction
+        let Some(bet) = bet_info_storage().may_load(storage, bet_info_key(round_id, player))?
+        else {
+            continue;
+        };
+        if bet.token_id.is_some() {
+            continue;
+        }
+
+        match ROUNDS.may_load(storage, round_id)? {
+            Some(round) => {
+                let amounts = unclaimed_amounts(&round, &bet);
+                settled.push((bet, amounts));
+            }
+            None => running.push(round_id),
+        }
+    }
+
+    Ok((settled, running))
+}
+
+/// Moves the finished rounds of a player to the unclaimed index and tracks the round of a new
+/// bet until it is finished
+pub fn track_bet(storage: &mut dyn Storage, player: &Addr, round_id: u128) -> StdResult<()> {
+    let (settled, mut running) = settled_bets(storage, player)?;
+    for (bet, amounts) in settled {
+        add_unclaimed(storage, &bet, &amounts)?;
+    }
+
+    if !running.contains(&round_id) {
+        running.push(round_id);
+    }
+    UNSETTLED_ROUNDS.save(storage, player.clone(), &running)
+}
+
+/// Adds the amounts of a bet of a finished round to the unclaimed index, unless there is nothing
+/// to claim
+pub fn add_unclaimed(
+    storage: &mut dyn Storage,
+    bet: &BetInfo,
+    amounts: &Unclaimed,
+) -> StdResult<()> {
+    if amounts.reward.is_zero() && amounts.refund.is_zero() {
+        return Ok(());
+    }
+
+    let totals_key = (bet.player.clone(), bet.collateral.clone());
+    let mut totals = UNCLAIMED_TOTALS
+        .may_load(storage, totals_key.clone())?
+        .unwrap_or_default();
+    totals.reward += amounts.reward;
+    totals.refund += amounts.refund;
+    UNCLAIMED_TOTALS.save(storage, totals_key, &totals)?;
+    UNCLAIMED_ROUNDS.save(
+        storage,
+        (
+            bet.player.clone(),
+            bet.collateral.clone(),
+            bet.round_id.u128(),
+        ),
+        amounts,
+    )
+}
+
+/// Removes a claimed bet from the unclaimed index. Bets of rounds not moved to the index yet are
+/// dropped when the next bet of the player moves them, as they are read from the bets
+pub fn remove_claimed(storage: &mut dyn Storage, bet: &BetInfo) -> StdResult<()> {
+    let key = (
+        bet.player.clone(),
+        bet.collateral.clone(),
+        bet.round_id.u128(),
+    );
+    let Some(amounts) = UNCLAIMED_ROUNDS.may_load(storage, key.clone())? else {
+        return Ok(());
+    };
+    UNCLAIMED_ROUNDS.remove(storage, key);
+
+    let totals_key = (bet.player.clone(), bet.collateral.clone());
+    let mut totals = UNCLAIMED_TOTALS.load(storage, totals_key.clone())?;
+    totals.reward = totals.reward.checked_sub(amounts.reward)?;
+    totals.refund = totals.refund.checked_sub(amounts.refund)?;
+    if totals == Unclaimed::default() {
+        UNCLAIMED_TOTALS.remove(storage, totals_key);
+    } else {
+        UNCLAIMED_TOTALS.save(storage, totals_key, &totals)?;
+    }
+
+    Ok(())
+}
+
+/// Amounts left to claim by a player with a collateral
+pub fn query_unclaimed_total(
+    storage: &dyn Storage,
+    player: &Addr,
+    collateral: &str,
+) -> StdResult<Unclaimed> {
+    let mut totals = UNCLAIMED_TOTALS
+        .may_load(storage, (player.clone(), collateral.to_string()))?
+        .unwrap_or_default();
+    let (settled, _) = settled_bets(storage, player)?;
+    for (_, amounts) in settled
+        .into_iter()
+        .filter(|(bet, _)| bet.collateral == collateral)
+    {
+        totals.reward += amounts.reward;
+        totals.refund += amounts.refund;
+    }
+
+    Ok(totals)
+}
+
+/// Amounts left to claim by a player with a collateral matching `filter`, by round in ascending
+/// order after `start_after`
+pub fn query_unclaimed_rounds(
+    storage: &dyn Storage,
+    player: &Addr,
+    collateral: &str,
+    start_after: Option<Uint128>,
+    limit: Option<u32>,
+    filter: impl Fn(&Unclaimed) -> bool,
+) -> StdResult<Vec<(Uint128, Unclaimed)>> {
+    let limit = limit.unwrap_or(MAX_PAGE_LIMIT).min(MAX_PAGE_LIMIT) as usize;
+    let start = start_after.map(|round_id| Bound::exclusive(round_id.u128()));
+    let mut rounds = UNCLAIMED_ROUNDS
+        .prefix((player.clone(), collateral.to_string()))
+        .range(storage, start, None, Order::Ascending)
+        .filter(|item| item.as_ref().map_or(true, |(_, amounts)| filter(amounts)))
+        .take(limit)
+        .map(|item| item.map(|(round_id, amounts)| (Uint128::from(round_id), amounts)))
+        .collect::<StdResult<Vec<_>>>()?;
+
+    // The finished rounds not moved to the index yet aren't in it, merge them into the page
+    let (settled, _) = settled_bets(storage, player)?;
+    rounds.extend(
+        settled
+            .into_iter()
+            .filter(|(bet, amounts)| {
+                bet.collateral == collateral
+                    && start_after.is_none_or(|start| bet.round_id > start)
+                    && filter(amounts)
+            })
+            .map(|(bet, amounts)| (bet.round_id, amounts)),
+    );
+    rounds.sort_by_key(|(round_id, _)| *round_id);
+    rounds.truncate(limit);
+
+    Ok(rounds)
+}
@@ -0,0 +1,354 @@
+//! Prediction game on cw-multi-test, with a Skip Connect oracle answering the price queries and a
+//! users contract accepting every experience update
+#![allow(dead_code)]
+
+use chrono::DateTime;
+use cosmwasm_std::testing::{MockApi, MockStorage};
+use cosmwasm_std::{
+    coins, from_json, to_json_binary, Addr, Api, Binary, BlockInfo, Coin, Decimal, Deps, DepsMut,
+    Empty, Env, Int128, MessageInfo, Querier, Response, StdError, StdResult, Storage, Uint128,
+};
+use cw20::Denom;
+use cw_multi_test::error::{bail, AnyResult};
+use cw_multi_test::{
+    App, AppResponse, BankKeeper, BasicAppBuilder, ContractWrapper, CosmosRouter, Executor, Module,
+    WasmKeeper,
+};
+use general::experience::{ExperienceConfig, PriceSource};
+use neutron_sdk::bindings::oracle::query::{GetPriceResponse, OracleQuery};
+use neutron_sdk::bindings::oracle::types::QuotePrice;
+use neutron_sdk::bindings::query::NeutronQuery;
+use prediction::prediction_game::msg::{ExecuteMsg, InstantiateMsg, QueryMsg};
+use prediction::prediction_game::{
+    ClaimReceipt, Collateral, Config, DenomTicker, Direction, FinishedRound, StatusResponse,
+    WalletInfo,
+};
+use prediction_game::contract;
+use prediction_game::error::ContractError;
+use serde::de::DeserializeOwned;
+
+pub const DENOM: &str = "untrn";
+pub const ROUND_SECONDS: u64 = 60;
+pub const MINIMUM_BET: u128 = 100;
+// 5%, with 2 decimals of precision
+pub const GAMING_FEE: u128 = 500;
+pub const INITIAL_BALANCE: u128 = 1_000_000_000;
+pub const START_PRICE: i128 = 60_000;
+
+pub type GameApp = App<BankKeeper, MockApi, MockStorage, Oracle, WasmKeeper<Empty, NeutronQuery>>;
+
+/// Answers every price query with the price set by the test, updated in the block of the query
+pub struct Oracle {
+    pub price: i128,
+}
+
+impl Module for Oracle {
+    type ExecT = Empty;
+    type QueryT = NeutronQuery;
+    type SudoT = Empty;
+
+    fn execute<ExecC, QueryC>(
+        &self,
+        _api: &dyn Api,
+        _storage: &mut dyn Storage,
+        _router: &dyn CosmosRouter<ExecC = ExecC, QueryC = QueryC>,
+        _block: &BlockInfo,
+        _sender: Addr,
+        _msg: Empty,
+    ) -> AnyResult<AppResponse> {
+        bail!("The oracle takes no messages")
+    }
+
+    fn query(
+        &self,
+        _api: &dyn Api,
+        _storage: &dyn Storage,
+        _querier: &dyn Querier,
+        block: &BlockInfo,
+        request: NeutronQuery,
+    ) -> AnyResult<Binary> {
+        let NeutronQuery::Oracle(OracleQuery::GetPrice { .. }) = request else {
+            bail!("Unsupported query {:?}", request)
+        };
+        let Some(updated_at) = DateTime::from_timestamp(block.time.seconds() as i64, 0) else {
+            bail!("Invalid block time")
+        };
+
+        Ok(to_json_binary(&GetPriceResponse {
+            price: QuotePrice {
+                price: Int128::new(self.price),
+                block_timestamp: updated_at.to_rfc3339(),
+                block_height: Some(block.height),
+            },
+            nonce: 0,
+            decimals: 18,
+            id: 0,
+        })?)
+    }
+
+    fn sudo<ExecC, QueryC>(
+        &self,
+        _api: &dyn Api,
+        _storage: &mut dyn Storage,
+        _router: &dyn CosmosRouter<ExecC = ExecC, QueryC = QueryC>,
+        _block: &BlockInfo,
+        _msg: Empty,
+    ) -> AnyResult<AppResponse> {
+        bail!("The oracle takes no sudo messages")
+    }
+}
+
+// The game reads the oracle in execute and reply only
+fn game_instantiate(
+    deps: DepsMut<NeutronQuery>,
+    env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    contract::instantiate(deps.into_empty(), env, info, msg)
+}
+
+fn game_query(deps: Deps<NeutronQuery>, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    contract::query(deps.into_empty(), env, msg)
+}
+
+fn users_instantiate(
+    _deps: DepsMut<NeutronQuery>,
+    _env: Env,
+    _info: MessageInfo,
+    _msg: Empty,
+) -> StdResult<Response> {
+    Ok(Response::new())
+}
+
+fn users_execute(
+    _deps: DepsMut<NeutronQuery>,
+    _env: Env,
+    _info: MessageInfo,
+    _msg: general::users::ExecuteMsg,
+) -> StdResult<Response> {
+    Ok(Response::new())
+}
+
+fn users_query(_deps: Deps<NeutronQuery>, _env: Env, _msg: Empty) -> StdResult<Binary> {
+    Err(StdError::generic_err("Not supported"))
+}
+
+pub struct Suite {
+    pub app: GameApp,
+    pub owner: Addr,
+    pub game: Addr,
+    pub users: Addr,
+    pub dev_wallet: Addr,
+}
+
+impl Suite {
+    /// Game betting on btc with a single native collateral. `configure` can change the config
+    /// and instantiate other contracts before the game
+    pub fn new(configure: impl FnOnce(&mut Suite, &mut Config)) -> Self {
+        let app = BasicAppBuilder::<Empty, NeutronQuery>::new_custom()
+            .with_custom(Oracle { price: START_PRICE })
+            .build(|_, _, _| {});
+        let owner = app.api().addr_make("owner");
+        let dev_wallet = app.api().addr_make("dev_wallet");
+        let mut suite = Suite {
+            app,
+            owner: owner.clone(),
+            game: Addr::unchecked(""),
+            users: Addr::unchecked(""),
+            dev_wallet: dev_wallet.clone(),
+        };
+
+        let users_code = suite.app.store_code(Box::new(ContractWrapper::new(
+            users_execute,
+            users_instantiate,
+            users_query,
+        )));
+        suite.users = suite
+            .app
+            .instantiate_contract(users_code, owner.clone(), &Empty {}, &[], "users", None)
+            .unwrap();
+
+        let mut config = Config {
+            next_round_seconds: Uint128::from(ROUND_SECONDS),
+            collaterals: vec![Collateral {
+                denom: Denom::Native(DENOM.to_string()),
+                minimum_bet: Uint128::new(MINIMUM_BET),
+                gaming_fee: Uint128::new(GAMING_FEE),
+                experience: ExperienceConfig {
+                    price_source: PriceSource::Fixed {
+                        usd_price: Decimal::one(),
+                    },
+                    token_decimals: 6,
+                    exp_per_usd_bet: 1,
+                    exp_per_usd_won: 1,
+                    max_exp_per_bet: 1_000,
+                    max_exp_per_claim: 1_000,
+                },
+                fixed_odds: None,
+                min_pool_to_open: Uint128::zero(),
+            }],
+            users_contract: suite.users.clone(),
+            elo_k_factor: 0,
+            dev_wallet_list: vec![WalletInfo {
+                address: dev_wallet,
+                ratio: Decimal::one(),
+            }],
+            swap_router: None,
+            position_nfts: false,
+            early_bet_bonus: Decimal::zero(),
+            min_players_to_open: 0,
+            bid_extension_seconds: Uint128::zero(),
+            max_bid_extensions: 0,
+            round_retention: 0,
+            hedging: false,
+        };
+        configure(&mut suite, &mut config);
+
+        let game_code = suite.app.store_code(Box::new(
+            ContractWrapper::new(contract::execute, game_instantiate, game_query)
+                .with_reply(contract::reply),
+        ));
+        let msg = InstantiateMsg {
+            config,
+            denom_tickers: vec![DenomTicker {
+                denom: "btc".to_string(),
+                ticker: "BTC".to_string(),
+            }],
+            owner: None,
+            extra_admins: None,
+        };
+        suite.game = suite
+            .app
+            .instantiate_contract(game_code, owner, &msg, &[], "prediction-game", None)
+            .unwrap();
+
+        // The first round takes bets
+        suite.close_round().unwrap();
+        suite
+    }
+
+    /// Address holding INITIAL_BALANCE of the collateral
+    pub fn account(&mut self, name: &str) -> Addr {
+        let address = self.app.api().addr_make(name);
+        self.app
+            .init_modules(|router, _, storage| {
+                router
+                    .bank
+                    .init_balance(storage, &address, coins(INITIAL_BALANCE, DENOM))
+            })
+            .unwrap();
+        address
+    }
+
+    pub fn balance(&self, address: &Addr) -> u128 {
+        self.app
+            .wrap()
+            .query_balance(address, DENOM)
+            .unwrap()
+            .amount
+            .u128()
+    }
+
+    pub fn set_price(&mut self, price: i128) {
+        self.app
+            .init_modules(|router, _, _| router.custom.price = price);
+    }
+
+    pub fn execute(
+        &mut self,
+        sender: &Addr,
+        msg: &ExecuteMsg,
+        funds: &[Coin],
+    ) -> AnyResult<AppResponse> {
+        self.app
+            .execute_contract(sender.clone(), self.game.clone(), msg, funds)
+    }
+
+    pub fn query<T: DeserializeOwned>(&self, msg: &QueryMsg) -> T {
+        self.app.wrap().query_wasm_smart(&self.game, msg).unwrap()
+    }
+
+    pub fn status(&self) -> StatusResponse {
+        self.query(&QueryMsg::Status {})
+    }
+
+    /// Round taking bets
+    pub fn bidding_round(&self) -> Uint128 {
+        self.status().bidding_round.unwrap().id
+    }
+
+    pub fn finished_round(&self, round_id: Uint128) -> FinishedRound {
+        self.query(&QueryMsg::FinishedRound { round_id })
+    }
+
+    pub fn close_round(&mut self) -> AnyResult<AppResponse> {
+        let owner = self.owner.clone();
+        self.execute(&owner, &ExecuteMsg::CloseRound {}, &[])
+    }
+
+    /// Waits until the next round boundary and closes the round
+    pub fn next_round(&mut self) -> AnyResult<AppResponse> {
+        self.app.update_block(|block| {
+            block.time = block.time.plus_seconds(ROUND_SECONDS);
+            block.height += 1;
+        });
+        self.close_round()
+    }
+
+    /// Opens the bidding round at the current price and finishes it at `close_price`, returns
+    /// the id of the round
+    pub fn play_round(&mut self, close_price: i128) -> Uint128 {
+        let round_id = self.bidding_round();
+        self.next_round().unwrap();
+        self.set_price(close_price);
+        self.next_round().unwrap();
+        round_id
+    }
+
+    pub fn bet(
+        &mut self,
+        player: &Addr,
+        direction: Direction,
+        amount: u128,
+    ) -> AnyResult<AppResponse> {
+        let round_id = self.bidding_round();
+        let amount_sent = Uint128::new(amount);
+        let msg = match direction {
+            Direction::Bull => ExecuteMsg::BetBull {
+                round_id,
+                amount: amount_sent,
+            },
+            Direction::Bear => ExecuteMsg::BetBear {
+                round_id,
+                amount: amount_sent,
+            },
+        };
+        self.execute(player, &msg, &coins(amount, DENOM))
+    }
+
+    /// Claims every round of the collateral
+    pub fn collect_winnings(&mut self, player: &Addr) -> AnyResult<ClaimReceipt> {
+        let msg = ExecuteMsg::CollectWinnings {
+            collateral: DENOM.to_string(),
+        };
+        let resp = self.execute(player, &msg, &[])?;
+        Ok(from_json(resp.data.unwrap())?)
+    }
+
+    pub fn collect_round(&mut self, player: &Addr, round_id: Uint128) -> AnyResult<ClaimReceipt> {
+        let msg = ExecuteMsg::CollectionWinningRound { round_id };
+        let resp = self.execute(player, &msg, &[])?;
+        Ok(from_json(resp.data.unwrap())?)
+    }
+}
+
+/// Gaming fee of parimutuel winnings
+pub fn fee(gross: u128) -> u128 {
+    gross * GAMING_FEE / 10_000
+}
+
+/// Error of the game behind a failed message
+pub fn game_error(err: cw_multi_test::error::AnyError) -> ContractError {
+    err.downcast().unwrap()
+}
@@ -0,0 +1,236 @@
+mod common;
+
+use common::*;
+use cosmwasm_std::Uint128;
+use prediction::positions::{NumTokensResponse, OwnerOfResponse, TokensResponse};
+use prediction::prediction_game::msg::{ExecuteMsg, QueryMsg};
+use prediction::prediction_game::{ClaimInfoResponse, ConfigUpdate, Direction, MyGameResponse};
+
+const STAKE: u128 = 10_000;
+
+/// Game keeping only the latest finished round in full
+fn setup() -> Suite {
+    Suite::new(|_, config| config.round_retention = 1)
+}
+
+/// Plays a round with bets from `first` and `second` closing at `close_price`, then finishes the
+/// next round so the first one is past the retention
+fn play_old_round(
+    suite: &mut Suite,
+    first: (&str, Direction),
+    second: (&str, Direction),
+    close_price: i128,
+) -> Uint128 {
+    for (name, direction) in [first, second] {
+        let player = suite.account(name);
+        suite.bet(&player, direction, STAKE).unwrap();
+    }
+    let round_id = suite.play_round(close_price);
+    suite.next_round().unwrap();
+    round_id
+}
+
+fn compact(suite: &mut Suite) {
+    compact_page(suite, None);
+}
+
+fn compact_page(suite: &mut Suite, limit: Option<u32>) {
+    let sender = suite.owner.clone();
+    suite
+        .execute(&sender, &ExecuteMsg::CompactRounds { limit }, &[])
+        .unwrap();
+}
+
+fn bets(suite: &Suite, player: &str) -> usize {
+    let player = suite.app.api().addr_make(player);
+    let games: MyGameResponse = suite.query(&QueryMsg::MyGameList {
+        player,
+        start_after: None,
+        limit: None,
+    });
+    games.my_game_list.len()
+}
+
+#[test]
+fn winnings_stay_claimable_until_the_round_is_compacted() {
+    let mut suite = setup();
+    let round_id = play_old_round(
+        &mut suite,
+        ("bull", Direction::Bull),
+        ("bear", Direction::Bear),
+        START_PRICE + 1,
+    );
+    let bull = suite.app.api().addr_make("bull");
+
+    // The lost bet is pruned, the round is kept for the winner
+    compact(&mut suite);
+    assert_eq!(bets(&suite, "bear"), 0);
+    assert_eq!(bets(&suite, "bull"), 1);
+    assert!(!suite.finished_round(round_id).compacted);
+
+    let gross = 2 * STAKE;
+    let receipt = suite.collect_winnings(&bull).unwrap();
+    assert_eq!(receipt.gross, Uint128::new(gross));
+    assert_eq!(
+        suite.balance(&bull),
+        INITIAL_BALANCE - STAKE + gross - fee(gross)
+    );
+
+    // With nothing left to claim the claim record is pruned and the round compacted
+    compact(&mut suite);
+    let round = suite.finished_round(round_id);
+    assert!(round.compacted);
+    assert_eq!(round.winner, Some(Direction::Bull));
+    assert_eq!(round.pools[0].bull_amount, Uint128::new(STAKE));
+    let claims: ClaimInfoResponse = suite.query(&QueryMsg::GetClaimInfoPerRound {
+        round_id,
+        start_after: None,
+        limit: None,
+    });
+    assert!(claims.claim_info.is_empty());
+    assert!(suite.collect_round(&bull, round_id).is_err());
+}
+
+#[test]
+fn refunds_stay_claimable_until_the_round_is_compacted() {
+    let mut suite = setup();
+    // Nobody bet on the winning side, both stakes are refunded
+    let round_id = play_old_round(
+        &mut suite,
+        ("first", Direction::Bull),
+        ("second", Direction::Bull),
+        START_PRICE - 1,
+    );
+    let first = suite.app.api().addr_make("first");
+    let second = suite.app.api().addr_make("second");
+    assert_eq!(suite.finished_round(round_id).winner, Some(Direction::Bear));
+
+    // No bet is lost
+    compact(&mut suite);
+    assert_eq!(bets(&suite, "first"), 1);
+    assert_eq!(bets(&suite, "second"), 1);
+
+    suite.collect_round(&first, round_id).unwrap();
+    compact(&mut suite);
+    assert!(!suite.finished_round(round_id).compacted);
+
+    let receipt = suite.collect_round(&second, round_id).unwrap();
+    assert_eq!(receipt.gross, Uint128::new(STAKE));
+    assert_eq!(receipt.fee, Uint128::zero());
+    assert_eq!(suite.balance(&first), INITIAL_BALANCE);
+    assert_eq!(suite.balance(&second), INITIAL_BALANCE);
+
+    compact(&mut suite);
+    let round = suite.finished_round(round_id);
+    assert!(round.compacted);
+    assert_eq!(round.pools[0].bull_amount, Uint128::new(2 * STAKE));
+    assert_eq!(suite.balance(&suite.game.clone()), 0);
+}
+
+#[test]
+fn compaction_resumes_where_the_previous_call_stopped() {
+    let mut suite = setup();
+    let mut rounds = vec![];
+    for (bull, bear) in [("bull1", "bear1"), ("bull2", "bear2"), ("bull3", "bear3")] {
+        let round_id = play_old_round(
+            &mut suite,
+            (bull, Direction::Bull),
+            (bear, Direction::Bear),
+            START_PRICE + 1,
+        );
+        suite.set_price(START_PRICE);
+        let winner = suite.app.api().addr_make(bull);
+        suite.collect_round(&winner, round_id).unwrap();
+        rounds.push(round_id);
+    }
+    suite.next_round().unwrap();
+
+    // A claim record and a lost bet per round, one record per call
+    let mut calls = 0;
+    let mut compacted = 0;
+    while compacted < rounds.len() {
+        compact_page(&mut suite, Some(1));
+        calls += 1;
+        let now_compacted = rounds
+            .iter()
+            .filter(|round_id| suite.finished_round(**round_id).compacted)
+            .count();
+        // Rounds are compacted in order, none is skipped or done twice
+        assert!(now_compacted >= compacted);
+        for round_id in &rounds[..now_compacted] {
+            assert!(suite.finished_round(*round_id).compacted);
+        }
+        compacted = now_compacted;
+        assert!(calls <= 3 * rounds.len(), "compaction doesn't progress");
+    }
+    assert!(calls >= 2 * rounds.len());
+    for name in ["bear1", "bear2", "bear3"] {
+        assert_eq!(bets(&suite, name), 0);
+    }
+}
+
+#[test]
+fn unclaimed_bets_and_open_positions_survive_compaction() {
+    let mut suite = Suite::new(|_, config| {
+        config.round_retention = 1;
+        config.position_nfts = true;
+    });
+    let holder = suite.account("holder");
+    let burned = suite.account("burned");
+    let player = suite.account("player");
+    let loser = suite.account("loser");
+
+    suite.bet(&holder, Direction::Bull, STAKE).unwrap();
+    suite.bet(&burned, Direction::Bear, STAKE).unwrap();
+    let tokenized_round = suite.play_round(START_PRICE + 1);
+    suite.set_price(START_PRICE);
+    let tokens: TokensResponse = suite.query(&QueryMsg::Tokens {
+        owner: holder.to_string(),
+        start_after: None,
+        limit: None,
+    });
+    let token_id = tokens.tokens[0].clone();
+
+    let owner = suite.owner.clone();
+    let msg = ExecuteMsg::UpdateConfig {
+        config: ConfigUpdate {
+            position_nfts: Some(false),
+            ..ConfigUpdate::default()
+        },
+    };
+    suite.execute(&owner, &msg, &[]).unwrap();
+    suite.bet(&player, Direction::Bear, STAKE).unwrap();
+    suite.bet(&loser, Direction::Bull, STAKE).unwrap();
+    let round_id = suite.play_round(START_PRICE - 1);
+    suite.next_round().unwrap();
+
+    compact(&mut suite);
+    compact(&mut suite);
+    // Only the lost position is burned
+    let count: NumTokensResponse = suite.query(&QueryMsg::NumTokens {});
+    assert_eq!(count.count, 1);
+    let owner_of: OwnerOfResponse = suite.query(&QueryMsg::OwnerOf {
+        token_id: token_id.clone(),
+        include_expired: None,
+    });
+    assert_eq!(owner_of.owner, holder.to_string());
+    assert_eq!(bets(&suite, "loser"), 0);
+    assert_eq!(bets(&suite, "player"), 1);
+    assert!(!suite.finished_round(tokenized_round).compacted);
+    assert!(!suite.finished_round(round_id).compacted);
+
+    let gross = 2 * STAKE;
+    let receipt = suite.collect_round(&player, round_id).unwrap();
+    assert_eq!(receipt.gross, Uint128::new(gross));
+    suite
+        .execute(&holder, &ExecuteMsg::ClaimPosition { token_id }, &[])
+        .unwrap();
+    assert_eq!(
+        suite.balance(&holder),
+        INITIAL_BALANCE - STAKE + gross - fee(gross)
+    );
+
+    compact(&mut suite);
+    assert!(suite.finished_round(tokenized_round).compacted);
+    assert!(suite.finished_round(round_id).compacted);
+}
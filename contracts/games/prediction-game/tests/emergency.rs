@@ -0,0 +1,142 @@
+mod common;
+
+use common::*;
+use cosmwasm_std::{coins, Uint128};
+use general::rbac::{RbacError, Role};
+use prediction::prediction_game::msg::ExecuteMsg;
+use prediction::prediction_game::Direction;
+use prediction_game::error::ContractError;
+
+const STAKE: u128 = 10_000;
+
+/// Stakes of `player` and `other` in the live round and `player` in the bidding round, then the
+/// owner halts the game and exits. Returns the live and bidding round ids
+fn exit_with_open_rounds(suite: &mut Suite) -> (Uint128, Uint128) {
+    let player = suite.account("player");
+    let other = suite.account("other");
+
+    let live_round = suite.bidding_round();
+    suite.bet(&player, Direction::Bull, STAKE).unwrap();
+    suite.bet(&other, Direction::Bear, STAKE).unwrap();
+    suite.next_round().unwrap();
+    let next_round = suite.bidding_round();
+    suite.bet(&player, Direction::Bear, STAKE).unwrap();
+
+    let owner = suite.owner.clone();
+    suite.execute(&owner, &ExecuteMsg::Halt {}, &[]).unwrap();
+    suite
+        .execute(&owner, &ExecuteMsg::EmergencyExit {}, &[])
+        .unwrap();
+
+    (live_round, next_round)
+}
+
+#[test]
+fn players_claim_their_stakes_back_after_the_exit() {
+    let mut suite = Suite::new(|_, _| {});
+    let sponsor = suite.account("sponsor");
+    let msg = ExecuteMsg::Sponsor {
+        start_round: suite.bidding_round() + Uint128::one(),
+        end_round: suite.bidding_round() + Uint128::new(2),
+        denom: None,
+        amount_per_round: Uint128::new(MINIMUM_BET),
+    };
+    suite
+        .execute(&sponsor, &msg, &coins(2 * MINIMUM_BET, DENOM))
+        .unwrap();
+
+    let (live_round, next_round) = exit_with_open_rounds(&mut suite);
+    let player = suite.app.api().addr_make("player");
+    assert!(suite.finished_round(live_round).pools[0].voided);
+    assert!(suite.finished_round(next_round).pools[0].voided);
+    // The rounds that will never be played are refunded to the sponsor
+    assert_eq!(suite.balance(&sponsor), INITIAL_BALANCE);
+
+    let receipt = suite.collect_winnings(&player).unwrap();
+    assert_eq!(receipt.gross, Uint128::new(2 * STAKE));
+    assert_eq!(receipt.fee, Uint128::zero());
+    assert_eq!(suite.balance(&player), INITIAL_BALANCE);
+
+    // No round is played anymore
+    let err = suite.close_round().unwrap_err();
+    assert_eq!(game_error(err), ContractError::RoundsPaused {});
+}
+
+#[test]
+fn the_exit_happens_once() {
+    let mut suite = Suite::new(|_, _| {});
+    exit_with_open_rounds(&mut suite);
+    let owner = suite.owner.clone();
+
+    let err = suite
+        .execute(&owner, &ExecuteMsg::EmergencyExit {}, &[])
+        .unwrap_err();
+    assert_eq!(game_error(err), ContractError::EmergencyExitActive {});
+}
+
+#[test]
+fn owner_pushes_the_stakes_of_voided_rounds() {
+    let mut suite = Suite::new(|_, _| {});
+    let (live_round, _) = exit_with_open_rounds(&mut suite);
+    let player = suite.app.api().addr_make("player");
+    let other = suite.app.api().addr_make("other");
+    let owner = suite.owner.clone();
+    let pauser = suite.app.api().addr_make("pauser");
+    let grant = ExecuteMsg::GrantRole {
+        role: Role::Pauser,
+        address: pauser.to_string(),
+    };
+    suite.execute(&owner, &grant, &[]).unwrap();
+
+    // Pushing refunds moves the stakes of players, pausers can't do it
+    let msg = ExecuteMsg::EmergencyRefund {
+        round_id: live_round,
+        limit: None,
+    };
+    assert!(suite.execute(&player, &msg, &[]).is_err());
+    let err = suite.execute(&pauser, &msg, &[]).unwrap_err();
+    assert_eq!(
+        game_error(err),
+        ContractError::Rbac(RbacError::MissingRole {
+            address: pauser,
+            role: Role::Owner,
+        })
+    );
+    suite.execute(&owner, &msg, &[]).unwrap();
+    assert_eq!(suite.balance(&other), INITIAL_BALANCE);
+    assert_eq!(suite.balance(&player), INITIAL_BALANCE - STAKE);
+
+    // The refunded bets are gone, they can't be claimed a second time
+    assert!(suite.collect_round(&other, live_round).is_err());
+    assert!(suite.collect_round(&player, live_round).is_err());
+    assert!(suite.collect_winnings(&other).is_err());
+    // Pushing again pays nobody
+    suite.execute(&owner, &msg, &[]).unwrap();
+    assert_eq!(suite.balance(&other), INITIAL_BALANCE);
+
+    // Only the stake of the other round is left to claim
+    let receipt = suite.collect_winnings(&player).unwrap();
+    assert_eq!(receipt.gross, Uint128::new(STAKE));
+    assert_eq!(suite.balance(&player), INITIAL_BALANCE);
+    assert_eq!(suite.balance(&suite.game.clone()), 0);
+}
+
+#[test]
+fn refunds_are_not_pushed_twice_after_a_claim() {
+    let mut suite = Suite::new(|_, _| {});
+    let (live_round, _) = exit_with_open_rounds(&mut suite);
+    let other = suite.app.api().addr_make("other");
+    let owner = suite.owner.clone();
+
+    // The player claims first, the push only pays who is left
+    let receipt = suite.collect_round(&other, live_round).unwrap();
+    assert_eq!(receipt.gross, Uint128::new(STAKE));
+    let msg = ExecuteMsg::EmergencyRefund {
+        round_id: live_round,
+        limit: None,
+    };
+    suite.execute(&owner, &msg, &[]).unwrap();
+    assert_eq!(suite.balance(&other), INITIAL_BALANCE);
+    let player = suite.app.api().addr_make("player");
+    assert_eq!(suite.balance(&player), INITIAL_BALANCE - STAKE);
+}
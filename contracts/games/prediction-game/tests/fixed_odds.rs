@@ -0,0 +1,156 @@
+mod common;
+
+use common::*;
+use cosmwasm_std::{coins, Addr, Decimal, Uint128};
+use cw_multi_test::{ContractWrapper, Executor};
+use prediction::prediction_game::{Config, Direction, FixedOdds};
+use vault::liquidity_vault::msg::{
+    ExecuteMsg as VaultExecuteMsg, InstantiateMsg as VaultInstantiateMsg,
+};
+use vault::liquidity_vault::Config as VaultConfig;
+
+const LIQUIDITY: u128 = 1_000_000;
+const STAKE: u128 = 10_000;
+
+/// Game taking every bet at 1.9x through a vault holding LIQUIDITY
+fn setup() -> (Suite, Addr) {
+    setup_with(|_| {})
+}
+
+/// Same game, with the config changed by `configure`
+fn setup_with(configure: impl FnOnce(&mut Config)) -> (Suite, Addr) {
+    let mut vault = Addr::unchecked("");
+    let mut suite = Suite::new(|suite, config| {
+        configure(config);
+        let code = suite
+            .app
+            .store_code(Box::new(ContractWrapper::new_with_empty(
+                liquidity_vault::contract::execute,
+                liquidity_vault::contract::instantiate,
+                liquidity_vault::contract::query,
+            )));
+        let msg = VaultInstantiateMsg {
+            owner: None,
+            config: VaultConfig {
+                denom: cw20::Denom::Native(DENOM.to_string()),
+                max_round_exposure: Decimal::percent(50),
+            },
+            games: vec![],
+        };
+        vault = suite
+            .app
+            .instantiate_contract(code, suite.owner.clone(), &msg, &[], "vault", None)
+            .unwrap();
+        config.collaterals[0].fixed_odds = Some(FixedOdds {
+            vault: vault.clone(),
+            house_edge: Decimal::percent(5),
+        });
+    });
+
+    let owner = suite.owner.clone();
+    let game = suite.game.to_string();
+    suite
+        .app
+        .execute_contract(
+            owner,
+            vault.clone(),
+            &VaultExecuteMsg::AddGame { game },
+            &[],
+        )
+        .unwrap();
+    let provider = suite.account("provider");
+    suite
+        .app
+        .execute_contract(
+            provider,
+            vault.clone(),
+            &VaultExecuteMsg::Deposit {},
+            &coins(LIQUIDITY, DENOM),
+        )
+        .unwrap();
+
+    (suite, vault)
+}
+
+#[test]
+fn winning_bet_is_paid_by_the_vault_without_fees() {
+    let (mut suite, vault) = setup();
+    let player = suite.account("player");
+    let loser = suite.account("loser");
+
+    suite.bet(&player, Direction::Bull, STAKE).unwrap();
+    suite.bet(&loser, Direction::Bear, STAKE).unwrap();
+    // The stakes are held by the vault until the round is settled
+    assert_eq!(suite.balance(&vault), LIQUIDITY + 2 * STAKE);
+    assert_eq!(suite.balance(&suite.game.clone()), 0);
+
+    let round_id = suite.play_round(START_PRICE + 1);
+    let payout = STAKE * 19 / 10;
+    assert_eq!(suite.finished_round(round_id).winner, Some(Direction::Bull));
+    assert_eq!(suite.balance(&vault), LIQUIDITY + 2 * STAKE - payout);
+
+    let receipt = suite.collect_winnings(&player).unwrap();
+    assert_eq!(receipt.gross, Uint128::new(payout));
+    assert_eq!(receipt.fee, Uint128::zero());
+    assert_eq!(receipt.net, Uint128::new(payout));
+    assert_eq!(suite.balance(&player), INITIAL_BALANCE - STAKE + payout);
+    assert_eq!(suite.balance(&suite.dev_wallet.clone()), 0);
+
+    // The lost stake stays with the vault
+    assert!(suite.collect_winnings(&player).is_err());
+    let receipt = suite.collect_winnings(&loser).unwrap();
+    assert_eq!(receipt.gross, Uint128::zero());
+    assert_eq!(suite.balance(&loser), INITIAL_BALANCE - STAKE);
+    assert_eq!(suite.balance(&suite.game.clone()), 0);
+}
+
+#[test]
+fn round_without_winner_refunds_the_stakes_from_the_vault() {
+    let (mut suite, vault) = setup();
+    let player = suite.account("player");
+
+    suite.bet(&player, Direction::Bear, STAKE).unwrap();
+    let round_id = suite.play_round(START_PRICE);
+    assert_eq!(suite.finished_round(round_id).winner, None);
+    assert_eq!(suite.balance(&vault), LIQUIDITY);
+
+    let receipt = suite.collect_round(&player, round_id).unwrap();
+    assert_eq!(receipt.gross, Uint128::new(STAKE));
+    assert_eq!(receipt.fee, Uint128::zero());
+    assert_eq!(suite.balance(&player), INITIAL_BALANCE);
+    assert_eq!(suite.balance(&suite.game.clone()), 0);
+}
+
+#[test]
+fn voided_round_refunds_the_stakes_from_the_vault() {
+    // The round is voided unless two players join
+    let (mut suite, vault) = setup_with(|config| config.min_players_to_open = 2);
+    let player = suite.account("player");
+
+    let round_id = suite.bidding_round();
+    suite.bet(&player, Direction::Bull, STAKE).unwrap();
+    suite.next_round().unwrap();
+    assert!(suite.finished_round(round_id).pools[0].voided);
+    assert_eq!(suite.balance(&vault), LIQUIDITY);
+
+    let receipt = suite.collect_round(&player, round_id).unwrap();
+    assert_eq!(receipt.gross, Uint128::new(STAKE));
+    assert_eq!(receipt.fee, Uint128::zero());
+    assert_eq!(suite.balance(&player), INITIAL_BALANCE);
+    assert_eq!(suite.balance(&suite.game.clone()), 0);
+}
+
+#[test]
+fn bets_paying_more_than_the_vault_exposure_are_rejected() {
+    let (mut suite, vault) = setup();
+    let player = suite.account("player");
+
+    // 1.9x of the stake is above half of the liquidity
+    let stake = LIQUIDITY / 2;
+    assert!(suite.bet(&player, Direction::Bull, stake).is_err());
+    assert_eq!(suite.balance(&player), INITIAL_BALANCE);
+    assert_eq!(suite.balance(&vault), LIQUIDITY);
+
+    suite.bet(&player, Direction::Bull, STAKE).unwrap();
+    assert_eq!(suite.balance(&vault), LIQUIDITY + STAKE);
+}
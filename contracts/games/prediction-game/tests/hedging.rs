@@ -0,0 +1,131 @@
+mod common;
+
+use common::*;
+use cosmwasm_std::Uint128;
+use prediction::prediction_game::msg::QueryMsg;
+use prediction::prediction_game::{Direction, RoundUsersResponse};
+
+const STAKE: u128 = 10_000;
+const HEDGE: u128 = 4_000;
+
+#[test]
+fn winning_side_of_a_hedged_bet_is_paid() {
+    let mut suite = Suite::new(|_, config| config.hedging = true);
+    let player = suite.account("player");
+    let other = suite.account("other");
+
+    suite.bet(&player, Direction::Bull, STAKE).unwrap();
+    suite.bet(&player, Direction::Bear, HEDGE).unwrap();
+    suite.bet(&other, Direction::Bear, STAKE).unwrap();
+    let round_id = suite.bidding_round();
+    let users: RoundUsersResponse = suite.query(&QueryMsg::GetUsersPerRound {
+        round_id,
+        start_after: None,
+        limit: None,
+    });
+    let bet = users
+        .round_users
+        .iter()
+        .find(|bet| bet.player == player)
+        .unwrap();
+    assert_eq!(bet.amount, Uint128::new(STAKE));
+    assert_eq!(bet.hedge.as_ref().unwrap().amount, Uint128::new(HEDGE));
+
+    suite.play_round(START_PRICE + 1);
+
+    // The bull side takes the whole pool, the hedge is lost
+    let gross = 2 * STAKE + HEDGE;
+    let receipt = suite.collect_round(&player, round_id).unwrap();
+    assert_eq!(receipt.payouts.len(), 1);
+    assert_eq!(receipt.gross, Uint128::new(gross));
+    assert_eq!(receipt.fee, Uint128::new(fee(gross)));
+    assert_eq!(
+        suite.balance(&player),
+        INITIAL_BALANCE - STAKE - HEDGE + gross - fee(gross)
+    );
+    assert!(suite.collect_round(&player, round_id).is_err());
+}
+
+#[test]
+fn both_sides_of_a_hedged_bet_are_refunded() {
+    // The round is voided unless two players join
+    let mut suite = Suite::new(|_, config| {
+        config.hedging = true;
+        config.min_players_to_open = 2;
+    });
+    let player = suite.account("player");
+
+    let round_id = suite.bidding_round();
+    suite.bet(&player, Direction::Bull, STAKE).unwrap();
+    suite.bet(&player, Direction::Bear, HEDGE).unwrap();
+    suite.next_round().unwrap();
+    assert!(suite.finished_round(round_id).pools[0].voided);
+
+    let receipt = suite.collect_winnings(&player).unwrap();
+    assert_eq!(receipt.payouts.len(), 1);
+    assert_eq!(receipt.gross, Uint128::new(STAKE + HEDGE));
+    assert_eq!(receipt.fee, Uint128::zero());
+    assert_eq!(suite.balance(&player), INITIAL_BALANCE);
+    assert_eq!(suite.balance(&suite.game.clone()), 0);
+}
+
+#[test]
+fn hedge_alone_on_the_winning_side_takes_the_pool() {
+    let mut suite = Suite::new(|_, config| config.hedging = true);
+    let player = suite.account("player");
+    let other = suite.account("other");
+
+    suite.bet(&player, Direction::Bull, STAKE).unwrap();
+    suite.bet(&player, Direction::Bear, HEDGE).unwrap();
+    suite.bet(&other, Direction::Bull, STAKE).unwrap();
+    let round_id = suite.play_round(START_PRICE - 1);
+
+    // Nobody else bet bear, the hedge gets every share of the pool
+    let gross = 2 * STAKE + HEDGE;
+    let receipt = suite.collect_round(&player, round_id).unwrap();
+    assert_eq!(receipt.gross, Uint128::new(gross));
+    assert_eq!(receipt.fee, Uint128::new(fee(gross)));
+    let receipt = suite.collect_round(&other, round_id).unwrap();
+    assert_eq!(receipt.gross, Uint128::zero());
+    assert_eq!(suite.balance(&other), INITIAL_BALANCE - STAKE);
+    assert_eq!(suite.balance(&suite.game.clone()), 0);
+}
+
+#[test]
+fn round_with_an_empty_side_is_refunded_with_hedging() {
+    let mut suite = Suite::new(|_, config| config.hedging = true);
+    let player = suite.account("player");
+    let other = suite.account("other");
+
+    // Hedging is allowed but nobody bet bear
+    suite.bet(&player, Direction::Bull, STAKE).unwrap();
+    suite.bet(&other, Direction::Bull, STAKE).unwrap();
+    let round_id = suite.play_round(START_PRICE + 1);
+    assert!(suite.finished_round(round_id).pools[0].is_refund());
+
+    for player in [&player, &other] {
+        let receipt = suite.collect_round(player, round_id).unwrap();
+        assert_eq!(receipt.gross, Uint128::new(STAKE));
+        assert_eq!(receipt.fee, Uint128::zero());
+        assert_eq!(suite.balance(player), INITIAL_BALANCE);
+    }
+}
+
+#[test]
+fn both_sides_of_a_hedged_bet_are_paid_back_without_winner() {
+    let mut suite = Suite::new(|_, config| config.hedging = true);
+    let player = suite.account("player");
+    let other = suite.account("other");
+
+    suite.bet(&player, Direction::Bull, STAKE).unwrap();
+    suite.bet(&player, Direction::Bear, HEDGE).unwrap();
+    suite.bet(&other, Direction::Bear, STAKE).unwrap();
+    // The price didn't move, every side gets its stake back
+    let round_id = suite.play_round(START_PRICE);
+    assert_eq!(suite.finished_round(round_id).winner, None);
+
+    let gross = STAKE + HEDGE;
+    let receipt = suite.collect_round(&player, round_id).unwrap();
+    assert_eq!(receipt.gross, Uint128::new(gross));
+    assert_eq!(suite.balance(&player), INITIAL_BALANCE - fee(gross));
+}
@@ -0,0 +1,118 @@
+mod common;
+
+use common::*;
+use cosmwasm_std::{coins, Addr, Uint128};
+use general::ibc::{intermediate_sender, IbcDenom, IbcDenomsResponse};
+use prediction::prediction_game::msg::{ExecuteMsg, QueryMsg};
+use prediction::prediction_game::Direction;
+use prediction_game::error::ContractError;
+
+const STAKE: u128 = 10_000;
+const CHANNEL: &str = "channel-0";
+const ORIGINAL_SENDER: &str = "cosmos1player";
+
+/// Address ibc-hooks executes the bets of ORIGINAL_SENDER with, funded with DENOM
+fn ibc_player(suite: &mut Suite) -> Addr {
+    let address = intermediate_sender(suite.app.api(), CHANNEL, ORIGINAL_SENDER).unwrap();
+    suite
+        .app
+        .init_modules(|router, _, storage| {
+            router
+                .bank
+                .init_balance(storage, &address, coins(INITIAL_BALANCE, DENOM))
+        })
+        .unwrap();
+    address
+}
+
+fn ibc_bet(suite: &mut Suite, player: &Addr) -> Result<(), ContractError> {
+    let msg = ExecuteMsg::IbcBet {
+        channel: CHANNEL.to_string(),
+        original_sender: ORIGINAL_SENDER.to_string(),
+        round_id: suite.bidding_round(),
+        direction: Direction::Bull,
+        return_to_origin: false,
+    };
+    suite
+        .execute(player, &msg, &coins(STAKE, DENOM))
+        .map(|_| ())
+        .map_err(game_error)
+}
+
+#[test]
+fn only_allowed_collaterals_are_bet_through_ibc() {
+    let mut suite = Suite::new(|_, _| {});
+    let player = ibc_player(&mut suite);
+    let owner = suite.owner.clone();
+
+    let err = ibc_bet(&mut suite, &player).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::IbcDenomNotAllowed {
+            denom: DENOM.to_string()
+        }
+    );
+
+    // Only the denom of a native collateral can be allowed
+    let msg = ExecuteMsg::AddIbcDenom {
+        ibc_denom: "ibc/ATOM".to_string(),
+    };
+    let err = suite.execute(&owner, &msg, &[]).unwrap_err();
+    assert_eq!(
+        game_error(err),
+        ContractError::IbcDenomNotCollateral {
+            ibc_denom: "ibc/ATOM".to_string()
+        }
+    );
+
+    let msg = ExecuteMsg::AddIbcDenom {
+        ibc_denom: DENOM.to_string(),
+    };
+    suite.execute(&owner, &msg, &[]).unwrap();
+    let resp: IbcDenomsResponse = suite.query(&QueryMsg::GetIbcDenoms {});
+    assert_eq!(
+        resp.ibc_denoms,
+        vec![IbcDenom {
+            ibc_denom: DENOM.to_string(),
+            denom: DENOM.to_string(),
+        }]
+    );
+    ibc_bet(&mut suite, &player).unwrap();
+    assert_eq!(suite.balance(&player), INITIAL_BALANCE - STAKE);
+
+    let msg = ExecuteMsg::RemoveIbcDenom {
+        ibc_denom: DENOM.to_string(),
+    };
+    suite.execute(&owner, &msg, &[]).unwrap();
+    assert!(ibc_bet(&mut suite, &player).is_err());
+    let resp: IbcDenomsResponse = suite.query(&QueryMsg::GetIbcDenoms {});
+    assert!(resp.ibc_denoms.is_empty());
+}
+
+#[test]
+fn ibc_bets_are_placed_for_the_intermediate_sender() {
+    let mut suite = Suite::new(|_, _| {});
+    let player = ibc_player(&mut suite);
+    let other = suite.account("other");
+    let owner = suite.owner.clone();
+    let msg = ExecuteMsg::AddIbcDenom {
+        ibc_denom: DENOM.to_string(),
+    };
+    suite.execute(&owner, &msg, &[]).unwrap();
+
+    // Anyone else claiming to relay that sender is rejected
+    let err = ibc_bet(&mut suite, &other).unwrap_err();
+    assert_eq!(err, ContractError::InvalidIbcSender {});
+
+    ibc_bet(&mut suite, &player).unwrap();
+    suite.bet(&other, Direction::Bear, STAKE).unwrap();
+    suite.play_round(START_PRICE + 1);
+
+    let gross = 2 * STAKE;
+    let receipt = suite.collect_winnings(&player).unwrap();
+    assert_eq!(receipt.gross, Uint128::new(gross));
+    assert_eq!(
+        suite.balance(&player),
+        INITIAL_BALANCE - STAKE + gross - fee(gross)
+    );
+}
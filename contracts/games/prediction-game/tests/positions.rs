@@ -0,0 +1,140 @@
+mod common;
+
+use common::*;
+use cosmwasm_std::Addr;
+use cw_utils::Expiration;
+use prediction::positions::{OwnerOfResponse, TokensResponse};
+use prediction::prediction_game::msg::{ExecuteMsg, QueryMsg};
+use prediction::prediction_game::Direction;
+use prediction_game::error::ContractError;
+
+const STAKE: u128 = 10_000;
+
+/// Game minting a position for every new bet
+fn setup() -> Suite {
+    Suite::new(|_, config| config.position_nfts = true)
+}
+
+fn token_of(suite: &Suite, owner: &Addr) -> String {
+    let tokens: TokensResponse = suite.query(&QueryMsg::Tokens {
+        owner: owner.to_string(),
+        start_after: None,
+        limit: None,
+    });
+    tokens.tokens[0].clone()
+}
+
+fn owner_of(suite: &Suite, token_id: &str) -> String {
+    let resp: OwnerOfResponse = suite.query(&QueryMsg::OwnerOf {
+        token_id: token_id.to_string(),
+        include_expired: None,
+    });
+    resp.owner
+}
+
+#[test]
+fn holder_of_a_transferred_position_is_paid() {
+    let mut suite = setup();
+    let player = suite.account("player");
+    let buyer = suite.account("buyer");
+    let loser = suite.account("loser");
+
+    suite.bet(&player, Direction::Bull, STAKE).unwrap();
+    suite.bet(&loser, Direction::Bear, STAKE).unwrap();
+    let round_id = suite.bidding_round();
+    let token_id = token_of(&suite, &player);
+    let msg = ExecuteMsg::TransferNft {
+        recipient: buyer.to_string(),
+        token_id: token_id.clone(),
+    };
+    suite.execute(&player, &msg, &[]).unwrap();
+    assert_eq!(owner_of(&suite, &token_id), buyer.to_string());
+
+    // The bettor can't add to a position it sold
+    let err = suite.bet(&player, Direction::Bull, STAKE).unwrap_err();
+    assert_eq!(
+        game_error(err),
+        ContractError::PositionTransferred {
+            token_id: token_id.clone()
+        }
+    );
+    let claim = ExecuteMsg::ClaimPosition {
+        token_id: token_id.clone(),
+    };
+    let err = suite.execute(&buyer, &claim, &[]).unwrap_err();
+    assert_eq!(
+        game_error(err),
+        ContractError::PositionPending {
+            token_id: token_id.clone()
+        }
+    );
+
+    suite.play_round(START_PRICE + 1);
+    // The bet is claimed through its position only
+    let err = suite.collect_round(&player, round_id).unwrap_err();
+    assert_eq!(
+        game_error(err),
+        ContractError::PositionTokenized {
+            token_id: token_id.clone()
+        }
+    );
+    assert!(suite.execute(&player, &claim, &[]).is_err());
+    suite.execute(&buyer, &claim, &[]).unwrap();
+
+    let gross = 2 * STAKE;
+    assert_eq!(suite.balance(&player), INITIAL_BALANCE - STAKE);
+    assert_eq!(suite.balance(&buyer), INITIAL_BALANCE + gross - fee(gross));
+    // The token is burnt with the claim
+    assert!(suite.execute(&buyer, &claim, &[]).is_err());
+}
+
+#[test]
+fn approved_spender_claims_for_the_holder() {
+    let mut suite = setup();
+    let player = suite.account("player");
+    let spender = suite.account("spender");
+    let loser = suite.account("loser");
+
+    suite.bet(&player, Direction::Bull, STAKE).unwrap();
+    suite.bet(&loser, Direction::Bear, STAKE).unwrap();
+    let token_id = token_of(&suite, &player);
+    let claim = ExecuteMsg::ClaimPosition {
+        token_id: token_id.clone(),
+    };
+    suite.play_round(START_PRICE + 1);
+
+    // Approvals expire, revoked ones can't be used either
+    let height = suite.app.block_info().height;
+    let approve = |expires| ExecuteMsg::Approve {
+        spender: spender.to_string(),
+        token_id: token_id.clone(),
+        expires,
+    };
+    suite
+        .execute(
+            &player,
+            &approve(Some(Expiration::AtHeight(height + 1))),
+            &[],
+        )
+        .unwrap();
+    suite.app.update_block(|block| block.height += 1);
+    assert!(suite.execute(&spender, &claim, &[]).is_err());
+    suite.execute(&player, &approve(None), &[]).unwrap();
+    let revoke = ExecuteMsg::Revoke {
+        spender: spender.to_string(),
+        token_id: token_id.clone(),
+    };
+    suite.execute(&player, &revoke, &[]).unwrap();
+    assert!(suite.execute(&spender, &claim, &[]).is_err());
+
+    suite.execute(&player, &approve(None), &[]).unwrap();
+    suite.execute(&spender, &claim, &[]).unwrap();
+    // The winnings go to the holder, not to the spender
+    let gross = 2 * STAKE;
+    assert_eq!(
+        suite.balance(&player),
+        INITIAL_BALANCE - STAKE + gross - fee(gross)
+    );
+    assert_eq!(suite.balance(&spender), INITIAL_BALANCE);
+    assert_eq!(suite.balance(&suite.game.clone()), 0);
+}
@@ -0,0 +1,139 @@
+mod common;
+
+use common::*;
+use cosmwasm_std::{coins, Addr, Decimal, Uint128};
+use cw_multi_test::error::AnyResult;
+use cw_multi_test::{AppResponse, ContractWrapper, Executor};
+use general::router::SwapOperation;
+use mock_router::msg::InstantiateMsg as RouterInstantiateMsg;
+use prediction::prediction_game::msg::ExecuteMsg;
+use prediction::prediction_game::Direction;
+use prediction_game::error::ContractError;
+
+const OFFER_DENOM: &str = "uatom";
+const OFFER: u128 = 5_000;
+/// Collateral received for OFFER at a rate of 2
+const STAKE: u128 = 10_000;
+
+/// Game swapping through a router paying 2 DENOM per OFFER_DENOM, returns the funded router
+fn setup() -> (Suite, Addr) {
+    let mut router = Addr::unchecked("");
+    let mut suite = Suite::new(|suite, config| {
+        let code = suite
+            .app
+            .store_code(Box::new(ContractWrapper::new_with_empty(
+                mock_router::contract::execute,
+                mock_router::contract::instantiate,
+                mock_router::contract::query,
+            )));
+        let msg = RouterInstantiateMsg {
+            rate: Decimal::from_ratio(2u128, 1u128),
+        };
+        router = suite
+            .app
+            .instantiate_contract(code, suite.owner.clone(), &msg, &[], "router", None)
+            .unwrap();
+        config.swap_router = Some(router.clone());
+    });
+    fund(&mut suite, &router, INITIAL_BALANCE, DENOM);
+    (suite, router)
+}
+
+/// Address holding `amount` of `denom`
+fn fund(suite: &mut Suite, address: &Addr, amount: u128, denom: &str) {
+    suite
+        .app
+        .init_modules(|router, _, storage| {
+            router
+                .bank
+                .init_balance(storage, address, coins(amount, denom))
+        })
+        .unwrap();
+}
+
+/// Address holding INITIAL_BALANCE of OFFER_DENOM
+fn swapper(suite: &mut Suite, name: &str) -> Addr {
+    let address = suite.app.api().addr_make(name);
+    fund(suite, &address, INITIAL_BALANCE, OFFER_DENOM);
+    address
+}
+
+fn offer_balance(suite: &Suite, address: &Addr) -> u128 {
+    suite
+        .app
+        .wrap()
+        .query_balance(address, OFFER_DENOM)
+        .unwrap()
+        .amount
+        .u128()
+}
+
+fn swap_and_bet(
+    suite: &mut Suite,
+    player: &Addr,
+    ask_denom: &str,
+    minimum_receive: Option<u128>,
+    offer: u128,
+) -> AnyResult<AppResponse> {
+    let msg = ExecuteMsg::SwapAndBet {
+        round_id: suite.bidding_round(),
+        direction: Direction::Bull,
+        collateral: DENOM.to_string(),
+        operations: vec![SwapOperation::NativeSwap {
+            offer_denom: OFFER_DENOM.to_string(),
+            ask_denom: ask_denom.to_string(),
+        }],
+        minimum_receive: minimum_receive.map(Uint128::new),
+        max_spread: None,
+    };
+    suite.execute(player, &msg, &coins(offer, OFFER_DENOM))
+}
+
+#[test]
+fn swapped_collateral_is_bet_for_the_player() {
+    let (mut suite, router) = setup();
+    let player = swapper(&mut suite, "player");
+    let loser = suite.account("loser");
+
+    swap_and_bet(&mut suite, &player, DENOM, Some(STAKE), OFFER).unwrap();
+    assert_eq!(offer_balance(&suite, &player), INITIAL_BALANCE - OFFER);
+    assert_eq!(offer_balance(&suite, &router), OFFER);
+    assert_eq!(suite.balance(&suite.game.clone()), STAKE);
+
+    suite.bet(&loser, Direction::Bear, STAKE).unwrap();
+    suite.play_round(START_PRICE + 1);
+    let gross = 2 * STAKE;
+    let receipt = suite.collect_winnings(&player).unwrap();
+    assert_eq!(receipt.gross, Uint128::new(gross));
+    assert_eq!(suite.balance(&player), gross - fee(gross));
+}
+
+#[test]
+fn failed_swaps_leave_the_offer_with_the_player() {
+    let (mut suite, router) = setup();
+    let player = swapper(&mut suite, "player");
+
+    // The route must end in the collateral
+    let err = swap_and_bet(&mut suite, &player, "uosmo", None, OFFER).unwrap_err();
+    assert_eq!(game_error(err), ContractError::InvalidSwapRoute {});
+
+    // Too little output and bets below the minimum revert the swap too
+    assert!(swap_and_bet(&mut suite, &player, DENOM, Some(STAKE + 1), OFFER).is_err());
+    let err = swap_and_bet(&mut suite, &player, DENOM, None, MINIMUM_BET / 2 - 1).unwrap_err();
+    assert_eq!(game_error(err), ContractError::BetUnderMinBetAmount {});
+
+    assert_eq!(offer_balance(&suite, &player), INITIAL_BALANCE);
+    assert_eq!(offer_balance(&suite, &router), 0);
+    assert_eq!(suite.balance(&router), INITIAL_BALANCE);
+    assert_eq!(suite.balance(&suite.game.clone()), 0);
+}
+
+#[test]
+fn swaps_need_a_router() {
+    let mut suite = Suite::new(|_, _| {});
+    let player = swapper(&mut suite, "player");
+
+    let err = swap_and_bet(&mut suite, &player, DENOM, None, OFFER).unwrap_err();
+    assert_eq!(game_error(err), ContractError::SwapRouterNotSet {});
+    assert_eq!(offer_balance(&suite, &player), INITIAL_BALANCE);
+}
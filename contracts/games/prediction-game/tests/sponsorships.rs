@@ -0,0 +1,99 @@
+mod common;
+
+use common::*;
+use cosmwasm_std::{coins, Addr, Uint128};
+use prediction::prediction_game::msg::ExecuteMsg;
+use prediction::prediction_game::Direction;
+
+const STAKE: u128 = 10_000;
+const BOOST: u128 = 1_000;
+
+/// Sponsors the bidding round with BOOST
+fn sponsor_next_round(suite: &mut Suite, sponsor: &Addr) {
+    let round_id = suite.bidding_round();
+    let msg = ExecuteMsg::Sponsor {
+        start_round: round_id,
+        end_round: round_id,
+        denom: None,
+        amount_per_round: Uint128::new(BOOST),
+    };
+    suite.execute(sponsor, &msg, &coins(BOOST, DENOM)).unwrap();
+}
+
+#[test]
+fn winners_share_the_boost_on_top_of_the_pool() {
+    let mut suite = Suite::new(|_, _| {});
+    let sponsor = suite.account("sponsor");
+    let player = suite.account("player");
+    let loser = suite.account("loser");
+
+    sponsor_next_round(&mut suite, &sponsor);
+    suite.bet(&player, Direction::Bear, STAKE).unwrap();
+    suite.bet(&loser, Direction::Bull, STAKE).unwrap();
+    let round_id = suite.play_round(START_PRICE - 1);
+
+    let round = suite.finished_round(round_id);
+    assert_eq!(round.pools[0].boost, Uint128::new(BOOST));
+    assert!(suite.status().sponsorships.is_empty());
+    assert_eq!(suite.balance(&sponsor), INITIAL_BALANCE - BOOST);
+
+    let gross = 2 * STAKE + BOOST;
+    let receipt = suite.collect_winnings(&player).unwrap();
+    assert_eq!(receipt.gross, Uint128::new(gross));
+    assert_eq!(receipt.fee, Uint128::new(fee(gross)));
+    assert_eq!(
+        suite.balance(&player),
+        INITIAL_BALANCE - STAKE + gross - fee(gross)
+    );
+    assert_eq!(suite.balance(&suite.dev_wallet.clone()), fee(gross));
+    assert_eq!(suite.balance(&suite.game.clone()), 0);
+}
+
+#[test]
+fn one_sided_round_refunds_the_sponsor_and_the_stakes() {
+    let mut suite = Suite::new(|_, _| {});
+    let sponsor = suite.account("sponsor");
+    let player = suite.account("player");
+
+    sponsor_next_round(&mut suite, &sponsor);
+    suite.bet(&player, Direction::Bull, STAKE).unwrap();
+    let round_id = suite.play_round(START_PRICE + 1);
+
+    // Nobody can win a round without bets on the other side, the boost goes back at once
+    let round = suite.finished_round(round_id);
+    assert_eq!(round.pools[0].boost, Uint128::zero());
+    assert_eq!(suite.balance(&sponsor), INITIAL_BALANCE);
+
+    let receipt = suite.collect_round(&player, round_id).unwrap();
+    assert_eq!(receipt.gross, Uint128::new(STAKE));
+    assert_eq!(receipt.fee, Uint128::zero());
+    assert_eq!(suite.balance(&player), INITIAL_BALANCE);
+    assert_eq!(suite.balance(&suite.game.clone()), 0);
+}
+
+#[test]
+fn round_without_winner_refunds_the_sponsor() {
+    let mut suite = Suite::new(|_, _| {});
+    let sponsor = suite.account("sponsor");
+    let bull = suite.account("bull");
+    let bear = suite.account("bear");
+
+    sponsor_next_round(&mut suite, &sponsor);
+    suite.bet(&bull, Direction::Bull, STAKE).unwrap();
+    suite.bet(&bear, Direction::Bear, STAKE).unwrap();
+    // The price didn't move, nobody won the boost
+    let round_id = suite.play_round(START_PRICE);
+
+    let round = suite.finished_round(round_id);
+    assert_eq!(round.winner, None);
+    assert_eq!(round.pools[0].boost, Uint128::zero());
+    assert_eq!(suite.balance(&sponsor), INITIAL_BALANCE);
+
+    // Both players get their stake back, as in any round without a winner
+    for player in [&bull, &bear] {
+        let receipt = suite.collect_round(player, round_id).unwrap();
+        assert_eq!(receipt.gross, Uint128::new(STAKE));
+        assert_eq!(suite.balance(player), INITIAL_BALANCE - fee(STAKE));
+    }
+    assert_eq!(suite.balance(&suite.game.clone()), 0);
+}
@@ -0,0 +1,151 @@
+mod common;
+
+use common::*;
+use cosmwasm_std::Addr;
+use general::timelock::{PendingChangesResponse, TimelockError};
+use prediction::prediction_game::msg::{ExecuteMsg, QueryMsg};
+use prediction::prediction_game::{Config, ConfigChange, ConfigUpdate};
+use prediction_game::error::ContractError;
+
+const DELAY: u64 = 3_600;
+
+/// Game whose sensitive changes wait for DELAY
+fn setup() -> Suite {
+    let mut suite = Suite::new(|_, _| {});
+    let owner = suite.owner.clone();
+    suite
+        .execute(
+            &owner,
+            &ExecuteMsg::UpdateTimelockDelay { seconds: DELAY },
+            &[],
+        )
+        .unwrap();
+    suite
+}
+
+fn config(suite: &Suite) -> Config {
+    suite.query(&QueryMsg::Config {})
+}
+
+fn pending_changes(suite: &Suite) -> PendingChangesResponse<ConfigChange> {
+    suite.query(&QueryMsg::PendingChanges {
+        start_after: None,
+        limit: None,
+    })
+}
+
+fn wait(suite: &mut Suite, seconds: u64) {
+    suite.app.update_block(|block| {
+        block.time = block.time.plus_seconds(seconds);
+        block.height += 1;
+    });
+}
+
+/// Moves the users contract to a new address and turns hedging on in a single update
+fn update_users_and_hedging(suite: &mut Suite) -> Addr {
+    let users = suite.app.api().addr_make("new_users");
+    let owner = suite.owner.clone();
+    let msg = ExecuteMsg::UpdateConfig {
+        config: ConfigUpdate {
+            users_contract: Some(users.clone()),
+            hedging: Some(true),
+            ..ConfigUpdate::default()
+        },
+    };
+    suite.execute(&owner, &msg, &[]).unwrap();
+    users
+}
+
+#[test]
+fn users_contract_waits_for_the_delay() {
+    let mut suite = setup();
+    let old_users = suite.users.clone();
+    let new_users = update_users_and_hedging(&mut suite);
+
+    // Hedging applies at once, the users contract is queued
+    assert!(config(&suite).hedging);
+    assert_eq!(config(&suite).users_contract, old_users);
+    let pending = pending_changes(&suite);
+    assert_eq!(pending.delay, DELAY);
+    assert_eq!(pending.changes.len(), 1);
+    let change = &pending.changes[0];
+    assert_eq!(
+        change.change,
+        ConfigChange::Config {
+            config: ConfigUpdate {
+                users_contract: Some(new_users.clone()),
+                ..ConfigUpdate::default()
+            }
+        }
+    );
+
+    // Anyone can execute it, once the delay has passed
+    let player = suite.account("player");
+    let msg = ExecuteMsg::ExecuteChange { id: change.id };
+    wait(&mut suite, DELAY - 1);
+    let err = suite.execute(&player, &msg, &[]).unwrap_err();
+    assert_eq!(
+        game_error(err),
+        ContractError::Timelock(TimelockError::ChangeNotReady {
+            id: change.id,
+            executable_at: change.executable_at,
+        })
+    );
+    wait(&mut suite, 1);
+    suite.execute(&player, &msg, &[]).unwrap();
+    assert_eq!(config(&suite).users_contract, new_users);
+    assert!(pending_changes(&suite).changes.is_empty());
+}
+
+#[test]
+fn owner_cancels_queued_changes() {
+    let mut suite = setup();
+    let old_users = suite.users.clone();
+    update_users_and_hedging(&mut suite);
+    let id = pending_changes(&suite).changes[0].id;
+
+    let player = suite.account("player");
+    let cancel = ExecuteMsg::CancelChange { id };
+    assert!(suite.execute(&player, &cancel, &[]).is_err());
+    let owner = suite.owner.clone();
+    suite.execute(&owner, &cancel, &[]).unwrap();
+    assert!(pending_changes(&suite).changes.is_empty());
+
+    wait(&mut suite, DELAY);
+    let err = suite
+        .execute(&player, &ExecuteMsg::ExecuteChange { id }, &[])
+        .unwrap_err();
+    assert_eq!(
+        game_error(err),
+        ContractError::Timelock(TimelockError::ChangeNotFound { id })
+    );
+    assert_eq!(config(&suite).users_contract, old_users);
+}
+
+#[test]
+fn decreasing_the_delay_is_queued() {
+    let mut suite = setup();
+    let owner = suite.owner.clone();
+
+    suite
+        .execute(&owner, &ExecuteMsg::UpdateTimelockDelay { seconds: 0 }, &[])
+        .unwrap();
+    let pending = pending_changes(&suite);
+    assert_eq!(pending.delay, DELAY);
+    assert_eq!(
+        pending.changes[0].change,
+        ConfigChange::TimelockDelay { seconds: 0 }
+    );
+
+    wait(&mut suite, DELAY);
+    let msg = ExecuteMsg::ExecuteChange {
+        id: pending.changes[0].id,
+    };
+    suite.execute(&owner, &msg, &[]).unwrap();
+    assert_eq!(pending_changes(&suite).delay, 0);
+
+    // Without a delay every field applies at once
+    let new_users = update_users_and_hedging(&mut suite);
+    assert_eq!(config(&suite).users_contract, new_users);
+    assert!(pending_changes(&suite).changes.is_empty());
+}
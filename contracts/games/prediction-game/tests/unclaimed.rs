@@ -0,0 +1,272 @@
+mod common;
+
+use common::*;
+use cosmwasm_std::{from_json, Addr, Uint128};
+use prediction::prediction_game::msg::{ExecuteMsg, QueryMsg};
+use prediction::prediction_game::{
+    Direction, MyGameResponse, PendingRefundableAmountResponse,
+    PendingRefundableAmountRoundsResponse, PendingRewardResponse, PendingRewardRoundsResponse,
+};
+use prediction_game::state::UNSETTLED_ROUNDS;
+
+const STAKE: u128 = 10_000;
+
+fn pending_reward(suite: &Suite, player: &Addr) -> u128 {
+    let resp: PendingRewardResponse = suite.query(&QueryMsg::MyPendingReward {
+        player: player.clone(),
+        collateral: DENOM.to_string(),
+    });
+    resp.pending_reward.u128()
+}
+
+fn refundable_amount(suite: &Suite, player: &Addr) -> u128 {
+    let resp: PendingRefundableAmountResponse = suite.query(&QueryMsg::MyRefundableAmount {
+        player: player.clone(),
+        collateral: DENOM.to_string(),
+    });
+    resp.pending_refundable_amount.u128()
+}
+
+fn reward_rounds(
+    suite: &Suite,
+    player: &Addr,
+    start_after: Option<Uint128>,
+    limit: Option<u32>,
+) -> PendingRewardRoundsResponse {
+    suite.query(&QueryMsg::MyPendingRewardRounds {
+        player: player.clone(),
+        collateral: DENOM.to_string(),
+        start_after,
+        limit,
+    })
+}
+
+/// What is left to claim computed from every bet of the player, as the queries did before the
+/// index
+fn full_scan(suite: &Suite, player: &Addr) -> u128 {
+    let games: MyGameResponse = suite.query(&QueryMsg::MyGameList {
+        player: player.clone(),
+        start_after: None,
+        limit: None,
+    });
+    games
+        .my_game_list
+        .iter()
+        .map(|bet| {
+            let resp: PendingRewardResponse = suite.query(&QueryMsg::MyPendingRewardRound {
+                round_id: bet.round_id,
+                player: player.clone(),
+            });
+            resp.pending_reward.u128()
+        })
+        .sum()
+}
+
+/// Rounds of the player not moved to the index yet
+fn unsettled_rounds(suite: &Suite, player: &Addr) -> Vec<u128> {
+    let key = UNSETTLED_ROUNDS.key(player.clone());
+    suite
+        .app
+        .wrap()
+        .query_wasm_raw(&suite.game, key.to_vec())
+        .unwrap()
+        .map(|value| from_json(value).unwrap())
+        .unwrap_or_default()
+}
+
+#[test]
+fn pending_rewards_are_indexed_until_claimed() {
+    let mut suite = Suite::new(|_, _| {});
+    let player = suite.account("player");
+    let loser = suite.account("loser");
+
+    let mut rounds = vec![];
+    for stake in [STAKE, 2 * STAKE] {
+        suite.bet(&player, Direction::Bull, stake).unwrap();
+        suite.bet(&loser, Direction::Bear, STAKE).unwrap();
+        rounds.push(suite.play_round(START_PRICE + 1));
+        suite.set_price(START_PRICE);
+    }
+    // Nothing is pending while the round is running
+    suite.bet(&player, Direction::Bull, STAKE).unwrap();
+
+    let first = 2 * STAKE;
+    let second = 3 * STAKE;
+    assert_eq!(pending_reward(&suite, &player), first + second);
+    assert_eq!(refundable_amount(&suite, &player), 0);
+    assert_eq!(pending_reward(&suite, &loser), 0);
+    let resp = reward_rounds(&suite, &player, None, None);
+    assert_eq!(
+        resp.pending_reward_rounds,
+        vec![
+            (rounds[0], Uint128::new(first)),
+            (rounds[1], Uint128::new(second))
+        ]
+    );
+    assert_eq!(resp.pending_reward_total, Uint128::new(first + second));
+
+    let receipt = suite.collect_round(&player, rounds[0]).unwrap();
+    assert_eq!(receipt.gross, Uint128::new(first));
+    assert_eq!(pending_reward(&suite, &player), second);
+
+    let receipt = suite.collect_winnings(&player).unwrap();
+    assert_eq!(receipt.gross, Uint128::new(second));
+    assert_eq!(pending_reward(&suite, &player), 0);
+    assert_eq!(
+        suite.balance(&player),
+        INITIAL_BALANCE - 4 * STAKE + first + second - fee(first) - fee(second)
+    );
+}
+
+#[test]
+fn refundable_amounts_are_indexed_until_claimed() {
+    let mut suite = Suite::new(|_, _| {});
+    let player = suite.account("player");
+
+    // Nobody else bet, the stake is refunded whatever the price
+    suite.bet(&player, Direction::Bull, STAKE).unwrap();
+    let round_id = suite.play_round(START_PRICE + 1);
+
+    assert_eq!(refundable_amount(&suite, &player), STAKE);
+    assert_eq!(pending_reward(&suite, &player), 0);
+    let resp: PendingRefundableAmountRoundsResponse =
+        suite.query(&QueryMsg::MyRefundableAmountRounds {
+            player: player.clone(),
+            collateral: DENOM.to_string(),
+            start_after: None,
+            limit: None,
+        });
+    assert_eq!(
+        resp.pending_refundable_amount_rounds,
+        vec![(round_id, Uint128::new(STAKE))]
+    );
+    assert_eq!(resp.pending_refundable_amount_total, Uint128::new(STAKE));
+
+    let receipt = suite.collect_winnings(&player).unwrap();
+    assert_eq!(receipt.gross, Uint128::new(STAKE));
+    assert_eq!(receipt.fee, Uint128::zero());
+    assert_eq!(refundable_amount(&suite, &player), 0);
+    assert_eq!(suite.balance(&player), INITIAL_BALANCE);
+}
+
+#[test]
+fn reward_rounds_are_paged() {
+    let mut suite = Suite::new(|_, _| {});
+    let player = suite.account("player");
+    let loser = suite.account("loser");
+
+    let mut rounds = vec![];
+    for _ in 0..3 {
+        suite.bet(&player, Direction::Bull, STAKE).unwrap();
+        suite.bet(&loser, Direction::Bear, STAKE).unwrap();
+        rounds.push(suite.play_round(START_PRICE + 1));
+        suite.set_price(START_PRICE);
+    }
+    // A refund in between is left out of the reward pages
+    suite.bet(&player, Direction::Bull, STAKE).unwrap();
+    suite.play_round(START_PRICE + 1);
+
+    let reward = Uint128::new(2 * STAKE);
+    let first = reward_rounds(&suite, &player, None, Some(2));
+    assert_eq!(
+        first.pending_reward_rounds,
+        vec![(rounds[0], reward), (rounds[1], reward)]
+    );
+    // The total counts the rounds of every page
+    assert_eq!(first.pending_reward_total, reward * Uint128::new(3));
+    let second = reward_rounds(&suite, &player, Some(rounds[1]), Some(2));
+    assert_eq!(second.pending_reward_rounds, vec![(rounds[2], reward)]);
+    let last = reward_rounds(&suite, &player, Some(rounds[2]), Some(2));
+    assert!(last.pending_reward_rounds.is_empty());
+}
+
+#[test]
+fn unsettled_rounds_stay_within_the_live_and_bidding_rounds() {
+    let mut suite = Suite::new(|_, _| {});
+    let player = suite.account("player");
+    let other = suite.account("other");
+
+    // Betting every round without claiming, the finished rounds move to the index at each bet
+    for _ in 0..5 {
+        suite.bet(&player, Direction::Bull, STAKE).unwrap();
+        suite.bet(&other, Direction::Bear, STAKE).unwrap();
+        assert!(unsettled_rounds(&suite, &player).len() <= 2);
+        suite.next_round().unwrap();
+    }
+    suite.next_round().unwrap();
+    suite.next_round().unwrap();
+
+    // The queries scan those 2 rounds besides the index, until the next bet moves them
+    assert_eq!(unsettled_rounds(&suite, &player).len(), 2);
+    assert_eq!(
+        pending_reward(&suite, &player) + refundable_amount(&suite, &player),
+        full_scan(&suite, &player)
+    );
+    suite.bet(&player, Direction::Bull, STAKE).unwrap();
+    assert_eq!(
+        unsettled_rounds(&suite, &player),
+        vec![suite.bidding_round().u128()]
+    );
+}
+
+#[test]
+fn index_matches_a_full_scan_after_claims_and_compaction() {
+    let mut suite = Suite::new(|_, config| config.round_retention = 1);
+    let first = suite.account("first");
+    let second = suite.account("second");
+    let third = suite.account("third");
+    let players = [&first, &second, &third];
+
+    // Won by first
+    suite.bet(&first, Direction::Bull, STAKE).unwrap();
+    suite.bet(&second, Direction::Bear, STAKE).unwrap();
+    let won = suite.play_round(START_PRICE + 1);
+    suite.set_price(START_PRICE);
+    // Refunded to first, nobody else bet
+    suite.bet(&first, Direction::Bull, STAKE).unwrap();
+    suite.play_round(START_PRICE - 1);
+    suite.set_price(START_PRICE);
+    // The price didn't move, every stake is paid back
+    suite.bet(&first, Direction::Bear, STAKE).unwrap();
+    suite.bet(&second, Direction::Bear, 2 * STAKE).unwrap();
+    suite.bet(&third, Direction::Bull, STAKE).unwrap();
+    let flat = suite.play_round(START_PRICE);
+    // Won by third
+    suite.bet(&first, Direction::Bull, STAKE).unwrap();
+    suite.bet(&third, Direction::Bear, 3 * STAKE).unwrap();
+    suite.play_round(START_PRICE - 1);
+    suite.set_price(START_PRICE);
+
+    suite.collect_round(&first, won).unwrap();
+    suite.collect_round(&second, flat).unwrap();
+    let owner = suite.owner.clone();
+    suite
+        .execute(&owner, &ExecuteMsg::CompactRounds { limit: None }, &[])
+        .unwrap();
+    // A bet still running is left out of both
+    suite.bet(&third, Direction::Bull, STAKE).unwrap();
+
+    for player in players {
+        let indexed = pending_reward(&suite, player) + refundable_amount(&suite, player);
+        assert_eq!(indexed, full_scan(&suite, player));
+        let rounds = reward_rounds(&suite, player, None, None);
+        let paged: u128 = rounds
+            .pending_reward_rounds
+            .iter()
+            .map(|(_, r)| r.u128())
+            .sum();
+        assert_eq!(paged, pending_reward(&suite, player));
+    }
+    assert_eq!(pending_reward(&suite, &first), STAKE);
+    assert_eq!(refundable_amount(&suite, &first), STAKE);
+    assert_eq!(pending_reward(&suite, &second), 0);
+    assert_eq!(pending_reward(&suite, &third), STAKE + 4 * STAKE);
+
+    for player in [&first, &third] {
+        suite.collect_winnings(player).unwrap();
+        assert_eq!(
+            pending_reward(&suite, player) + refundable_amount(&suite, player),
+            0
+        );
+    }
+}
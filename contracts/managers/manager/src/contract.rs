@@ -1,21 +1,27 @@
 use cosmwasm_std::{
     entry_point, instantiate2_address, to_json_binary, Addr, Binary, Decimal, Deps, DepsMut, Empty,
-    Env, MessageInfo, Order, Response, StdError, StdResult, Uint128, WasmMsg,
+    Env, MessageInfo, Order, Response, StdResult, Uint128, WasmMsg,
 };
-use cw2::{get_contract_version, set_contract_version};
-use cw_ownable::{assert_owner, get_ownership, initialize_owner};
+use cw2::set_contract_version;
+use cw_ownable::{get_ownership, initialize_owner};
 use cw_storage_plus::Bound;
-use prediction::prediction_game::{DenomTicker, WalletInfo};
+use general::events::{attr, patch_field, VeloAction, VeloContract, VeloEvent};
+use general::migration::{self, MigrationStep};
+use general::rbac::{self, assert_role, Role};
+use prediction::prediction_game::{Collateral, DenomTicker, WalletInfo};
 
 use crate::{
     error::ContractError,
-    msg::{ExecuteMsg, GameInfo, InstantiateMsg, MigrateMsg, QueryMsg},
+    msg::{ConfigUpdate, ExecuteMsg, GameInfo, InstantiateMsg, MigrateMsg, QueryMsg},
     state::{Config, CONFIG, GAMES},
 };
 
 const CONTRACT_NAME: &str = env!("CARGO_PKG_NAME");
 const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
 
+// State rewrites run by migrate, by the version that introduced them
+const MIGRATIONS: &[MigrationStep<MigrateMsg>] = &[];
+
 const DEFAULT_MAX_LIMIT: u32 = 1000;
 
 #[cfg_attr(not(feature = "library"), entry_point)]
@@ -53,14 +59,7 @@ pub fn instantiate(
         Some(&info.sender.clone().into_string()),
     )?;
 
-    let mut total_ratio = Decimal::zero();
-    for dev_wallet in msg.dev_wallet_list.iter() {
-        total_ratio += dev_wallet.ratio;
-    }
-
-    if total_ratio != Decimal::one() {
-        return Err(ContractError::WrongRatio {});
-    }
+    validate_dev_wallets(&msg.dev_wallet_list)?;
 
     let canonical_creator = deps.api.addr_canonicalize(env.contract.address.as_str())?;
     let code_info_response = deps.querier.query_wasm_code_info(msg.users_code_id)?;
@@ -85,7 +84,9 @@ pub fn instantiate(
         code_id: msg.users_code_id,
         msg: to_json_binary(&general::users::InstantiateMsg {
             config: msg.users_config,
-            extra_admins: Some(vec![info.sender.clone()]),
+            // The manager keeps the roles it needs to register games
+            owner: Some(info.sender.clone()),
+            extra_admins: Some(vec![env.contract.address.clone()]),
         })?,
         funds: vec![],
         admin: Some(info.sender.to_string()),
@@ -93,12 +94,12 @@ pub fn instantiate(
         salt: Binary::from(salt),
     };
 
-    Ok(Response::new()
-        .add_message(wasm_msg)
-        .add_attribute("action", "instantiate")
-        .add_attribute("contract_name", CONTRACT_NAME)
-        .add_attribute("contract_version", CONTRACT_VERSION)
-        .add_attribute("owner", info.sender))
+    Ok(Response::new().add_message(wasm_msg).add_event(
+        velo_event(VeloAction::Instantiate)
+            .add_attribute(attr::CONTRACT_NAME, CONTRACT_NAME)
+            .add_attribute(attr::CONTRACT_VERSION, CONTRACT_VERSION)
+            .add_attribute(attr::OWNER, info.sender),
+    ))
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
@@ -110,13 +111,13 @@ pub fn execute(
 ) -> Result<Response, ContractError> {
     match msg {
         ExecuteMsg::UpdateOwnership(action) => update_ownership(deps, env, info, action),
+        ExecuteMsg::GrantRole { role, address } => grant_role(deps, info, role, address),
+        ExecuteMsg::RevokeRole { role, address } => revoke_role(deps, info, role, address),
+        ExecuteMsg::UpdateConfig { config } => update_config(deps, info, config),
         ExecuteMsg::CreateGame {
             next_round_seconds,
-            minimum_bet,
-            gaming_fee,
-            token_denom,
-            exp_per_denom_bet,
-            exp_per_denom_won,
+            collaterals,
+            elo_k_factor,
             denom_tickers,
             label,
         } => create_game(
@@ -124,11 +125,8 @@ pub fn execute(
             env,
             info,
             next_round_seconds,
-            minimum_bet,
-            gaming_fee,
-            token_denom,
-            exp_per_denom_bet,
-            exp_per_denom_won,
+            collaterals,
+            elo_k_factor,
             denom_tickers,
             label,
         ),
@@ -171,7 +169,95 @@ fn update_ownership(
     action: cw_ownable::Action,
 ) -> Result<Response, ContractError> {
     let ownership = cw_ownable::update_ownership(deps, &env.block, &info.sender, action)?;
-    Ok(Response::new().add_attributes(ownership.into_attributes()))
+    Ok(Response::new().add_event(
+        velo_event(VeloAction::UpdateOwnership).add_attributes(ownership.into_attributes()),
+    ))
+}
+
+fn grant_role(
+    deps: DepsMut,
+    info: MessageInfo,
+    role: Role,
+    address: String,
+) -> Result<Response, ContractError> {
+    let member = rbac::grant_role(deps.storage, deps.api, &info.sender, role, &address)?;
+    Ok(Response::new().add_event(
+        velo_event(VeloAction::GrantRole)
+            .add_attribute(attr::ROLE, role.as_str())
+            .add_attribute(attr::MEMBER, member),
+    ))
+}
+
+fn revoke_role(
+    deps: DepsMut,
+    info: MessageInfo,
+    role: Role,
+    address: String,
+) -> Result<Response, ContractError> {
+    let member = rbac::revoke_role(deps.storage, deps.api, &info.sender, role, &address)?;
+    Ok(Response::new().add_event(
+        velo_event(VeloAction::RevokeRole)
+            .add_attribute(attr::ROLE, role.as_str())
+            .add_attribute(attr::MEMBER, member),
+    ))
+}
+
+fn update_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    update: ConfigUpdate,
+) -> Result<Response, ContractError> {
+    assert_role(deps.storage, Role::ConfigAdmin, &info.sender)?;
+    let mut config = CONFIG.load(deps.storage)?;
+
+    let mut attributes = vec![];
+    patch_field(
+        &mut attributes,
+        "users_code_id",
+        &mut config.users_code_id,
+        update.users_code_id,
+    )?;
+    patch_field(
+        &mut attributes,
+        "users_contract",
+        &mut config.users_contract,
+        update.users_contract,
+    )?;
+    patch_field(
+        &mut attributes,
+        "games_code_id",
+        &mut config.games_code_id,
+        update.games_code_id,
+    )?;
+    patch_field(
+        &mut attributes,
+        "dev_wallet_list",
+        &mut config.dev_wallet_list,
+        update.dev_wallet_list,
+    )?;
+
+    // Code ids must be stored on chain
+    deps.querier.query_wasm_code_info(config.users_code_id)?;
+    deps.querier.query_wasm_code_info(config.games_code_id)?;
+    deps.api.addr_validate(config.users_contract.as_str())?;
+    validate_dev_wallets(&config.dev_wallet_list)?;
+
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_event(velo_event(VeloAction::UpdateConfig).add_attributes(attributes)))
+}
+
+fn validate_dev_wallets(wallets: &[WalletInfo]) -> Result<(), ContractError> {
+    let mut total_ratio = Decimal::zero();
+    for dev_wallet in wallets.iter() {
+        total_ratio += dev_wallet.ratio;
+    }
+
+    if total_ratio != Decimal::one() {
+        return Err(ContractError::WrongRatio {});
+    }
+
+    Ok(())
 }
 
 #[allow(clippy::too_many_arguments)]
@@ -180,15 +266,12 @@ fn create_game(
     env: Env,
     info: MessageInfo,
     next_round_seconds: Uint128,
-    minimum_bet: Uint128,
-    gaming_fee: Uint128,
-    token_denom: String,
-    exp_per_denom_bet: u64,
-    exp_per_denom_won: u64,
+    collaterals: Vec<Collateral>,
+    elo_k_factor: u64,
     denom_tickers: Vec<DenomTicker>,
     label: String,
 ) -> Result<Response, ContractError> {
-    assert_owner(deps.storage, &info.sender)?;
+    assert_role(deps.storage, Role::MarketCreator, &info.sender)?;
     let config = CONFIG.load(deps.storage)?;
     let canonical_creator = deps.api.addr_canonicalize(env.contract.address.as_str())?;
     let code_info_response = deps.querier.query_wasm_code_info(config.games_code_id)?;
@@ -208,16 +291,23 @@ fn create_game(
         msg: to_json_binary(&prediction::prediction_game::msg::InstantiateMsg {
             config: prediction::prediction_game::Config {
                 next_round_seconds,
-                minimum_bet,
-                gaming_fee,
-                token_denom,
+                collaterals,
                 users_contract: config.users_contract.clone(),
-                exp_per_denom_bet,
-                exp_per_denom_won,
+                elo_k_factor,
                 dev_wallet_list: config.dev_wallet_list,
+                swap_router: None,
+                position_nfts: false,
+                early_bet_bonus: Decimal::zero(),
+                min_players_to_open: 0,
+                bid_extension_seconds: Uint128::zero(),
+                max_bid_extensions: 0,
+                round_retention: 0,
+                hedging: false,
             },
             denom_tickers,
-            extra_admins: Some(vec![info.sender.clone()]),
+            // Games belong to the owner of the manager, which keeps the roles to halt and update them
+            owner: get_ownership(deps.storage)?.owner,
+            extra_admins: Some(vec![env.contract.address.clone()]),
         })?,
         funds: vec![],
         admin: Some(info.sender.to_string()),
@@ -236,8 +326,7 @@ fn create_game(
     Ok(Response::new()
         .add_message(create_game_message)
         .add_message(add_game_to_users_contract_message)
-        .add_attribute("action", "create_game")
-        .add_attribute("contract_address", address))
+        .add_event(velo_event(VeloAction::CreateGame).add_attribute(attr::GAME, address)))
 }
 
 fn modify_dev_wallets(
@@ -246,17 +335,9 @@ fn modify_dev_wallets(
     wallets: Vec<WalletInfo>,
     update_all_games: bool,
 ) -> Result<Response, ContractError> {
-    assert_owner(deps.storage, &info.sender)?;
+    assert_role(deps.storage, Role::ConfigAdmin, &info.sender)?;
     let mut config = CONFIG.load(deps.storage)?;
-
-    let mut total_ratio = Decimal::zero();
-    for dev_wallet in wallets.iter() {
-        total_ratio += dev_wallet.ratio;
-    }
-
-    if total_ratio != Decimal::one() {
-        return Err(ContractError::WrongRatio {});
-    }
+    validate_dev_wallets(&wallets)?;
 
     config.dev_wallet_list.clone_from(&wallets);
     CONFIG.save(deps.storage, &config)?;
@@ -282,10 +363,10 @@ fn modify_dev_wallets(
         }
     }
 
-    Ok(Response::new()
-        .add_messages(messages)
-        .add_attribute("action", "modify_dev_wallets")
-        .add_attribute("update_all_games", update_all_games.to_string()))
+    Ok(Response::new().add_messages(messages).add_event(
+        velo_event(VeloAction::ModifyDevWallets)
+            .add_attribute(attr::UPDATE_ALL_GAMES, update_all_games.to_string()),
+    ))
 }
 
 fn update_code_ids(
@@ -294,13 +375,17 @@ fn update_code_ids(
     users_code_id: u64,
     games_code_id: u64,
 ) -> Result<Response, ContractError> {
-    assert_owner(deps.storage, &info.sender)?;
+    assert_role(deps.storage, Role::ConfigAdmin, &info.sender)?;
     let mut config = CONFIG.load(deps.storage)?;
     config.users_code_id = users_code_id;
     config.games_code_id = games_code_id;
     CONFIG.save(deps.storage, &config)?;
 
-    Ok(Response::new().add_attribute("action", "update_code_ids"))
+    Ok(Response::new().add_event(
+        velo_event(VeloAction::UpdateCodeIds)
+            .add_attribute(attr::USERS_CODE_ID, users_code_id.to_string())
+            .add_attribute(attr::GAMES_CODE_ID, games_code_id.to_string()),
+    ))
 }
 
 fn update_users_contract(
@@ -310,7 +395,7 @@ fn update_users_contract(
     update_all_games: bool,
     add_all_games_to_users_contract: bool,
 ) -> Result<Response, ContractError> {
-    assert_owner(deps.storage, &info.sender)?;
+    assert_role(deps.storage, Role::ConfigAdmin, &info.sender)?;
     let mut config = CONFIG.load(deps.storage)?;
     config.users_contract = address.clone();
     CONFIG.save(deps.storage, &config)?;
@@ -325,19 +410,15 @@ fn update_users_contract(
 
         if update_all_games {
             for game in games.iter() {
-                let mut games_config: prediction::prediction_game::Config =
-                    deps.querier.query_wasm_smart(
-                        game.clone(),
-                        &to_json_binary(&prediction::prediction_game::msg::QueryMsg::Config {})?,
-                    )?;
-
-                games_config.users_contract = address.clone();
-
+                // Only the users contract is sent, the rest of the config of the game is kept
                 messages.push(WasmMsg::Execute {
                     contract_addr: game.to_string(),
                     msg: to_json_binary(
                         &prediction::prediction_game::msg::ExecuteMsg::UpdateConfig {
-                            config: games_config,
+                            config: prediction::prediction_game::ConfigUpdate {
+                                users_contract: Some(address.clone()),
+                                ..Default::default()
+                            },
                         },
                     )?,
                     funds: vec![],
@@ -354,18 +435,18 @@ fn update_users_contract(
         }
     }
 
-    Ok(Response::new()
-        .add_messages(messages)
-        .add_attribute("action", "update_users_contract")
-        .add_attribute("update_all_games", update_all_games.to_string())
-        .add_attribute(
-            "add_all_games_to_users_contract",
-            add_all_games_to_users_contract.to_string(),
-        ))
+    Ok(Response::new().add_messages(messages).add_event(
+        velo_event(VeloAction::UpdateUsersContract)
+            .add_attribute(attr::UPDATE_ALL_GAMES, update_all_games.to_string())
+            .add_attribute(
+                attr::ADD_ALL_GAMES_TO_USERS_CONTRACT,
+                add_all_games_to_users_contract.to_string(),
+            ),
+    ))
 }
 
 fn halt_all_games(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
-    assert_owner(deps.storage, &info.sender)?;
+    assert_role(deps.storage, Role::Pauser, &info.sender)?;
     let games: Vec<Addr> = GAMES
         .range(deps.storage, None, None, Order::Ascending)
         .filter_map(Result::ok)
@@ -383,11 +464,11 @@ fn halt_all_games(deps: DepsMut, info: MessageInfo) -> Result<Response, Contract
 
     Ok(Response::new()
         .add_messages(messages)
-        .add_attribute("action", "halt_all_games"))
+        .add_event(velo_event(VeloAction::HaltAllGames)))
 }
 
 fn resume_all_games(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
-    assert_owner(deps.storage, &info.sender)?;
+    assert_role(deps.storage, Role::Pauser, &info.sender)?;
     let games: Vec<Addr> = GAMES
         .range(deps.storage, None, None, Order::Ascending)
         .filter_map(Result::ok)
@@ -405,7 +486,7 @@ fn resume_all_games(deps: DepsMut, info: MessageInfo) -> Result<Response, Contra
 
     Ok(Response::new()
         .add_messages(messages)
-        .add_attribute("action", "resume_all_games"))
+        .add_event(velo_event(VeloAction::ResumeAllGames)))
 }
 
 fn manually_add_game(
@@ -414,7 +495,7 @@ fn manually_add_game(
     address: Addr,
     add_to_users_contract: bool,
 ) -> Result<Response, ContractError> {
-    assert_owner(deps.storage, &info.sender)?;
+    assert_role(deps.storage, Role::ConfigAdmin, &info.sender)?;
     deps.api.addr_validate(address.as_str())?;
     GAMES.save(deps.storage, address.clone(), &Empty {})?;
 
@@ -432,8 +513,7 @@ fn manually_add_game(
 
     Ok(Response::new()
         .add_messages(messages)
-        .add_attribute("action", "manually_add_game")
-        .add_attribute("contract_address", address))
+        .add_event(velo_event(VeloAction::AddGameManually).add_attribute(attr::GAME, address)))
 }
 
 fn manually_remove_game(
@@ -442,7 +522,7 @@ fn manually_remove_game(
     address: Addr,
     remove_from_users_contract: bool,
 ) -> Result<Response, ContractError> {
-    assert_owner(deps.storage, &info.sender)?;
+    assert_role(deps.storage, Role::ConfigAdmin, &info.sender)?;
     GAMES.remove(deps.storage, address.clone());
 
     let mut messages = vec![];
@@ -459,14 +539,32 @@ fn manually_remove_game(
 
     Ok(Response::new()
         .add_messages(messages)
-        .add_attribute("action", "manually_remove_game")
-        .add_attribute("contract_address", address))
+        .add_event(velo_event(VeloAction::RemoveGameManually).add_attribute(attr::GAME, address)))
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
         QueryMsg::Ownership {} => to_json_binary(&get_ownership(deps.storage)?),
+        QueryMsg::MigrationPlan { from_version } => {
+            to_json_binary(&migration::query_migration_plan(
+                deps.storage,
+                CONTRACT_VERSION,
+                MIGRATIONS,
+                from_version,
+            )?)
+        }
+        QueryMsg::Roles { address } => to_json_binary(&rbac::query_roles(deps.storage, &address)?),
+        QueryMsg::RoleMembers {
+            role,
+            start_after,
+            limit,
+        } => to_json_binary(&rbac::query_role_members(
+            deps.storage,
+            role,
+            start_after,
+            limit,
+        )?),
         QueryMsg::Config {} => to_json_binary(&query_config(deps)?),
         QueryMsg::Games { start_after, limit } => {
             to_json_binary(&query_games(deps, start_after, limit))
@@ -569,12 +667,25 @@ fn query_games_info_with_duration(
     Ok(games_info)
 }
 
+fn velo_event(action: VeloAction) -> VeloEvent {
+    VeloEvent::new(VeloContract::Manager, action)
+}
+
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> StdResult<Response> {
-    let version = get_contract_version(deps.storage)?;
-    if version.contract != CONTRACT_NAME {
-        return Err(StdError::generic_err("Can only upgrade from same type"));
-    }
-    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
-    Ok(Response::default())
+pub fn migrate(deps: DepsMut, env: Env, msg: MigrateMsg) -> Result<Response, ContractError> {
+    let plan = migration::migrate(
+        deps,
+        &env,
+        CONTRACT_NAME,
+        CONTRACT_VERSION,
+        MIGRATIONS,
+        &msg,
+    )?;
+
+    Ok(Response::new().add_event(
+        velo_event(VeloAction::Migrate)
+            .add_attribute(attr::FROM_VERSION, plan.from_version)
+            .add_attribute(attr::CONTRACT_VERSION, plan.to_version)
+            .add_attribute(attr::STEPS, plan.steps.join(",")),
+    ))
 }
@@ -1,5 +1,7 @@
 use cosmwasm_std::{Instantiate2AddressError, StdError};
 use cw_ownable::OwnershipError;
+use general::migration::MigrationError;
+use general::rbac::RbacError;
 use thiserror::Error;
 
 #[derive(Error, Debug, PartialEq)]
@@ -10,6 +12,12 @@ pub enum ContractError {
     #[error(transparent)]
     Ownership(#[from] OwnershipError),
 
+    #[error(transparent)]
+    Rbac(#[from] RbacError),
+
+    #[error(transparent)]
+    Migration(#[from] MigrationError),
+
     #[error(transparent)]
     Instantiate2AddressError(#[from] Instantiate2AddressError),
 
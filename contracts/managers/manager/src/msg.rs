@@ -1,8 +1,10 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
 use cosmwasm_std::{Addr, Uint128};
 use cw_ownable::{cw_ownable_execute, cw_ownable_query};
+use general::migration::MigrationPlanResponse;
+use general::rbac::{Role, RoleMembersResponse, RolesResponse};
 use general::users::Config as UsersConfig;
-use prediction::prediction_game::{DenomTicker, WalletInfo};
+use prediction::prediction_game::{Collateral, DenomTicker, WalletInfo};
 
 use crate::state::Config;
 
@@ -17,13 +19,14 @@ pub struct InstantiateMsg {
 #[cw_ownable_execute]
 #[cw_serde]
 pub enum ExecuteMsg {
+    // Updates the config of the manager only, fields not set are kept
+    UpdateConfig {
+        config: ConfigUpdate,
+    },
     CreateGame {
         next_round_seconds: Uint128,
-        minimum_bet: Uint128,
-        gaming_fee: Uint128,
-        token_denom: String,
-        exp_per_denom_bet: u64,
-        exp_per_denom_won: u64,
+        collaterals: Vec<Collateral>,
+        elo_k_factor: u64,
         denom_tickers: Vec<DenomTicker>,
         label: String,
     },
@@ -54,6 +57,15 @@ pub enum ExecuteMsg {
         address: Addr,
         remove_from_users_contract: bool,
     },
+    // Owner actions to grant and revoke the roles of general::rbac
+    GrantRole {
+        role: Role,
+        address: String,
+    },
+    RevokeRole {
+        role: Role,
+        address: String,
+    },
 }
 
 #[cw_ownable_query]
@@ -78,6 +90,27 @@ pub enum QueryMsg {
         limit: Option<u32>,
         duration: Uint128,
     },
+    // Migration steps this code runs when migrating from from_version, the stored version if not set
+    #[returns(MigrationPlanResponse)]
+    MigrationPlan { from_version: Option<String> },
+    #[returns(RolesResponse)]
+    Roles { address: Addr },
+    #[returns(RoleMembersResponse)]
+    RoleMembers {
+        role: Role,
+        start_after: Option<Addr>,
+        limit: Option<u32>,
+    },
+}
+
+/// Fields of the config to update, the ones not set are kept
+#[cw_serde]
+#[derive(Default)]
+pub struct ConfigUpdate {
+    pub users_code_id: Option<u64>,
+    pub users_contract: Option<Addr>,
+    pub games_code_id: Option<u64>,
+    pub dev_wallet_list: Option<Vec<WalletInfo>>,
 }
 
 #[cw_serde]
@@ -86,5 +119,6 @@ pub struct GameInfo {
     pub next_round_seconds: Uint128,
 }
 
+// Params of the migration steps, see general::migration
 #[cw_serde]
 pub struct MigrateMsg {}
@@ -0,0 +1,680 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    from_json, to_json_binary, Addr, Binary, Decimal, Deps, DepsMut, Empty, Env, MessageInfo,
+    Order, Response, StdResult, Uint128,
+};
+use cw20::{Cw20ReceiveMsg, Denom};
+use cw_ownable::{assert_owner, get_ownership, initialize_owner};
+use cw_storage_plus::Bound;
+use cw_utils::must_pay;
+use general::asset::{query_balance, transfer_msg};
+use general::events::{attr, VeloAction, VeloContract, VeloEvent};
+use prediction::prediction_game::Direction;
+use vault::liquidity_vault::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg, ReceiveMsg};
+use vault::liquidity_vault::{
+    CapacityResponse, Config, GamesResponse, RoundExposure, SharesResponse, StateResponse,
+};
+
+use crate::error::ContractError;
+use crate::state::{CONFIG, EXPOSURES, GAMES, LOCKED, SHARES, TOTAL_SHARES};
+
+// Pagination info for queries
+const MAX_PAGE_LIMIT: u32 = 250;
+
+const CONTRACT_NAME: &str = env!("CARGO_PKG_NAME");
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    cw2::set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    initialize_owner(
+        deps.storage,
+        deps.api,
+        Some(
+            deps.api
+                .addr_validate(msg.owner.unwrap_or(info.sender.to_string()).as_str())?
+                .as_str(),
+        ),
+    )?;
+
+    validate_max_round_exposure(msg.config.max_round_exposure)?;
+    if let Denom::Cw20(address) = &msg.config.denom {
+        deps.api.addr_validate(address.as_str())?;
+    }
+
+    for game in msg.games {
+        GAMES.save(deps.storage, deps.api.addr_validate(&game)?, &Empty {})?;
+    }
+
+    CONFIG.save(deps.storage, &msg.config)?;
+    TOTAL_SHARES.save(deps.storage, &Uint128::zero())?;
+    LOCKED.save(deps.storage, &Uint128::zero())?;
+
+    Ok(Response::new().add_event(velo_event(VeloAction::Instantiate)))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(deps: DepsMut, _env: Env, MigrateMsg {}: MigrateMsg) -> StdResult<Response> {
+    cw2::set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    Ok(Response::new())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::UpdateConfig { max_round_exposure } => {
+            update_config(deps, info, max_round_exposure)
+        }
+        ExecuteMsg::AddGame { game } => add_game(deps, info, game),
+        ExecuteMsg::RemoveGame { game } => remove_game(deps, info, game),
+        ExecuteMsg::Deposit {} => {
+            let config = CONFIG.load(deps.storage)?;
+            let Denom::Native(denom) = &config.denom else {
+                return Err(ContractError::InvalidFunds {});
+            };
+            let amount = must_pay(&info, denom)?;
+            deposit(deps, env, info.sender, amount)
+        }
+        ExecuteMsg::Receive(msg) => receive(deps, env, info, msg),
+        ExecuteMsg::Withdraw { shares } => withdraw(deps, env, info, shares),
+        ExecuteMsg::LockPayout {
+            round_id,
+            direction,
+            stake,
+            payout,
+        } => lock_payout(deps, env, info, round_id, direction, stake, payout),
+        ExecuteMsg::SettleRound { round_id, winner } => settle_round(deps, info, round_id, winner),
+        ExecuteMsg::UpdateOwnership(action) => update_ownership(deps, env, info, action),
+    }
+}
+
+fn receive(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: Cw20ReceiveMsg,
+) -> Result<Response, ContractError> {
+    let config = CONFIG.load(deps.storage)?;
+    if config.denom != Denom::Cw20(info.sender) {
+        return Err(ContractError::InvalidFunds {});
+    }
+
+    let sender = deps.api.addr_validate(&msg.sender)?;
+    match from_json(&msg.msg)? {
+        ReceiveMsg::Deposit {} => deposit(deps, env, sender, msg.amount),
+    }
+}
+
+/// Mints shares for `amount`, which is already in the vault balance
+fn deposit(
+    deps: DepsMut,
+    env: Env,
+    depositor: Addr,
+    amount: Uint128,
+) -> Result<Response, ContractError> {
+    if amount.is_zero() {
+        return Err(ContractError::ZeroAmount {});
+    }
+
+    let config = CONFIG.load(deps.storage)?;
+    let assets_before = query_assets(deps.as_ref(), &env, &config)?.checked_sub(amount)?;
+    let total_shares = TOTAL_SHARES.load(deps.storage)?;
+
+    let shares = if total_shares.is_zero() {
+        amount
+    } else if assets_before.is_zero() {
+        // Rounds paid out everything, new deposits would be shared with the old LPs
+        return Err(ContractError::VaultDrained {});
+    } else {
+        amount.multiply_ratio(total_shares, assets_before)
+    };
+    if shares.is_zero() {
+        return Err(ContractError::ZeroAmount {});
+    }
+
+    TOTAL_SHARES.save(deps.storage, &total_shares.checked_add(shares)?)?;
+    SHARES.update(
+        deps.storage,
+        depositor.clone(),
+        |current| -> Result<_, ContractError> {
+            Ok(current.unwrap_or_default().checked_add(shares)?)
+        },
+    )?;
+
+    Ok(Response::new().add_event(
+        velo_event(VeloAction::Deposit)
+            .add_attribute(attr::OWNER, depositor.to_string())
+            .add_attribute(attr::AMOUNT, amount.to_string())
+            .add_attribute(attr::SHARES, shares.to_string()),
+    ))
+}
+
+fn withdraw(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    shares: Uint128,
+) -> Result<Response, ContractError> {
+    if shares.is_zero() {
+        return Err(ContractError::ZeroAmount {});
+    }
+
+    let owned = SHARES
+        .may_load(deps.storage, info.sender.clone())?
+        .unwrap_or_default();
+    if shares > owned {
+        return Err(ContractError::NotEnoughShares {});
+    }
+
+    let config = CONFIG.load(deps.storage)?;
+    let assets = query_assets(deps.as_ref(), &env, &config)?;
+    let total_shares = TOTAL_SHARES.load(deps.storage)?;
+    let amount = shares.multiply_ratio(assets, total_shares);
+
+    // Liquidity locked by unsettled rounds stays in the vault
+    let available = assets.saturating_sub(LOCKED.load(deps.storage)?);
+    if amount > available {
+        return Err(ContractError::InsufficientLiquidity { available });
+    }
+
+    TOTAL_SHARES.save(deps.storage, &total_shares.checked_sub(shares)?)?;
+    if owned == shares {
+        SHARES.remove(deps.storage, info.sender.clone());
+    } else {
+        SHARES.save(
+            deps.storage,
+            info.sender.clone(),
+            &owned.checked_sub(shares)?,
+        )?;
+    }
+
+    let mut resp = Response::new();
+    if !amount.is_zero() {
+        resp = resp.add_message(transfer_msg(&config.denom, &info.sender, amount)?);
+    }
+
+    Ok(resp.add_event(
+        velo_event(VeloAction::Withdraw)
+            .add_attribute(attr::OWNER, info.sender.to_string())
+            .add_attribute(attr::AMOUNT, amount.to_string())
+            .add_attribute(attr::SHARES, shares.to_string()),
+    ))
+}
+
+/// Takes the other side of a fixed odds bet, the game has already forwarded the stake
+fn lock_payout(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    round_id: Uint128,
+    direction: Direction,
+    stake: Uint128,
+    payout: Uint128,
+) -> Result<Response, ContractError> {
+    if !GAMES.has(deps.storage, info.sender.clone()) {
+        return Err(ContractError::GameNotRegistered {});
+    }
+
+    let config = CONFIG.load(deps.storage)?;
+    let assets = query_assets(deps.as_ref(), &env, &config)?;
+    let key = (info.sender.clone(), round_id.u128());
+    let mut exposure = EXPOSURES
+        .may_load(deps.storage, key.clone())?
+        .unwrap_or_default();
+    let locked_before = exposure.locked();
+
+    exposure.stakes = exposure.stakes.checked_add(stake)?;
+    match direction {
+        Direction::Bull => exposure.bull_payout = exposure.bull_payout.checked_add(payout)?,
+        Direction::Bear => exposure.bear_payout = exposure.bear_payout.checked_add(payout)?,
+    }
+
+    let max_side_payout = assets.mul_floor(config.max_round_exposure);
+    if exposure.side_payout(&direction) > max_side_payout {
+        return Err(ContractError::ExposureLimitExceeded {
+            max_payout: max_side_payout,
+        });
+    }
+
+    let locked = LOCKED
+        .load(deps.storage)?
+        .checked_sub(locked_before)?
+        .checked_add(exposure.locked())?;
+    if locked > assets {
+        return Err(ContractError::InsufficientLiquidity {
+            available: assets.saturating_sub(LOCKED.load(deps.storage)?),
+        });
+    }
+
+    LOCKED.save(deps.storage, &locked)?;
+    EXPOSURES.save(deps.storage, key, &exposure)?;
+
+    Ok(Response::new().add_event(
+        velo_event(VeloAction::LockPayout)
+            .add_attribute(attr::GAME, info.sender.to_string())
+            .add_attribute(attr::ROUND_ID, round_id.to_string())
+            .add_attribute(attr::DIRECTION, direction.to_string())
+            .add_attribute(attr::STAKE, stake.to_string())
+            .add_attribute(attr::PAYOUT, payout.to_string()),
+    ))
+}
+
+/// Releases the liquidity locked for a round and sends the game what its winners are owed.
+/// Games that were removed can still settle the rounds they locked
+fn settle_round(
+    deps: DepsMut,
+    info: MessageInfo,
+    round_id: Uint128,
+    winner: Option<Direction>,
+) -> Result<Response, ContractError> {
+    let key = (info.sender.clone(), round_id.u128());
+    let exposure = EXPOSURES
+        .may_load(deps.storage, key.clone())?
+        .ok_or(ContractError::RoundNotLocked { round_id })?;
+    EXPOSURES.remove(deps.storage, key);
+    let locked = LOCKED.load(deps.storage)?.checked_sub(exposure.locked())?;
+    LOCKED.save(deps.storage, &locked)?;
+
+    let config = CONFIG.load(deps.storage)?;
+    let payout = exposure.payout(winner.as_ref());
+    let mut resp = Response::new();
+    if !payout.is_zero() {
+        resp = resp.add_message(transfer_msg(&config.denom, &info.sender, payout)?);
+    }
+
+    Ok(resp.add_event(
+        velo_event(VeloAction::SettleRound)
+            .add_attribute(attr::GAME, info.sender.to_string())
+            .add_attribute(attr::ROUND_ID, round_id.to_string())
+            .add_attribute(
+                attr::WINNER,
+                match &winner {
+                    Some(w) => w.to_string(),
+                    None => "everybody".to_string(),
+                },
+            )
+            .add_attribute(attr::PAYOUT, payout.to_string()),
+    ))
+}
+
+fn update_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    max_round_exposure: Decimal,
+) -> Result<Response, ContractError> {
+    assert_owner(deps.storage, &info.sender)?;
+    validate_max_round_exposure(max_round_exposure)?;
+
+    CONFIG.update(deps.storage, |mut config| -> StdResult<_> {
+        config.max_round_exposure = max_round_exposure;
+        Ok(config)
+    })?;
+
+    Ok(Response::new().add_event(velo_event(VeloAction::UpdateConfig)))
+}
+
+fn add_game(deps: DepsMut, info: MessageInfo, game: String) -> Result<Response, ContractError> {
+    assert_owner(deps.storage, &info.sender)?;
+
+    let game = deps.api.addr_validate(&game)?;
+    if GAMES.has(deps.storage, game.clone()) {
+        return Err(ContractError::GameAlreadyRegistered {});
+    }
+    GAMES.save(deps.storage, game.clone(), &Empty {})?;
+
+    Ok(Response::new()
+        .add_event(velo_event(VeloAction::AddGame).add_attribute(attr::GAME, game.to_string())))
+}
+
+fn remove_game(deps: DepsMut, info: MessageInfo, game: String) -> Result<Response, ContractError> {
+    assert_owner(deps.storage, &info.sender)?;
+
+    let game = deps.api.addr_validate(&game)?;
+    if !GAMES.has(deps.storage, game.clone()) {
+        return Err(ContractError::GameNotRegistered {});
+    }
+    GAMES.remove(deps.storage, game.clone());
+
+    Ok(Response::new()
+        .add_event(velo_event(VeloAction::RemoveGame).add_attribute(attr::GAME, game.to_string())))
+}
+
+fn update_ownership(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    action: cw_ownable::Action,
+) -> Result<Response, ContractError> {
+    let ownership = cw_ownable::update_ownership(deps, &env.block, &info.sender, action)?;
+    Ok(Response::new().add_event(
+        velo_event(VeloAction::UpdateOwnership).add_attributes(ownership.into_attributes()),
+    ))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => to_json_binary(&CONFIG.load(deps.storage)?),
+        QueryMsg::State {} => to_json_binary(&query_state(deps, env)?),
+        QueryMsg::Shares { address } => to_json_binary(&query_shares(deps, env, address)?),
+        QueryMsg::Capacity {
+            game,
+            round_id,
+            direction,
+        } => to_json_binary(&query_capacity(deps, env, game, round_id, direction)?),
+        QueryMsg::RoundExposure { game, round_id } => {
+            to_json_binary(&query_round_exposure(deps, game, round_id)?)
+        }
+        QueryMsg::Games { start_after, limit } => {
+            to_json_binary(&query_games(deps, start_after, limit)?)
+        }
+        QueryMsg::Ownership {} => to_json_binary(&get_ownership(deps.storage)?),
+    }
+}
+
+fn query_state(deps: Deps, env: Env) -> StdResult<StateResponse> {
+    let config = CONFIG.load(deps.storage)?;
+
+    Ok(StateResponse {
+        assets: query_assets(deps, &env, &config)?,
+        locked: LOCKED.load(deps.storage)?,
+        total_shares: TOTAL_SHARES.load(deps.storage)?,
+    })
+}
+
+fn query_shares(deps: Deps, env: Env, address: String) -> StdResult<SharesResponse> {
+    let address = deps.api.addr_validate(&address)?;
+    let shares = SHARES.may_load(deps.storage, address)?.unwrap_or_default();
+    let total_shares = TOTAL_SHARES.load(deps.storage)?;
+
+    let amount = if total_shares.is_zero() {
+        Uint128::zero()
+    } else {
+        let config = CONFIG.load(deps.storage)?;
+        shares.multiply_ratio(query_assets(deps, &env, &config)?, total_shares)
+    };
+
+    Ok(SharesResponse { shares, amount })
+}
+
+/// Max payout the vault accepts on `direction` for the round. The stake of the bet is not
+/// counted, so a bet within the capacity is always accepted by lock_payout
+fn query_capacity(
+    deps: Deps,
+    env: Env,
+    game: String,
+    round_id: Uint128,
+    direction: Direction,
+) -> StdResult<CapacityResponse> {
+    let game = deps.api.addr_validate(&game)?;
+    if !GAMES.has(deps.storage, game.clone()) {
+        return Ok(CapacityResponse {
+            max_payout: Uint128::zero(),
+        });
+    }
+
+    let config = CONFIG.load(deps.storage)?;
+    let assets = query_assets(deps, &env, &config)?;
+    let exposure = EXPOSURES
+        .may_load(deps.storage, (game, round_id.u128()))?
+        .unwrap_or_default();
+
+    let free = assets.saturating_sub(LOCKED.load(deps.storage)?);
+    let round_room = assets
+        .mul_floor(config.max_round_exposure)
+        .saturating_sub(exposure.side_payout(&direction));
+
+    Ok(CapacityResponse {
+        max_payout: free.min(round_room),
+    })
+}
+
+fn query_round_exposure(deps: Deps, game: String, round_id: Uint128) -> StdResult<RoundExposure> {
+    let game = deps.api.addr_validate(&game)?;
+
+    Ok(EXPOSURES
+        .may_load(deps.storage, (game, round_id.u128()))?
+        .unwrap_or_default())
+}
+
+fn query_games(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<GamesResponse> {
+    let limit = limit.unwrap_or(MAX_PAGE_LIMIT).min(MAX_PAGE_LIMIT) as usize;
+    let start = start_after
+        .map(|game| deps.api.addr_validate(&game))
+        .transpose()?
+        .map(Bound::exclusive);
+
+    let games = GAMES
+        .keys(deps.storage, start, None, Order::Ascending)
+        .take(limit)
+        .collect::<StdResult<Vec<Addr>>>()?;
+
+    Ok(GamesResponse { games })
+}
+
+/// Vault balance, the stakes of unsettled rounds included
+fn query_assets(deps: Deps, env: &Env, config: &Config) -> StdResult<Uint128> {
+    query_balance(&deps.querier, &config.denom, &env.contract.address)
+}
+
+fn validate_max_round_exposure(max_round_exposure: Decimal) -> Result<(), ContractError> {
+    if max_round_exposure.is_zero() || max_round_exposure > Decimal::one() {
+        return Err(ContractError::InvalidMaxRoundExposure {});
+    }
+
+    Ok(())
+}
+
+fn velo_event(action: VeloAction) -> VeloEvent {
+    VeloEvent::new(VeloContract::LiquidityVault, action)
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::testing::{
+        message_info, mock_dependencies, mock_env, MockApi, MockQuerier, MockStorage,
+    };
+    use cosmwasm_std::{coins, BankMsg, CosmosMsg, OwnedDeps};
+
+    use super::*;
+
+    const DENOM: &str = "untrn";
+
+    type Deps = OwnedDeps<MockStorage, MockApi, MockQuerier>;
+
+    /// Vault of DENOM whose rounds can pay up to half of the assets on one direction
+    fn setup() -> (Deps, Addr) {
+        let mut deps = mock_dependencies();
+        let owner = deps.api.addr_make("owner");
+        let game = deps.api.addr_make("game");
+        let msg = InstantiateMsg {
+            owner: None,
+            config: Config {
+                denom: Denom::Native(DENOM.to_string()),
+                max_round_exposure: Decimal::percent(50),
+            },
+            games: vec![game.to_string()],
+        };
+        instantiate(deps.as_mut(), mock_env(), message_info(&owner, &[]), msg).unwrap();
+        (deps, game)
+    }
+
+    /// Sets the vault balance, as the bank does before the vault is called
+    fn set_assets(deps: &mut Deps, amount: u128) {
+        deps.querier
+            .bank
+            .update_balance(mock_env().contract.address, coins(amount, DENOM));
+    }
+
+    fn deposit(deps: &mut Deps, provider: &Addr, amount: u128, assets: u128) -> Uint128 {
+        set_assets(deps, assets);
+        let info = message_info(provider, &coins(amount, DENOM));
+        execute(deps.as_mut(), mock_env(), info, ExecuteMsg::Deposit {}).unwrap();
+        SHARES.load(&deps.storage, provider.clone()).unwrap()
+    }
+
+    fn lock(
+        deps: &mut Deps,
+        game: &Addr,
+        round_id: u128,
+        direction: Direction,
+        stake: u128,
+        payout: u128,
+    ) -> Result<Response, ContractError> {
+        let msg = ExecuteMsg::LockPayout {
+            round_id: Uint128::new(round_id),
+            direction,
+            stake: Uint128::new(stake),
+            payout: Uint128::new(payout),
+        };
+        execute(deps.as_mut(), mock_env(), message_info(game, &[]), msg)
+    }
+
+    fn sent(resp: &Response) -> Vec<(String, u128)> {
+        resp.messages
+            .iter()
+            .map(|msg| match &msg.msg {
+                CosmosMsg::Bank(BankMsg::Send { to_address, amount }) => {
+                    (to_address.clone(), amount[0].amount.u128())
+                }
+                msg => panic!("Unexpected message {:?}", msg),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn shares_are_minted_and_redeemed_at_the_vault_price() {
+        let (mut deps, _) = setup();
+        let first = deps.api.addr_make("first");
+        let second = deps.api.addr_make("second");
+
+        assert_eq!(
+            deposit(&mut deps, &first, 1_000, 1_000),
+            Uint128::new(1_000)
+        );
+        // The vault won 500 from lost stakes, a share is worth 1.5
+        assert_eq!(deposit(&mut deps, &second, 300, 1_800), Uint128::new(200));
+        assert_eq!(
+            TOTAL_SHARES.load(&deps.storage).unwrap(),
+            Uint128::new(1_200)
+        );
+
+        let withdraw = |shares: u128| ExecuteMsg::Withdraw {
+            shares: Uint128::new(shares),
+        };
+        let err = execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&second, &[]),
+            withdraw(201),
+        )
+        .unwrap_err();
+        assert_eq!(err, ContractError::NotEnoughShares {});
+
+        let resp = execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&first, &[]),
+            withdraw(1_000),
+        )
+        .unwrap();
+        assert_eq!(sent(&resp), vec![(first.to_string(), 1_500)]);
+        assert!(!SHARES.has(&deps.storage, first));
+        assert_eq!(TOTAL_SHARES.load(&deps.storage).unwrap(), Uint128::new(200));
+    }
+
+    #[test]
+    fn payouts_are_capped_by_the_exposure_and_the_liquidity() {
+        let (mut deps, game) = setup();
+        let provider = deps.api.addr_make("provider");
+        deposit(&mut deps, &provider, 1_000, 1_000);
+        // The game forwards the stake before locking the payout
+        set_assets(&mut deps, 1_100);
+
+        let err = lock(&mut deps, &provider, 1, Direction::Bull, 100, 100).unwrap_err();
+        assert_eq!(err, ContractError::GameNotRegistered {});
+        let err = lock(&mut deps, &game, 1, Direction::Bull, 100, 600).unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::ExposureLimitExceeded {
+                max_payout: Uint128::new(550)
+            }
+        );
+        lock(&mut deps, &game, 1, Direction::Bull, 100, 500).unwrap();
+        assert_eq!(LOCKED.load(&deps.storage).unwrap(), Uint128::new(500));
+
+        // Each round has its own cap, but together they can't lock more than the assets
+        lock(&mut deps, &game, 2, Direction::Bear, 0, 550).unwrap();
+        let err = lock(&mut deps, &game, 3, Direction::Bull, 0, 100).unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::InsufficientLiquidity {
+                available: Uint128::new(50)
+            }
+        );
+
+        // Locked liquidity can't be withdrawn
+        let msg = ExecuteMsg::Withdraw {
+            shares: Uint128::new(1_000),
+        };
+        let err =
+            execute(deps.as_mut(), mock_env(), message_info(&provider, &[]), msg).unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::InsufficientLiquidity {
+                available: Uint128::new(50)
+            }
+        );
+    }
+
+    #[test]
+    fn rounds_without_winner_give_the_stakes_back() {
+        let (mut deps, game) = setup();
+        let provider = deps.api.addr_make("provider");
+        deposit(&mut deps, &provider, 1_000, 1_000);
+        set_assets(&mut deps, 1_200);
+        lock(&mut deps, &game, 1, Direction::Bull, 100, 190).unwrap();
+        lock(&mut deps, &game, 1, Direction::Bear, 100, 190).unwrap();
+        assert_eq!(LOCKED.load(&deps.storage).unwrap(), Uint128::new(200));
+
+        let settle = ExecuteMsg::SettleRound {
+            round_id: Uint128::one(),
+            winner: None,
+        };
+        let resp = execute(
+            deps.as_mut(),
+            mock_env(),
+            message_info(&game, &[]),
+            settle.clone(),
+        )
+        .unwrap();
+        assert_eq!(sent(&resp), vec![(game.to_string(), 200)]);
+        assert!(LOCKED.load(&deps.storage).unwrap().is_zero());
+
+        // A round is settled once
+        let err = execute(deps.as_mut(), mock_env(), message_info(&game, &[]), settle).unwrap_err();
+        assert_eq!(
+            err,
+            ContractError::RoundNotLocked {
+                round_id: Uint128::one()
+            }
+        );
+    }
+}
@@ -0,0 +1,52 @@
+use cosmwasm_std::{OverflowError, StdError, Uint128};
+use cw_ownable::OwnershipError;
+use cw_utils::PaymentError;
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("{0}")]
+    Payment(#[from] PaymentError),
+
+    #[error(transparent)]
+    Ownership(#[from] OwnershipError),
+
+    #[error("{0}")]
+    Overflow(#[from] OverflowError),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+
+    #[error("Wrong denom sent")]
+    InvalidFunds {},
+
+    #[error("Max round exposure must be higher than 0 and not higher than 1")]
+    InvalidMaxRoundExposure {},
+
+    #[error("Amount must be higher than 0")]
+    ZeroAmount {},
+
+    #[error("Vault has no assets left for its shares, deposits are disabled")]
+    VaultDrained {},
+
+    #[error("Not enough shares")]
+    NotEnoughShares {},
+
+    #[error("Not enough free liquidity, available: {}", available)]
+    InsufficientLiquidity { available: Uint128 },
+
+    #[error("Round exposure limit exceeded, max payout: {}", max_payout)]
+    ExposureLimitExceeded { max_payout: Uint128 },
+
+    #[error("Game is already registered")]
+    GameAlreadyRegistered {},
+
+    #[error("Game is not registered")]
+    GameNotRegistered {},
+
+    #[error("No exposure for round {}", round_id)]
+    RoundNotLocked { round_id: Uint128 },
+}
@@ -0,0 +1,35 @@
+use cosmwasm_std::{Addr, Empty, Uint128};
+use cw_storage_plus::{Item, Map};
+use vault::liquidity_vault::{Config, RoundExposure};
+
+/// Top level storage key. Values must not conflict.
+/// Each key is only one byte long to ensure we use the smallest possible storage keys.
+#[repr(u8)]
+pub enum TopKey {
+    Config = b'a',
+    TotalShares = b'b',
+    Shares = b'c',
+    // Games allowed to lock liquidity
+    Games = b'd',
+    Exposures = b'e',
+    Locked = b'f',
+}
+
+impl TopKey {
+    const fn as_str(&self) -> &str {
+        let array_ref = unsafe { std::mem::transmute::<&TopKey, &[u8; 1]>(self) };
+        match core::str::from_utf8(array_ref) {
+            Ok(a) => a,
+            Err(_) => panic!("Non-utf8 enum value found. Use a-z, A-Z and 0-9"),
+        }
+    }
+}
+
+pub const CONFIG: Item<Config> = Item::new(TopKey::Config.as_str());
+pub const TOTAL_SHARES: Item<Uint128> = Item::new(TopKey::TotalShares.as_str());
+pub const SHARES: Map<Addr, Uint128> = Map::new(TopKey::Shares.as_str());
+pub const GAMES: Map<Addr, Empty> = Map::new(TopKey::Games.as_str());
+// Map (game, round id) -> exposure of the vault to the round until it is settled
+pub const EXPOSURES: Map<(Addr, u128), RoundExposure> = Map::new(TopKey::Exposures.as_str());
+// Sum of the locked amount of every unsettled round
+pub const LOCKED: Item<Uint128> = Item::new(TopKey::Locked.as_str());
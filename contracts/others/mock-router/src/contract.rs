@@ -0,0 +1,122 @@
+#[cfg(not(feature = "library"))]
+use cosmwasm_std::entry_point;
+use cosmwasm_std::{
+    to_json_binary, Binary, Decimal, Deps, DepsMut, Env, MessageInfo, Response, StdResult, Uint128,
+};
+use cw2::set_contract_version;
+use cw20::Denom;
+use cw_utils::one_coin;
+use general::asset::transfer_msg;
+use general::router::SwapOperation;
+
+use crate::error::ContractError;
+use crate::msg::{ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg};
+use crate::state::{Config, CONFIG};
+
+const CONTRACT_NAME: &str = env!("CARGO_PKG_NAME");
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn instantiate(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: InstantiateMsg,
+) -> Result<Response, ContractError> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    CONFIG.save(
+        deps.storage,
+        &Config {
+            owner: info.sender,
+            rate: msg.rate,
+        },
+    )?;
+
+    Ok(Response::new())
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn execute(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    match msg {
+        ExecuteMsg::ExecuteSwapOperations {
+            operations,
+            minimum_receive,
+            to,
+            max_spread: _,
+        } => execute_swap_operations(deps, info, operations, minimum_receive, to),
+        ExecuteMsg::SetRate { rate } => set_rate(deps, info, rate),
+    }
+}
+
+/// Pays the ask asset of the last operation at the configured rate, out of the router balance
+fn execute_swap_operations(
+    deps: DepsMut,
+    info: MessageInfo,
+    operations: Vec<SwapOperation>,
+    minimum_receive: Option<Uint128>,
+    to: Option<String>,
+) -> Result<Response, ContractError> {
+    let (Some(first), Some(last)) = (operations.first(), operations.last()) else {
+        return Err(ContractError::MustProvideOperations {});
+    };
+
+    let offer = one_coin(&info)?;
+    if first.offer_key() != offer.denom {
+        return Err(ContractError::InvalidOfferAsset {});
+    }
+
+    let config = CONFIG.load(deps.storage)?;
+    let amount = offer.amount.mul_floor(config.rate);
+    if let Some(minimum) = minimum_receive {
+        if amount < minimum {
+            return Err(ContractError::AssertionMinimumReceive { minimum, amount });
+        }
+    }
+
+    let recipient = match to {
+        Some(to) => deps.api.addr_validate(&to)?,
+        None => info.sender,
+    };
+
+    let ask_denom = match last {
+        SwapOperation::NativeSwap { ask_denom, .. } => Denom::Native(ask_denom.clone()),
+        SwapOperation::AstroSwap { ask_asset_info, .. } => ask_asset_info.to_denom(),
+    };
+
+    Ok(Response::new()
+        .add_message(transfer_msg(&ask_denom, &recipient, amount)?)
+        .add_attribute("action", "execute_swap_operations")
+        .add_attribute("offer_amount", offer.amount)
+        .add_attribute("return_amount", amount))
+}
+
+fn set_rate(deps: DepsMut, info: MessageInfo, rate: Decimal) -> Result<Response, ContractError> {
+    let mut config = CONFIG.load(deps.storage)?;
+    if info.sender != config.owner {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    config.rate = rate;
+    CONFIG.save(deps.storage, &config)?;
+
+    Ok(Response::new().add_attribute("action", "set_rate"))
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+    match msg {
+        QueryMsg::Config {} => to_json_binary(&CONFIG.load(deps.storage)?),
+    }
+}
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> StdResult<Response> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+    Ok(Response::default())
+}
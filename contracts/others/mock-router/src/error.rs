@@ -0,0 +1,28 @@
+use cosmwasm_std::{StdError, Uint128};
+use cw_utils::PaymentError;
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("{0}")]
+    Payment(#[from] PaymentError),
+
+    #[error("Unauthorized")]
+    Unauthorized {},
+
+    #[error("Must provide swap operations to execute")]
+    MustProvideOperations {},
+
+    #[error("First operation must offer the coin sent")]
+    InvalidOfferAsset {},
+
+    #[error(
+        "Assertion failed; minimum receive amount: {}, swap amount: {}",
+        minimum,
+        amount
+    )]
+    AssertionMinimumReceive { minimum: Uint128, amount: Uint128 },
+}
@@ -0,0 +1,35 @@
+use cosmwasm_schema::{cw_serde, QueryResponses};
+use cosmwasm_std::{Decimal, Uint128};
+use general::router::SwapOperation;
+
+use crate::state::Config;
+
+#[cw_serde]
+pub struct InstantiateMsg {
+    // Amount of the last ask asset paid for each token offered
+    pub rate: Decimal,
+}
+
+#[cw_serde]
+pub enum ExecuteMsg {
+    // Same message as general::router::RouterExecuteMsg
+    ExecuteSwapOperations {
+        operations: Vec<SwapOperation>,
+        minimum_receive: Option<Uint128>,
+        to: Option<String>,
+        max_spread: Option<Decimal>,
+    },
+    SetRate {
+        rate: Decimal,
+    },
+}
+
+#[cw_serde]
+#[derive(QueryResponses)]
+pub enum QueryMsg {
+    #[returns(Config)]
+    Config {},
+}
+
+#[cw_serde]
+pub struct MigrateMsg {}
@@ -0,0 +1,11 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, Decimal};
+use cw_storage_plus::Item;
+
+#[cw_serde]
+pub struct Config {
+    pub owner: Addr,
+    pub rate: Decimal,
+}
+
+pub const CONFIG: Item<Config> = Item::new("config");
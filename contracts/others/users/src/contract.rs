@@ -1,19 +1,25 @@
 use censor::Censor;
 use cosmwasm_std::{
-    entry_point, to_json_binary, DepsMut, Empty, Env, MessageInfo, Order, Response, StdError,
+    entry_point, to_json_binary, Attribute, DepsMut, Empty, Env, MessageInfo, Order, Response,
     Storage,
 };
 use cosmwasm_std::{Addr, Binary, Deps, StdResult};
 use cw2::set_contract_version;
 use cw_storage_plus::Bound;
+use general::events::{attr, patch_field, VeloAction, VeloContract, VeloEvent};
+use general::migration;
+use general::rbac::{self, assert_role, Role};
+use general::timelock::{self, PendingChange};
 use general::users::{
-    Config, Elo, EventInfo, ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg, User,
+    Config, ConfigChange, ConfigUpdate, Elo, EventInfo, ExecuteMsg, InstantiateMsg, MigrateMsg,
+    QueryMsg, User,
 };
 use url::Url;
 
 use crate::error::ContractError;
+use crate::migrations::MIGRATIONS;
 use crate::state::{
-    ADDRESS_TO_USER, ADMINS, CONFIG, FINISHED_EVENTS, GAME_CONTRACTS, NUM_USERS, ONGOING_EVENTS,
+    ADDRESS_TO_USER, CONFIG, FINISHED_EVENTS, GAME_CONTRACTS, NUM_USERS, ONGOING_EVENTS,
     PARTICIPATED_EVENTS, USERNAME_TO_USER,
 };
 
@@ -31,22 +37,22 @@ pub fn instantiate(
 ) -> Result<Response, ContractError> {
     set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
 
-    let mut admins = vec![info.sender];
-    if let Some(admins_list) = msg.extra_admins {
-        for admin in admins_list {
-            deps.api.addr_validate(admin.as_str())?;
-            admins.push(admin);
-        }
-    }
-
-    ADMINS.save(deps.storage, &admins)?;
+    let owner = msg.owner.unwrap_or(info.sender);
+    rbac::initialize(
+        deps.storage,
+        deps.api,
+        owner.as_str(),
+        &msg.extra_admins.unwrap_or_default(),
+    )?;
+    validate_config(&msg.config)?;
     NUM_USERS.save(deps.storage, &0)?;
     CONFIG.save(deps.storage, &msg.config)?;
 
-    Ok(Response::new()
-        .add_attribute("action", "instantiate")
-        .add_attribute("contract_name", CONTRACT_NAME)
-        .add_attribute("contract_version", CONTRACT_VERSION))
+    Ok(Response::new().add_event(
+        velo_event(VeloAction::Instantiate)
+            .add_attribute(attr::CONTRACT_NAME, CONTRACT_NAME)
+            .add_attribute(attr::CONTRACT_VERSION, CONTRACT_VERSION),
+    ))
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
@@ -57,7 +63,12 @@ pub fn execute(
     msg: ExecuteMsg,
 ) -> Result<Response, ContractError> {
     match msg {
-        ExecuteMsg::UpdateConfig { config } => update_config(deps, info, config),
+        ExecuteMsg::UpdateConfig { config } => update_config(deps, env, info, config),
+        ExecuteMsg::ExecuteChange { id } => execute_change(deps, env, id),
+        ExecuteMsg::CancelChange { id } => cancel_change(deps, info, id),
+        ExecuteMsg::UpdateTimelockDelay { seconds } => {
+            update_timelock_delay(deps, env, info, seconds)
+        }
         ExecuteMsg::AddGame { address } => add_game(deps, info, address),
         ExecuteMsg::AddGames { addresses } => add_games(deps, info, addresses),
         ExecuteMsg::RemoveGame { address } => remove_game(deps, info, address),
@@ -72,8 +83,9 @@ pub fn execute(
             experience,
             elo,
         } => add_experience_and_elo(deps, info, env, user, experience, elo),
-        ExecuteMsg::AddAdmin { new_admin } => add_admin(deps, info, new_admin),
-        ExecuteMsg::RemoveAdmin { old_admin } => remove_admin(deps, info, old_admin),
+        ExecuteMsg::UpdateOwnership(action) => update_ownership(deps, env, info, action),
+        ExecuteMsg::GrantRole { role, address } => grant_role(deps, info, role, address),
+        ExecuteMsg::RevokeRole { role, address } => revoke_role(deps, info, role, address),
         ExecuteMsg::AddEvent {
             event_name,
             start_timestamp,
@@ -96,25 +108,158 @@ pub fn execute(
 }
 
 fn update_config(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    update: ConfigUpdate,
+) -> Result<Response, ContractError> {
+    assert_role(deps.storage, Role::ConfigAdmin, &info.sender)?;
+
+    // Fail now rather than when the change is executed
+    let mut config = CONFIG.load(deps.storage)?;
+    patch_config(&mut config, update.clone())?;
+    validate_config(&config)?;
+
+    // Only the fields changing reached levels wait for the timelock, the rest applies at once
+    let (timelocked, immediate) = update.split_timelocked();
+    let mut resp = Response::new();
+    if immediate != ConfigUpdate::default() {
+        resp = apply_change(deps.branch(), ConfigChange::Config { config: immediate })?;
+    }
+    if timelocked != ConfigUpdate::default() {
+        let queued = queue_or_apply_change(
+            deps,
+            env,
+            info.sender,
+            ConfigChange::Config { config: timelocked },
+        )?;
+        resp = resp.add_events(queued.events);
+    }
+
+    Ok(resp)
+}
+
+/// Sets the fields of the update, returning the old and new value of each one as attributes
+fn patch_config(config: &mut Config, update: ConfigUpdate) -> StdResult<Vec<Attribute>> {
+    let mut attributes = vec![];
+    patch_field(
+        &mut attributes,
+        "initial_exp_per_level",
+        &mut config.initial_exp_per_level,
+        update.initial_exp_per_level,
+    )?;
+    patch_field(
+        &mut attributes,
+        "exp_increase_per_level",
+        &mut config.exp_increase_per_level,
+        update.exp_increase_per_level,
+    )?;
+
+    Ok(attributes)
+}
+
+fn validate_config(config: &Config) -> Result<(), ContractError> {
+    if config.initial_exp_per_level == 0 {
+        return Err(ContractError::InvalidExpPerLevel {});
+    }
+
+    Ok(())
+}
+
+/// Applies the change at once when there is no timelock delay, otherwise queues it
+fn queue_or_apply_change(
     deps: DepsMut,
+    env: Env,
+    proposer: Addr,
+    change: ConfigChange,
+) -> Result<Response, ContractError> {
+    if timelock::delay(deps.storage)? == 0 {
+        return apply_change(deps, change);
+    }
+
+    let pending = timelock::queue(deps.storage, proposer, env.block.time, change)?;
+    Ok(Response::new().add_event(
+        velo_event(VeloAction::QueueChange)
+            .add_attribute(attr::CHANGE_ID, pending.id.to_string())
+            .add_attribute(attr::CHANGE, pending.change.as_str())
+            .add_attribute(
+                attr::EXECUTABLE_AT,
+                pending.executable_at.seconds().to_string(),
+            ),
+    ))
+}
+
+fn apply_change(deps: DepsMut, change: ConfigChange) -> Result<Response, ContractError> {
+    match change {
+        ConfigChange::Config { config: update } => {
+            let mut config = CONFIG.load(deps.storage)?;
+            let attributes = patch_config(&mut config, update)?;
+            validate_config(&config)?;
+            CONFIG.save(deps.storage, &config)?;
+
+            Ok(Response::new().add_event(
+                velo_event(VeloAction::UpdateConfig)
+                    .add_attributes(attributes)
+                    .add_attribute(
+                        attr::EXP_PER_LEVEL,
+                        config.initial_exp_per_level.to_string(),
+                    )
+                    .add_attribute(
+                        attr::EXP_INCREASE_PER_LEVEL,
+                        config.exp_increase_per_level.to_string(),
+                    ),
+            ))
+        }
+        ConfigChange::TimelockDelay { seconds } => {
+            timelock::set_delay(deps.storage, seconds)?;
+
+            Ok(Response::new().add_event(
+                velo_event(VeloAction::UpdateTimelockDelay)
+                    .add_attribute(attr::DELAY, seconds.to_string()),
+            ))
+        }
+    }
+}
+
+fn execute_change(deps: DepsMut, env: Env, id: u64) -> Result<Response, ContractError> {
+    let pending: PendingChange<ConfigChange> =
+        timelock::take_ready(deps.storage, id, env.block.time)?;
+    let res = apply_change(deps, pending.change)?;
+
+    Ok(res.add_event(
+        velo_event(VeloAction::ExecuteChange).add_attribute(attr::CHANGE_ID, id.to_string()),
+    ))
+}
+
+fn cancel_change(deps: DepsMut, info: MessageInfo, id: u64) -> Result<Response, ContractError> {
+    assert_role(deps.storage, Role::Owner, &info.sender)?;
+    let pending: PendingChange<ConfigChange> = timelock::cancel(deps.storage, id)?;
+
+    Ok(Response::new().add_event(
+        velo_event(VeloAction::CancelChange)
+            .add_attribute(attr::CHANGE_ID, id.to_string())
+            .add_attribute(attr::CHANGE, pending.change.as_str()),
+    ))
+}
+
+fn update_timelock_delay(
+    deps: DepsMut,
+    env: Env,
     info: MessageInfo,
-    config: Config,
+    seconds: u64,
 ) -> Result<Response, ContractError> {
-    assert_is_admin(deps.as_ref(), info)?;
+    assert_role(deps.storage, Role::Owner, &info.sender)?;
 
-    CONFIG.save(deps.storage, &config)?;
+    let change = ConfigChange::TimelockDelay { seconds };
+    if seconds >= timelock::delay(deps.storage)? {
+        return apply_change(deps, change);
+    }
 
-    Ok(Response::new()
-        .add_attribute("action", "update_config")
-        .add_attribute("exp_per_level", config.initial_exp_per_level.to_string())
-        .add_attribute(
-            "increase_exp_per_level",
-            config.exp_increase_per_level.to_string(),
-        ))
+    queue_or_apply_change(deps, env, info.sender, change)
 }
 
 fn add_game(deps: DepsMut, info: MessageInfo, game: Addr) -> Result<Response, ContractError> {
-    assert_is_admin(deps.as_ref(), info)?;
+    assert_role(deps.storage, Role::ConfigAdmin, &info.sender)?;
 
     GAME_CONTRACTS.save(
         deps.storage,
@@ -122,9 +267,7 @@ fn add_game(deps: DepsMut, info: MessageInfo, game: Addr) -> Result<Response, Co
         &Empty {},
     )?;
 
-    Ok(Response::new()
-        .add_attribute("action", "add_game_contract")
-        .add_attribute("address", game))
+    Ok(Response::new().add_event(velo_event(VeloAction::AddGame).add_attribute(attr::GAME, game)))
 }
 
 fn add_games(
@@ -132,27 +275,28 @@ fn add_games(
     info: MessageInfo,
     games: Vec<Addr>,
 ) -> Result<Response, ContractError> {
-    assert_is_admin(deps.as_ref(), info)?;
+    assert_role(deps.storage, Role::ConfigAdmin, &info.sender)?;
 
+    let mut event = velo_event(VeloAction::AddGames);
     for game in games {
         GAME_CONTRACTS.save(
             deps.storage,
             deps.api.addr_validate(game.as_ref())?,
             &Empty {},
         )?;
+        event = event.add_attribute(attr::GAME, game);
     }
 
-    Ok(Response::new().add_attribute("action", "add_games_contract"))
+    Ok(Response::new().add_event(event))
 }
 
 fn remove_game(deps: DepsMut, info: MessageInfo, game: Addr) -> Result<Response, ContractError> {
-    assert_is_admin(deps.as_ref(), info)?;
+    assert_role(deps.storage, Role::ConfigAdmin, &info.sender)?;
 
     GAME_CONTRACTS.remove(deps.storage, game.clone());
 
     Ok(Response::new()
-        .add_attribute("action", "remove_game_contract")
-        .add_attribute("address", game))
+        .add_event(velo_event(VeloAction::RemoveGame).add_attribute(attr::GAME, game)))
 }
 
 fn modify_user(
@@ -188,8 +332,7 @@ fn modify_user(
     )?;
 
     Ok(Response::new()
-        .add_attribute("action", "modify_user")
-        .add_attribute("user", info.sender))
+        .add_event(velo_event(VeloAction::ModifyUser).add_attribute(attr::PLAYER, info.sender)))
 }
 
 fn modify_verification(
@@ -198,17 +341,18 @@ fn modify_verification(
     username: String,
     is_verified: bool,
 ) -> Result<Response, ContractError> {
-    assert_is_admin(deps.as_ref(), info)?;
+    assert_role(deps.storage, Role::Moderator, &info.sender)?;
 
     let mut user = USERNAME_TO_USER.load(deps.storage, username.to_owned())?;
     user.is_verified = Some(is_verified);
     ADDRESS_TO_USER.save(deps.storage, user.to_owned().address.unwrap(), &user)?;
     USERNAME_TO_USER.save(deps.storage, username.to_owned(), &user)?;
 
-    Ok(Response::new()
-        .add_attribute("action", "modify_verification")
-        .add_attribute("username", username)
-        .add_attribute("is_verified", is_verified.to_string()))
+    Ok(Response::new().add_event(
+        velo_event(VeloAction::ModifyVerification)
+            .add_attribute(attr::USERNAME, username)
+            .add_attribute(attr::IS_VERIFIED, is_verified.to_string()),
+    ))
 }
 
 fn reset_elo(
@@ -216,7 +360,7 @@ fn reset_elo(
     info: MessageInfo,
     elo_substraction: Option<u64>,
 ) -> Result<Response, ContractError> {
-    assert_is_admin(deps.as_ref(), info)?;
+    assert_role(deps.storage, Role::ConfigAdmin, &info.sender)?;
 
     let all_addresses: Vec<Addr> = ADDRESS_TO_USER
         .range(deps.storage, None, None, Order::Ascending)
@@ -234,7 +378,7 @@ fn reset_elo(
         ADDRESS_TO_USER.save(deps.storage, each_addr, &user)?;
     }
 
-    Ok(Response::new().add_attribute("action", "reset_elo"))
+    Ok(Response::new().add_event(velo_event(VeloAction::ResetElo)))
 }
 
 fn add_experience_and_elo(
@@ -319,41 +463,53 @@ fn add_experience_and_elo(
         )?;
     }
 
-    Ok(Response::new()
-        .add_attribute("action", "modify_experience_and_elo")
-        .add_attribute("game_contract", info.sender)
-        .add_attribute("user", user)
-        .add_attribute("experience_addition", experience.to_string())
-        .add_attribute("elo_modification", elo.is_some().to_string()))
+    Ok(Response::new().add_event(
+        velo_event(VeloAction::AddExperienceAndElo)
+            .add_attribute(attr::GAME, info.sender)
+            .add_attribute(attr::PLAYER, user)
+            .add_attribute(attr::EXPERIENCE, experience.to_string())
+            .add_attribute(attr::ELO_MODIFIED, elo.is_some().to_string()),
+    ))
 }
 
-fn add_admin(deps: DepsMut, info: MessageInfo, new_admin: Addr) -> Result<Response, ContractError> {
-    assert_is_admin(deps.as_ref(), info)?;
-    deps.api.addr_validate(new_admin.as_ref())?;
-    let mut admins = ADMINS.load(deps.storage)?;
-
-    admins.push(new_admin.clone());
-
-    ADMINS.save(deps.storage, &admins)?;
-
-    Ok(Response::new().add_attribute("add_admin", new_admin.to_string()))
+fn update_ownership(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    action: cw_ownable::Action,
+) -> Result<Response, ContractError> {
+    let ownership = cw_ownable::update_ownership(deps, &env.block, &info.sender, action)?;
+    Ok(Response::new().add_event(
+        velo_event(VeloAction::UpdateOwnership).add_attributes(ownership.into_attributes()),
+    ))
 }
 
-fn remove_admin(
+fn grant_role(
     deps: DepsMut,
     info: MessageInfo,
-    old_admin: Addr,
+    role: Role,
+    address: String,
 ) -> Result<Response, ContractError> {
-    assert_is_admin(deps.as_ref(), info)?;
-    let mut admins = ADMINS.load(deps.storage)?;
-    admins.retain(|admin| admin != old_admin);
-
-    if admins.is_empty() {
-        return Err(ContractError::NeedOneAdmin {});
-    }
+    let member = rbac::grant_role(deps.storage, deps.api, &info.sender, role, &address)?;
+    Ok(Response::new().add_event(
+        velo_event(VeloAction::GrantRole)
+            .add_attribute(attr::ROLE, role.as_str())
+            .add_attribute(attr::MEMBER, member),
+    ))
+}
 
-    ADMINS.save(deps.storage, &admins)?;
-    Ok(Response::new().add_attribute("remove_admin", old_admin.to_string()))
+fn revoke_role(
+    deps: DepsMut,
+    info: MessageInfo,
+    role: Role,
+    address: String,
+) -> Result<Response, ContractError> {
+    let member = rbac::revoke_role(deps.storage, deps.api, &info.sender, role, &address)?;
+    Ok(Response::new().add_event(
+        velo_event(VeloAction::RevokeRole)
+            .add_attribute(attr::ROLE, role.as_str())
+            .add_attribute(attr::MEMBER, member),
+    ))
 }
 
 fn add_event(
@@ -365,7 +521,7 @@ fn add_event(
     end_timestamp: u64,
     games: Option<Vec<Addr>>,
 ) -> Result<Response, ContractError> {
-    assert_is_admin(deps.as_ref(), info)?;
+    assert_role(deps.storage, Role::MarketCreator, &info.sender)?;
 
     if ONGOING_EVENTS.has(deps.storage, event_name.clone()) {
         return Err(ContractError::EventAlreadyExists {});
@@ -389,8 +545,7 @@ fn add_event(
     ONGOING_EVENTS.save(deps.storage, event_name.clone(), &event)?;
 
     Ok(Response::new()
-        .add_attribute("action", "add_event")
-        .add_attribute("event_name", event_name))
+        .add_event(velo_event(VeloAction::AddEvent).add_attribute(attr::EVENT_NAME, event_name)))
 }
 
 fn add_game_to_event(
@@ -399,7 +554,7 @@ fn add_game_to_event(
     event_name: String,
     game_address: Addr,
 ) -> Result<Response, ContractError> {
-    assert_is_admin(deps.as_ref(), info)?;
+    assert_role(deps.storage, Role::MarketCreator, &info.sender)?;
 
     let mut event = ONGOING_EVENTS.load(deps.storage, event_name.clone())?;
 
@@ -412,10 +567,11 @@ fn add_game_to_event(
 
     ONGOING_EVENTS.save(deps.storage, event_name.clone(), &event)?;
 
-    Ok(Response::new()
-        .add_attribute("action", "add_game_to_event")
-        .add_attribute("event_name", event_name)
-        .add_attribute("game_address", game_address))
+    Ok(Response::new().add_event(
+        velo_event(VeloAction::AddGameToEvent)
+            .add_attribute(attr::EVENT_NAME, event_name)
+            .add_attribute(attr::GAME, game_address),
+    ))
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
@@ -435,7 +591,29 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
         QueryMsg::Users { start_after, limit } => {
             to_json_binary(&query_users(deps, start_after, limit))
         }
-        QueryMsg::Admins {} => to_json_binary(&query_admins(deps)?),
+        QueryMsg::Ownership {} => to_json_binary(&cw_ownable::get_ownership(deps.storage)?),
+        QueryMsg::PendingChanges { start_after, limit } => to_json_binary(
+            &timelock::query_pending_changes::<ConfigChange>(deps.storage, start_after, limit)?,
+        ),
+        QueryMsg::MigrationPlan { from_version } => {
+            to_json_binary(&migration::query_migration_plan(
+                deps.storage,
+                CONTRACT_VERSION,
+                MIGRATIONS,
+                from_version,
+            )?)
+        }
+        QueryMsg::Roles { address } => to_json_binary(&rbac::query_roles(deps.storage, &address)?),
+        QueryMsg::RoleMembers {
+            role,
+            start_after,
+            limit,
+        } => to_json_binary(&rbac::query_role_members(
+            deps.storage,
+            role,
+            start_after,
+            limit,
+        )?),
         QueryMsg::OngoingEvents { start_after, limit } => {
             to_json_binary(&query_ongoing_events(deps, start_after, limit))
         }
@@ -485,11 +663,6 @@ fn query_game_registered(deps: Deps, game_address: Addr) -> StdResult<bool> {
     Ok(GAME_CONTRACTS.has(deps.storage, game_address))
 }
 
-fn query_admins(deps: Deps) -> StdResult<Vec<Addr>> {
-    let admins = ADMINS.load(deps.storage)?;
-    Ok(admins)
-}
-
 fn query_ongoing_events(
     deps: Deps,
     start_after: Option<String>,
@@ -681,24 +854,25 @@ fn validate_user(
     Ok(())
 }
 
-fn assert_is_admin(deps: Deps, info: MessageInfo) -> StdResult<bool> {
-    let admins = ADMINS.load(deps.storage)?;
-    if !admins.contains(&info.sender) {
-        return Err(StdError::generic_err(format!(
-            "Only an admin can execute this function. Sender: {}",
-            info.sender
-        )));
-    }
-
-    Ok(true)
+fn velo_event(action: VeloAction) -> VeloEvent {
+    VeloEvent::new(VeloContract::Users, action)
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn migrate(deps: DepsMut, _env: Env, _msg: MigrateMsg) -> StdResult<Response> {
-    let version = cw2::get_contract_version(deps.storage)?;
-    if version.contract != CONTRACT_NAME {
-        return Err(StdError::generic_err("Can only upgrade from same type"));
-    }
-    cw2::set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
-    Ok(Response::default())
+pub fn migrate(deps: DepsMut, env: Env, msg: MigrateMsg) -> Result<Response, ContractError> {
+    let plan = migration::migrate(
+        deps,
+        &env,
+        CONTRACT_NAME,
+        CONTRACT_VERSION,
+        MIGRATIONS,
+        &msg,
+    )?;
+
+    Ok(Response::new().add_event(
+        velo_event(VeloAction::Migrate)
+            .add_attribute(attr::FROM_VERSION, plan.from_version)
+            .add_attribute(attr::CONTRACT_VERSION, plan.to_version)
+            .add_attribute(attr::STEPS, plan.steps.join(",")),
+    ))
 }
@@ -1,4 +1,8 @@
 use cosmwasm_std::StdError;
+use cw_ownable::OwnershipError;
+use general::migration::MigrationError;
+use general::rbac::RbacError;
+use general::timelock::TimelockError;
 use thiserror::Error;
 use url::ParseError;
 
@@ -10,6 +14,18 @@ pub enum ContractError {
     #[error("{0}")]
     ParseError(#[from] ParseError),
 
+    #[error(transparent)]
+    Ownership(#[from] OwnershipError),
+
+    #[error(transparent)]
+    Rbac(#[from] RbacError),
+
+    #[error(transparent)]
+    Timelock(#[from] TimelockError),
+
+    #[error(transparent)]
+    Migration(#[from] MigrationError),
+
     #[error("Neither EXP or ELO can be modified by user")]
     CantModifyExpOrElo {},
 
@@ -45,9 +61,6 @@ pub enum ContractError {
     #[error("Username already exists")]
     UsernameAlreadyExists {},
 
-    #[error("At least one admin must remain")]
-    NeedOneAdmin {},
-
     #[error("Event already exists")]
     EventAlreadyExists {},
 
@@ -56,4 +69,7 @@ pub enum ContractError {
 
     #[error("Cannot create an event that already finished")]
     EventAlreadyFinished {},
+
+    #[error("Exp per level must be higher than 0")]
+    InvalidExpPerLevel {},
 }
@@ -0,0 +1,50 @@
+use cosmwasm_std::{DepsMut, Env, StdResult};
+use general::migration::{MigrationStep, StepRun};
+use general::rbac;
+use general::timelock;
+use general::users::MigrateMsg;
+
+use crate::state::ADMINS;
+
+/// State rewrites run by `migrate`, by the version that introduced them
+pub const MIGRATIONS: &[MigrationStep<MigrateMsg>] = &[
+    MigrationStep {
+        version: "0.2.0",
+        name: "admins_to_roles",
+        run: StepRun::Once(admins_to_roles),
+    },
+    MigrationStep {
+        version: "0.2.0",
+        name: "timelock_delay",
+        run: StepRun::Once(timelock_delay),
+    },
+];
+
+// The owner of the message, or the first admin, becomes the owner and the rest get every other
+// role
+fn admins_to_roles(deps: DepsMut, _env: &Env, msg: &MigrateMsg) -> StdResult<()> {
+    let Some(admins) = ADMINS.may_load(deps.storage)? else {
+        return Ok(());
+    };
+
+    match &msg.owner {
+        Some(owner) => rbac::initialize(deps.storage, deps.api, owner.as_str(), &admins)?,
+        None => {
+            if let Some((owner, admins)) = admins.split_first() {
+                rbac::initialize(deps.storage, deps.api, owner.as_str(), admins)?;
+            }
+        }
+    }
+    ADMINS.remove(deps.storage);
+
+    Ok(())
+}
+
+// Config changes are timelocked from now on, the delay can be set while migrating
+fn timelock_delay(deps: DepsMut, _env: &Env, msg: &MigrateMsg) -> StdResult<()> {
+    if let Some(seconds) = msg.timelock_delay {
+        timelock::set_delay(deps.storage, seconds)?;
+    }
+
+    Ok(())
+}
@@ -33,6 +33,7 @@ pub const ADDRESS_TO_USER: Map<Addr, User> = Map::new(TopKey::AddressToUser.as_s
 pub const USERNAME_TO_USER: Map<String, User> = Map::new(TopKey::UsernameToUser.as_str());
 pub const GAME_CONTRACTS: Map<Addr, Empty> = Map::new(TopKey::GameContracts.as_str());
 pub const CONFIG: Item<Config> = Item::new(TopKey::Config.as_str());
+/* Replaced by the roles of general::rbac, only read when migrating */
 pub const ADMINS: Item<Vec<Addr>> = Item::new(TopKey::Admins.as_str());
 pub const ONGOING_EVENTS: Map<String, EventInfo> = Map::new(TopKey::OngoingEvents.as_str());
 pub const FINISHED_EVENTS: Map<String, EventInfo> = Map::new(TopKey::FinishedEvents.as_str());
@@ -2,27 +2,83 @@ use std::collections::HashMap;
 
 use cosmwasm_schema::cw_serde;
 use cosmwasm_std::{Addr, Decimal, Uint128};
+use cw20::Denom;
+use general::experience::ExperienceConfig;
 
 #[cw_serde]
 pub struct Config {
     pub minimum_bet: Uint128,
     pub gaming_fee: Uint128,
-    // The token we are placing the bet with
-    pub token_denom: String,
+    // The token we are placing the bet with, a native denom or a CW20 contract
+    pub token_denom: Denom,
     // Address of the users contract where this contract will add the XP.
     pub users_contract: Addr,
-    // Rewards for Users
-    pub exp_per_denom_bet: u64,
-    pub exp_per_denom_won: u64,
+    // Rewards for Users, calculated from the USD value of each bet and win
+    pub experience: ExperienceConfig,
+    // K-factor used to compute the ELO change of a player on every settled bet
+    #[serde(default)]
+    pub elo_k_factor: u64,
     pub fee_receiver_wallet_list: Vec<WalletInfo>,
 }
 
+/// Fields of the config to update, the ones not set are kept
+#[cw_serde]
+#[derive(Default)]
+pub struct ConfigUpdate {
+    pub minimum_bet: Option<Uint128>,
+    pub gaming_fee: Option<Uint128>,
+    pub token_denom: Option<Denom>,
+    pub users_contract: Option<Addr>,
+    pub experience: Option<ExperienceConfig>,
+    pub elo_k_factor: Option<u64>,
+    pub fee_receiver_wallet_list: Option<Vec<WalletInfo>>,
+}
+
+impl ConfigUpdate {
+    /// Splits the update into the fields that wait for the timelock, the ones moving funds or
+    /// trusted with them (fee, token, users contract, fee receivers), and the ones applied at once
+    pub fn split_timelocked(self) -> (ConfigUpdate, ConfigUpdate) {
+        let timelocked = ConfigUpdate {
+            gaming_fee: self.gaming_fee,
+            token_denom: self.token_denom,
+            users_contract: self.users_contract,
+            fee_receiver_wallet_list: self.fee_receiver_wallet_list,
+            ..ConfigUpdate::default()
+        };
+        let immediate = ConfigUpdate {
+            gaming_fee: None,
+            token_denom: None,
+            users_contract: None,
+            fee_receiver_wallet_list: None,
+            ..self
+        };
+        (timelocked, immediate)
+    }
+}
+
 #[cw_serde]
 pub struct WalletInfo {
     pub address: Addr,
     pub ratio: Decimal,
 }
 
+/// Sensitive changes queued by general::timelock until the delay passes
+#[allow(clippy::large_enum_variant)]
+#[cw_serde]
+pub enum ConfigChange {
+    Config { config: ConfigUpdate },
+    TimelockDelay { seconds: u64 },
+}
+
+impl ConfigChange {
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            ConfigChange::Config { .. } => "config",
+            ConfigChange::TimelockDelay { .. } => "timelock_delay",
+        }
+    }
+}
+
 /// Primary key for betinfo: (round_id, player)
 pub type BetInfoKey = (u128, Addr);
 /// Primary key for claiminfo: (round_id, player)
@@ -57,6 +113,60 @@ pub struct Bet {
     pub result_option: Option<String>,
     pub cancelled: bool,
     pub num_players: u64,
+    // Prizes escrowed for the winners of the bet
+    #[serde(default)]
+    pub sponsorships: Vec<Sponsorship>,
+    // Sponsored prize shared by the winners, set when the bet is completed with winners
+    #[serde(default)]
+    pub boost: Uint128,
+}
+
+impl Bet {
+    /// Amount shared by the winners, sponsored boost included
+    pub fn prize(&self) -> Uint128 {
+        self.current_bet_amounts.values().sum::<Uint128>() + self.boost
+    }
+
+    /// Total escrowed by the sponsors of the bet
+    pub fn sponsored(&self) -> Uint128 {
+        self.sponsorships.iter().map(|s| s.amount).sum()
+    }
+}
+
+#[cw_serde]
+pub struct Sponsorship {
+    pub sponsor: Addr,
+    pub amount: Uint128,
+}
+
+/// Data set on the response of a bet
+#[cw_serde]
+pub struct BetReceipt {
+    pub bet_id: Uint128,
+    pub option: String,
+    // Amount placed with this bet
+    pub stake: Uint128,
+    // Total amount the player has in this bet
+    pub total_stake: Uint128,
+    // Amounts bet on each option after the bet
+    pub current_bet_amounts: HashMap<String, Uint128>,
+}
+
+#[cw_serde]
+pub struct BetPayout {
+    pub bet_id: Uint128,
+    pub amount: Uint128,
+}
+
+/// Data set on the response of a claim
+#[cw_serde]
+pub struct ClaimReceipt {
+    pub player: Addr,
+    // Gross payout (winnings or refund) of each claimed bet
+    pub payouts: Vec<BetPayout>,
+    pub gross: Uint128,
+    pub fee: Uint128,
+    pub net: Uint128,
 }
 
 #[cw_serde]
@@ -64,3 +174,37 @@ pub struct BetOption {
     pub title: String,
     pub img_url: Option<String>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_fields_trusted_with_funds_are_timelocked() {
+        let update = ConfigUpdate {
+            minimum_bet: Some(Uint128::new(100)),
+            gaming_fee: Some(Uint128::new(500)),
+            users_contract: Some(Addr::unchecked("users")),
+            elo_k_factor: Some(32),
+            ..ConfigUpdate::default()
+        };
+
+        let (timelocked, immediate) = update.split_timelocked();
+        assert_eq!(
+            timelocked,
+            ConfigUpdate {
+                gaming_fee: Some(Uint128::new(500)),
+                users_contract: Some(Addr::unchecked("users")),
+                ..ConfigUpdate::default()
+            }
+        );
+        assert_eq!(
+            immediate,
+            ConfigUpdate {
+                minimum_bet: Some(Uint128::new(100)),
+                elo_k_factor: Some(32),
+                ..ConfigUpdate::default()
+            }
+        );
+    }
+}
@@ -0,0 +1,55 @@
+use cosmwasm_std::{
+    coins, to_json_binary, Addr, BankMsg, CosmosMsg, CustomQuery, QuerierWrapper, StdResult,
+    Uint128, WasmMsg,
+};
+use cw20::{BalanceResponse, Cw20ExecuteMsg, Cw20QueryMsg, Denom};
+
+/// Key identifying a token in storage and messages: the denom of native tokens or the
+/// address of CW20 contracts
+pub fn denom_key(denom: &Denom) -> String {
+    match denom {
+        Denom::Native(denom) => denom.clone(),
+        Denom::Cw20(contract) => contract.to_string(),
+    }
+}
+
+/// Message sending `amount` of a native or CW20 token to `recipient`, so payouts and fees
+/// are settled the same way whatever the game is played with
+pub fn transfer_msg(denom: &Denom, recipient: &Addr, amount: Uint128) -> StdResult<CosmosMsg> {
+    Ok(match denom {
+        Denom::Native(denom) => BankMsg::Send {
+            to_address: recipient.to_string(),
+            amount: coins(amount.u128(), denom),
+        }
+        .into(),
+        Denom::Cw20(contract) => WasmMsg::Execute {
+            contract_addr: contract.to_string(),
+            msg: to_json_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: recipient.to_string(),
+                amount,
+            })?,
+            funds: vec![],
+        }
+        .into(),
+    })
+}
+
+/// Balance of a native or CW20 token held by `address`
+pub fn query_balance<C: CustomQuery>(
+    querier: &QuerierWrapper<C>,
+    denom: &Denom,
+    address: &Addr,
+) -> StdResult<Uint128> {
+    match denom {
+        Denom::Native(denom) => Ok(querier.query_balance(address, denom)?.amount),
+        Denom::Cw20(contract) => {
+            let response: BalanceResponse = querier.query_wasm_smart(
+                contract,
+                &Cw20QueryMsg::Balance {
+                    address: address.to_string(),
+                },
+            )?;
+            Ok(response.balance)
+        }
+    }
+}
@@ -0,0 +1,114 @@
+use cosmwasm_std::{Decimal, Uint128};
+
+use crate::users::Elo;
+
+/// Accumulates the Elo change of a player over all the bets settled in a single claim.
+/// Each bet moves the rating by `k_factor * (score - expected)`, where the expected score is
+/// the implied probability of the chosen side, i.e. the share of the pool that backed it.
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct EloDelta {
+    gained: u64,
+    lost: u64,
+}
+
+impl EloDelta {
+    pub fn record(&mut self, k_factor: u64, side_amount: Uint128, pool_amount: Uint128, won: bool) {
+        if k_factor == 0 || pool_amount.is_zero() {
+            return;
+        }
+
+        // Side can never be bigger than the pool, but cap it to avoid underflows below
+        let expected = Decimal::from_ratio(side_amount.min(pool_amount), pool_amount);
+        let k_factor = Uint128::from(k_factor);
+
+        if won {
+            let gained = k_factor.mul_floor(Decimal::one() - expected).u128() as u64;
+            self.gained = self.gained.saturating_add(gained);
+        } else {
+            let lost = k_factor.mul_floor(expected).u128() as u64;
+            self.lost = self.lost.saturating_add(lost);
+        }
+    }
+
+    /// Net change to send to the users contract, None if the rating doesn't move
+    pub fn into_elo(self) -> Option<Elo> {
+        if self.gained == self.lost {
+            return None;
+        }
+
+        Some(Elo {
+            amount: self.gained.abs_diff(self.lost),
+            add: self.gained > self.lost,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn winning_against_the_crowd_gains_more() {
+        let mut underdog = EloDelta::default();
+        underdog.record(32, Uint128::new(25), Uint128::new(100), true);
+        let mut favourite = EloDelta::default();
+        favourite.record(32, Uint128::new(75), Uint128::new(100), true);
+
+        assert_eq!(
+            underdog.into_elo(),
+            Some(Elo {
+                amount: 24,
+                add: true
+            })
+        );
+        assert_eq!(
+            favourite.into_elo(),
+            Some(Elo {
+                amount: 8,
+                add: true
+            })
+        );
+    }
+
+    #[test]
+    fn losing_with_the_crowd_costs_more() {
+        let mut delta = EloDelta::default();
+        delta.record(32, Uint128::new(75), Uint128::new(100), false);
+
+        assert_eq!(
+            delta.into_elo(),
+            Some(Elo {
+                amount: 24,
+                add: false
+            })
+        );
+    }
+
+    #[test]
+    fn bets_of_a_claim_add_up_to_the_net_change() {
+        let mut delta = EloDelta::default();
+        delta.record(32, Uint128::new(50), Uint128::new(100), true);
+        delta.record(32, Uint128::new(50), Uint128::new(100), false);
+        assert_eq!(delta.clone().into_elo(), None);
+
+        delta.record(32, Uint128::new(50), Uint128::new(100), false);
+        assert_eq!(
+            delta.into_elo(),
+            Some(Elo {
+                amount: 16,
+                add: false
+            })
+        );
+    }
+
+    #[test]
+    fn disabled_or_empty_pools_dont_move_the_rating() {
+        let mut delta = EloDelta::default();
+        delta.record(0, Uint128::new(50), Uint128::new(100), true);
+        delta.record(32, Uint128::zero(), Uint128::zero(), true);
+        // A side can't be bigger than the pool, it is capped instead of underflowing
+        delta.record(32, Uint128::new(200), Uint128::new(100), true);
+
+        assert_eq!(delta.into_elo(), None);
+    }
+}
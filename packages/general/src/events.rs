@@ -0,0 +1,331 @@
+use cosmwasm_schema::serde::Serialize;
+use cosmwasm_std::{to_json_string, Attribute, Event, StdResult};
+
+/// Bumped every time an event or attribute is renamed or changes meaning, so indexers
+/// can decode old and new events side by side.
+pub const EVENT_SCHEMA_VERSION: &str = "1";
+
+// Prefix of all event types. The chain will additionally prefix them with "wasm-"
+pub const EVENT_PREFIX: &str = "velo";
+
+/// Attribute keys. Every event has `version` and `contract`, the rest depend on the action.
+pub mod attr {
+    pub const VERSION: &str = "version";
+    pub const CONTRACT: &str = "contract";
+
+    pub const PLAYER: &str = "player";
+    pub const AMOUNT: &str = "amount";
+    pub const FEE: &str = "fee";
+    pub const DENOM: &str = "denom";
+    pub const DENOMS: &str = "denoms";
+    pub const COLLATERAL: &str = "collateral";
+    pub const RECEIVED: &str = "received";
+    pub const CHANNEL: &str = "channel";
+    pub const ORIGINAL_SENDER: &str = "original_sender";
+    pub const IBC_DENOM: &str = "ibc_denom";
+    pub const SEQUENCE: &str = "sequence";
+    pub const TOKEN_ID: &str = "token_id";
+    pub const RECIPIENT: &str = "recipient";
+    pub const SENDER: &str = "sender";
+    pub const SPENDER: &str = "spender";
+    pub const OPERATOR: &str = "operator";
+    pub const TICKER: &str = "ticker";
+    pub const ROLE: &str = "role";
+    pub const MEMBER: &str = "member";
+    pub const CHANGE_ID: &str = "change_id";
+    pub const CHANGE: &str = "change";
+    pub const EXECUTABLE_AT: &str = "executable_at";
+    pub const DELAY: &str = "delay";
+    pub const OWNER: &str = "owner";
+
+    // Games
+    pub const ROUND_ID: &str = "round_id";
+    pub const BET_ID: &str = "bet_id";
+    pub const DIRECTION: &str = "direction";
+    pub const OPTION: &str = "option";
+    pub const RESULT_OPTION: &str = "result_option";
+    pub const BULL_AMOUNT: &str = "bull_amount";
+    pub const BEAR_AMOUNT: &str = "bear_amount";
+    pub const OPEN_PRICE: &str = "open_price";
+    pub const CLOSE_PRICE: &str = "close_price";
+    pub const WINNER: &str = "winner";
+    pub const HALTED: &str = "halted";
+    pub const BETTING: &str = "betting";
+    pub const ROUNDS: &str = "rounds";
+    pub const CLAIMS: &str = "claims";
+    pub const REASON: &str = "reason";
+    pub const RESUME_AT: &str = "resume_at";
+    pub const HOOK: &str = "hook";
+    pub const ERROR: &str = "error";
+    pub const SPONSOR: &str = "sponsor";
+    pub const SPONSORSHIP_ID: &str = "sponsorship_id";
+    pub const START_ROUND: &str = "start_round";
+    pub const END_ROUND: &str = "end_round";
+    pub const OPEN_TIME: &str = "open_time";
+    pub const EXTENSION: &str = "extension";
+    pub const COMPACTED_ROUNDS: &str = "compacted_rounds";
+    pub const PRUNED_BETS: &str = "pruned_bets";
+    pub const PRUNED_CLAIMS: &str = "pruned_claims";
+
+    // Users
+    pub const USERNAME: &str = "username";
+    pub const IS_VERIFIED: &str = "is_verified";
+    pub const EXPERIENCE: &str = "experience";
+    pub const ELO_MODIFIED: &str = "elo_modified";
+    pub const EVENT_NAME: &str = "event_name";
+    pub const GAME: &str = "game";
+    pub const EXP_PER_LEVEL: &str = "exp_per_level";
+    pub const EXP_INCREASE_PER_LEVEL: &str = "exp_increase_per_level";
+
+    // Manager
+    pub const CONTRACT_NAME: &str = "contract_name";
+    pub const CONTRACT_VERSION: &str = "contract_version";
+    pub const FROM_VERSION: &str = "from_version";
+    pub const STEPS: &str = "steps";
+    pub const REMAINING_STEPS: &str = "remaining_steps";
+    pub const USERS_CODE_ID: &str = "users_code_id";
+    pub const GAMES_CODE_ID: &str = "games_code_id";
+    pub const UPDATE_ALL_GAMES: &str = "update_all_games";
+    pub const ADD_ALL_GAMES_TO_USERS_CONTRACT: &str = "add_all_games_to_users_contract";
+
+    // Liquidity vault
+    pub const SHARES: &str = "shares";
+    pub const STAKE: &str = "stake";
+    pub const PAYOUT: &str = "payout";
+    pub const VAULT: &str = "vault";
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum VeloContract {
+    PredictionGame,
+    DaoBets,
+    Users,
+    Manager,
+    LiquidityVault,
+}
+
+impl VeloContract {
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            VeloContract::PredictionGame => "prediction_game",
+            VeloContract::DaoBets => "dao_bets",
+            VeloContract::Users => "users",
+            VeloContract::Manager => "manager",
+            VeloContract::LiquidityVault => "liquidity_vault",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum VeloAction {
+    // Shared
+    Instantiate,
+    Migrate,
+    ContinueMigration,
+    UpdateConfig,
+    UpdateOwnership,
+    GrantRole,
+    RevokeRole,
+    QueueChange,
+    ExecuteChange,
+    CancelChange,
+    UpdateTimelockDelay,
+    // Games
+    Bet,
+    Claim,
+    FinishRound,
+    StartRound,
+    NewRound,
+    Halt,
+    Resume,
+    UpdatePause,
+    EmergencyExit,
+    EmergencyRefund,
+    CompactRounds,
+    ModifyDevWallets,
+    AddTicker,
+    ModifyBetArray,
+    CreateBet,
+    ModifyBet,
+    CompleteBet,
+    CancelBet,
+    SwapAndBet,
+    IbcBet,
+    AddIbcDenom,
+    RemoveIbcDenom,
+    IbcPayout,
+    IbcPayoutFailed,
+    RetryIbcPayout,
+    ClaimPosition,
+    TransferPosition,
+    SendPosition,
+    ApprovePosition,
+    RevokePosition,
+    ApproveAllPositions,
+    RevokeAllPositions,
+    Sponsor,
+    BoostRound,
+    RefundSponsor,
+    ExtendRound,
+    VoidRound,
+    AddHook,
+    RemoveHook,
+    HookFailed,
+    // Users
+    AddGame,
+    AddGames,
+    RemoveGame,
+    ModifyUser,
+    ModifyVerification,
+    ResetElo,
+    AddExperienceAndElo,
+    AddEvent,
+    AddGameToEvent,
+    // Manager
+    CreateGame,
+    UpdateCodeIds,
+    UpdateUsersContract,
+    HaltAllGames,
+    ResumeAllGames,
+    AddGameManually,
+    RemoveGameManually,
+    // Liquidity vault
+    Deposit,
+    Withdraw,
+    LockPayout,
+    SettleRound,
+}
+
+impl VeloAction {
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            VeloAction::Instantiate => "instantiate",
+            VeloAction::Migrate => "migrate",
+            VeloAction::ContinueMigration => "continue_migration",
+            VeloAction::UpdateConfig => "update_config",
+            VeloAction::UpdateOwnership => "update_ownership",
+            VeloAction::GrantRole => "grant_role",
+            VeloAction::RevokeRole => "revoke_role",
+            VeloAction::QueueChange => "queue_change",
+            VeloAction::ExecuteChange => "execute_change",
+            VeloAction::CancelChange => "cancel_change",
+            VeloAction::UpdateTimelockDelay => "update_timelock_delay",
+            VeloAction::Bet => "bet",
+            VeloAction::Claim => "claim",
+            VeloAction::FinishRound => "finish_round",
+            VeloAction::StartRound => "start_round",
+            VeloAction::NewRound => "new_round",
+            VeloAction::Halt => "halt",
+            VeloAction::Resume => "resume",
+            VeloAction::UpdatePause => "update_pause",
+            VeloAction::EmergencyExit => "emergency_exit",
+            VeloAction::EmergencyRefund => "emergency_refund",
+            VeloAction::CompactRounds => "compact_rounds",
+            VeloAction::ModifyDevWallets => "modify_dev_wallets",
+            VeloAction::AddTicker => "add_ticker",
+            VeloAction::ModifyBetArray => "modify_bet_array",
+            VeloAction::CreateBet => "create_bet",
+            VeloAction::ModifyBet => "modify_bet",
+            VeloAction::CompleteBet => "complete_bet",
+            VeloAction::CancelBet => "cancel_bet",
+            VeloAction::SwapAndBet => "swap_and_bet",
+            VeloAction::IbcBet => "ibc_bet",
+            VeloAction::AddIbcDenom => "add_ibc_denom",
+            VeloAction::RemoveIbcDenom => "remove_ibc_denom",
+            VeloAction::IbcPayout => "ibc_payout",
+            VeloAction::IbcPayoutFailed => "ibc_payout_failed",
+            VeloAction::RetryIbcPayout => "retry_ibc_payout",
+            VeloAction::ClaimPosition => "claim_position",
+            VeloAction::TransferPosition => "transfer_position",
+            VeloAction::SendPosition => "send_position",
+            VeloAction::ApprovePosition => "approve_position",
+            VeloAction::RevokePosition => "revoke_position",
+            VeloAction::ApproveAllPositions => "approve_all_positions",
+            VeloAction::RevokeAllPositions => "revoke_all_positions",
+            VeloAction::Sponsor => "sponsor",
+            VeloAction::BoostRound => "boost_round",
+            VeloAction::RefundSponsor => "refund_sponsor",
+            VeloAction::ExtendRound => "extend_round",
+            VeloAction::VoidRound => "void_round",
+            VeloAction::AddHook => "add_hook",
+            VeloAction::RemoveHook => "remove_hook",
+            VeloAction::HookFailed => "hook_failed",
+            VeloAction::AddGame => "add_game",
+            VeloAction::AddGames => "add_games",
+            VeloAction::RemoveGame => "remove_game",
+            VeloAction::ModifyUser => "modify_user",
+            VeloAction::ModifyVerification => "modify_verification",
+            VeloAction::ResetElo => "reset_elo",
+            VeloAction::AddExperienceAndElo => "add_experience_and_elo",
+            VeloAction::AddEvent => "add_event",
+            VeloAction::AddGameToEvent => "add_game_to_event",
+            VeloAction::CreateGame => "create_game",
+            VeloAction::UpdateCodeIds => "update_code_ids",
+            VeloAction::UpdateUsersContract => "update_users_contract",
+            VeloAction::HaltAllGames => "halt_all_games",
+            VeloAction::ResumeAllGames => "resume_all_games",
+            VeloAction::AddGameManually => "add_game_manually",
+            VeloAction::RemoveGameManually => "remove_game_manually",
+            VeloAction::Deposit => "deposit",
+            VeloAction::Withdraw => "withdraw",
+            VeloAction::LockPayout => "lock_payout",
+            VeloAction::SettleRound => "settle_round",
+        }
+    }
+
+    /// Event type, e.g. `velo_bet`
+    pub fn event_type(&self) -> String {
+        format!("{}_{}", EVENT_PREFIX, self.as_str())
+    }
+}
+
+/// Event following the shared schema: one event per action, typed name and common attributes
+pub struct VeloEvent(Event);
+
+impl VeloEvent {
+    pub fn new(contract: VeloContract, action: VeloAction) -> Self {
+        VeloEvent(
+            Event::new(action.event_type())
+                .add_attribute(attr::VERSION, EVENT_SCHEMA_VERSION)
+                .add_attribute(attr::CONTRACT, contract.as_str()),
+        )
+    }
+
+    pub fn add_attribute(self, key: &'static str, value: impl Into<String>) -> Self {
+        VeloEvent(self.0.add_attribute(key, value))
+    }
+
+    pub fn add_attributes(self, attributes: impl IntoIterator<Item = Attribute>) -> Self {
+        VeloEvent(self.0.add_attributes(attributes))
+    }
+}
+
+impl From<VeloEvent> for Event {
+    fn from(event: VeloEvent) -> Self {
+        event.0
+    }
+}
+
+/// Sets a config field when the update has a value for it, recording the JSON encoded old and
+/// new values as `old_<field>` and `new_<field>` attributes
+pub fn patch_field<T: Serialize>(
+    attributes: &mut Vec<Attribute>,
+    field: &str,
+    current: &mut T,
+    update: Option<T>,
+) -> StdResult<()> {
+    let Some(value) = update else {
+        return Ok(());
+    };
+
+    attributes.push(Attribute::new(
+        format!("old_{field}"),
+        to_json_string(current)?,
+    ));
+    attributes.push(Attribute::new(
+        format!("new_{field}"),
+        to_json_string(&value)?,
+    ));
+    *current = value;
+    Ok(())
+}
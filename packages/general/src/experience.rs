@@ -0,0 +1,227 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Decimal, QuerierWrapper, StdError, StdResult, Uint128, Uint512};
+use neutron_sdk::bindings::query::NeutronQuery;
+
+use crate::oracle::{query_usd_price, PRICE_DECIMALS};
+
+// Tokens with more decimals than this are not supported
+pub const MAX_TOKEN_DECIMALS: u32 = 18;
+
+#[cw_serde]
+pub enum PriceSource {
+    // USD value of one whole token, set by the admins
+    Fixed { usd_price: Decimal },
+    // USD value of one whole token read from the Skip Connect oracle with this ticker
+    Oracle { ticker: String },
+}
+
+#[cw_serde]
+pub struct ExperienceConfig {
+    pub price_source: PriceSource,
+    // Decimals of the token used to bet, e.g. 6 for untrn
+    pub token_decimals: u32,
+    pub exp_per_usd_bet: u64,
+    pub exp_per_usd_won: u64,
+    // Maximum XP a single bet or a single claim can give
+    pub max_exp_per_bet: u64,
+    pub max_exp_per_claim: u64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ExperienceAction {
+    Bet,
+    Won,
+}
+
+impl ExperienceConfig {
+    pub fn validate(&self) -> StdResult<()> {
+        if self.token_decimals > MAX_TOKEN_DECIMALS {
+            return Err(StdError::generic_err(format!(
+                "Token decimals can't be higher than {}",
+                MAX_TOKEN_DECIMALS
+            )));
+        }
+
+        if let PriceSource::Oracle { ticker } = &self.price_source {
+            if ticker.is_empty() {
+                return Err(StdError::generic_err("Oracle ticker can't be empty"));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// XP for betting or winning `amount` base units of the bet token, based on their USD value.
+/// The oracle price is not checked for staleness because XP doesn't move funds and a stale,
+/// unknown or unreachable feed should never block a bet or a claim, it gives no XP instead.
+pub fn compute_experience(
+    querier: &QuerierWrapper<NeutronQuery>,
+    config: &ExperienceConfig,
+    amount: Uint128,
+    action: ExperienceAction,
+) -> StdResult<u64> {
+    let (exp_per_usd, max_exp) = match action {
+        ExperienceAction::Bet => (config.exp_per_usd_bet, config.max_exp_per_bet),
+        ExperienceAction::Won => (config.exp_per_usd_won, config.max_exp_per_claim),
+    };
+
+    if amount.is_zero() || exp_per_usd == 0 || max_exp == 0 {
+        return Ok(0);
+    }
+
+    // USD price of one whole token with PRICE_DECIMALS precision
+    let usd_price = match &config.price_source {
+        PriceSource::Fixed { usd_price } => {
+            // Decimal already has 18 decimals, same as PRICE_DECIMALS
+            usd_price.atomics()
+        }
+        PriceSource::Oracle { ticker } => {
+            // A failed query or a negative price would be an oracle malfunction, don't give any
+            // XP for it
+            query_usd_price(querier, ticker.clone())
+                .ok()
+                .and_then(|oracle_price| Uint128::try_from(oracle_price.price).ok())
+                .unwrap_or_default()
+        }
+    };
+
+    let denominator =
+        Uint512::from(10u128).checked_pow(config.token_decimals + PRICE_DECIMALS as u32)?;
+
+    // Amount, price and XP per USD take up to 128 + 128 + 64 bits, so the product always fits
+    let experience = Uint512::from(amount)
+        .checked_mul(Uint512::from(usd_price))?
+        .checked_mul(Uint512::from(exp_per_usd))?
+        .checked_div(denominator)?
+        .min(Uint512::from(max_exp));
+
+    // Capped to a u64 above so it can't overflow
+    Ok(Uint128::try_from(experience)?.u128() as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::testing::MockQuerier;
+    use cosmwasm_std::{to_json_binary, ContractResult, Int128, SystemError, SystemResult};
+    use neutron_sdk::bindings::oracle::query::GetPriceResponse;
+    use neutron_sdk::bindings::oracle::types::QuotePrice;
+
+    use super::*;
+
+    fn config(price_source: PriceSource) -> ExperienceConfig {
+        ExperienceConfig {
+            price_source,
+            token_decimals: 6,
+            exp_per_usd_bet: 10,
+            exp_per_usd_won: 20,
+            max_exp_per_bet: 1_000,
+            max_exp_per_claim: 50,
+        }
+    }
+
+    fn oracle_config() -> ExperienceConfig {
+        config(PriceSource::Oracle {
+            ticker: "NTRN".to_string(),
+        })
+    }
+
+    /// Querier answering every oracle query with `price` in 18 decimals, or failing without it
+    fn querier(price: Option<i128>) -> MockQuerier<NeutronQuery> {
+        MockQuerier::<NeutronQuery>::new(&[]).with_custom_handler(move |_| match price {
+            Some(price) => SystemResult::Ok(ContractResult::Ok(
+                to_json_binary(&GetPriceResponse {
+                    price: QuotePrice {
+                        price: Int128::new(price),
+                        block_timestamp: "2024-01-01T00:00:00Z".to_string(),
+                        block_height: None,
+                    },
+                    nonce: 0,
+                    decimals: 18,
+                    id: 0,
+                })
+                .unwrap(),
+            )),
+            None => SystemResult::Err(SystemError::UnsupportedRequest {
+                kind: "oracle".to_string(),
+            }),
+        })
+    }
+
+    fn experience(
+        querier: &MockQuerier<NeutronQuery>,
+        config: &ExperienceConfig,
+        amount: u128,
+        action: ExperienceAction,
+    ) -> u64 {
+        compute_experience(
+            &QuerierWrapper::new(querier),
+            config,
+            Uint128::new(amount),
+            action,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn fixed_price_gives_xp_per_usd() {
+        let config = config(PriceSource::Fixed {
+            usd_price: Decimal::percent(50),
+        });
+        let querier = querier(None);
+
+        // 5 tokens at 0.5 USD
+        assert_eq!(
+            experience(&querier, &config, 5_000_000, ExperienceAction::Bet),
+            25
+        );
+        assert_eq!(experience(&querier, &config, 0, ExperienceAction::Bet), 0);
+        // Capped per claim
+        assert_eq!(
+            experience(&querier, &config, 5_000_000, ExperienceAction::Won),
+            50
+        );
+    }
+
+    #[test]
+    fn oracle_price_gives_xp_per_usd() {
+        let querier = querier(Some(2 * 10i128.pow(18)));
+
+        // 3 tokens at 2 USD
+        assert_eq!(
+            experience(&querier, &oracle_config(), 3_000_000, ExperienceAction::Bet),
+            60
+        );
+    }
+
+    #[test]
+    fn broken_oracle_gives_no_xp() {
+        let config = oracle_config();
+
+        assert_eq!(
+            experience(&querier(None), &config, 3_000_000, ExperienceAction::Bet),
+            0
+        );
+        assert_eq!(
+            experience(
+                &querier(Some(-1)),
+                &config,
+                3_000_000,
+                ExperienceAction::Bet
+            ),
+            0
+        );
+    }
+
+    #[test]
+    fn huge_amounts_are_capped_without_overflow() {
+        let config = config(PriceSource::Fixed {
+            usd_price: Decimal::MAX,
+        });
+
+        assert_eq!(
+            experience(&querier(None), &config, u128::MAX, ExperienceAction::Bet),
+            1_000
+        );
+    }
+}
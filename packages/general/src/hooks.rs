@@ -0,0 +1,77 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{to_json_binary, Addr, StdResult, SubMsg, Uint128, WasmMsg};
+
+// Reply id of every hook submessage, the payload of the submessage is the listener address
+pub const HOOK_REPLY_ID: u64 = 1;
+// Gas a listener can use per hook. With a limit an out of gas listener is handled like any other error
+// instead of failing the whole transaction
+pub const HOOK_GAS_LIMIT: u64 = 1_000_000;
+// Maximum amount of listeners a game can have, every bet and claim pays the gas of all of them
+pub const MAX_HOOKS: usize = 10;
+
+#[cw_serde]
+pub struct HookPayout {
+    // Round id in prediction games, bet id in dao bets
+    pub id: Uint128,
+    pub amount: Uint128,
+}
+
+#[cw_serde]
+pub enum GameHookMsg {
+    Bet {
+        player: Addr,
+        // Round id in prediction games, bet id in dao bets
+        id: Uint128,
+        // Direction in prediction games, option in dao bets
+        side: String,
+        // Native denom or CW20 address of the amount
+        denom: String,
+        amount: Uint128,
+    },
+    Settle {
+        id: Uint128,
+        // Winning direction or option. None if everybody gets refunded
+        result: Option<String>,
+    },
+    Claim {
+        player: Addr,
+        // Native denom or CW20 address of the amounts
+        denom: String,
+        payouts: Vec<HookPayout>,
+        gross: Uint128,
+        fee: Uint128,
+        net: Uint128,
+    },
+}
+
+/// Message listener contracts need to accept in their ExecuteMsg
+#[cw_serde]
+pub enum HookExecuteMsg {
+    VeloGameHook(GameHookMsg),
+}
+
+#[cw_serde]
+pub struct HooksResponse {
+    pub hooks: Vec<Addr>,
+}
+
+/// One submessage per listener. Errors are caught in the reply so a failing listener never blocks the game
+pub fn hook_messages(hooks: Vec<Addr>, msg: GameHookMsg) -> StdResult<Vec<SubMsg>> {
+    let hook_msg = to_json_binary(&HookExecuteMsg::VeloGameHook(msg))?;
+
+    hooks
+        .into_iter()
+        .map(|hook| {
+            Ok(SubMsg::reply_on_error(
+                WasmMsg::Execute {
+                    contract_addr: hook.to_string(),
+                    msg: hook_msg.clone(),
+                    funds: vec![],
+                },
+                HOOK_REPLY_ID,
+            )
+            .with_gas_limit(HOOK_GAS_LIMIT)
+            .with_payload(to_json_binary(&hook)?))
+        })
+        .collect()
+}
@@ -0,0 +1,163 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{
+    coin, to_json_binary, to_json_string, Addr, Api, CanonicalAddr, Env, IbcMsg, IbcTimeout,
+    StdError, StdResult, SubMsg, SubMsgResponse, Uint128,
+};
+use cw20::Denom;
+use sha2::{Digest, Sha256};
+
+use crate::asset::transfer_msg;
+
+// Prefix ibc-hooks uses to derive the address that executes contracts for a remote sender
+pub const IBC_HOOK_SENDER_PREFIX: &str = "ibc-wasm-hook-intermediary";
+// Time the origin chain has to receive winnings sent back before they are refunded to the game
+pub const IBC_TRANSFER_TIMEOUT_SECONDS: u64 = 600;
+// Reply id of the transfers sending winnings back, the payload of the submessage is the payout
+pub const IBC_PAYOUT_REPLY_ID: u64 = 3;
+
+/// Where a bet placed through an ibc-hooks memo came from
+#[cw_serde]
+pub struct IbcOrigin {
+    // Channel on this chain the transfer arrived through
+    pub channel: String,
+    // Address of the player on the origin chain
+    pub sender: String,
+    // Winnings are sent back to the origin chain instead of staying in the local address
+    pub return_to_origin: bool,
+}
+
+/// Denom accepted through IBC and the game token it is bet as, the same denom when the game only
+/// allow-lists its own native tokens
+#[cw_serde]
+pub struct IbcDenom {
+    pub ibc_denom: String,
+    pub denom: String,
+}
+
+#[cw_serde]
+pub struct IbcDenomsResponse {
+    pub ibc_denoms: Vec<IbcDenom>,
+}
+
+/// Winnings sent back to an origin chain, kept by channel and packet sequence until the transfer
+/// is acknowledged. If it fails the amount is credited back to the player
+#[cw_serde]
+pub struct IbcPayout {
+    pub player: Addr,
+    // Native denom of the amount
+    pub denom: String,
+    pub amount: Uint128,
+}
+
+/// Outcome of a transfer ibc-hooks reports to the contract set in its `ibc_callback` memo
+#[cw_serde]
+pub enum IbcLifecycleComplete {
+    IbcAck {
+        channel: String,
+        sequence: u64,
+        ack: String,
+        success: bool,
+    },
+    IbcTimeout {
+        channel: String,
+        sequence: u64,
+    },
+}
+
+impl IbcLifecycleComplete {
+    /// Channel and sequence of the packet, and whether the tokens reached the origin chain
+    pub fn outcome(&self) -> (String, u64, bool) {
+        match self {
+            IbcLifecycleComplete::IbcAck {
+                channel,
+                sequence,
+                success,
+                ..
+            } => (channel.clone(), *sequence, *success),
+            IbcLifecycleComplete::IbcTimeout { channel, sequence } => {
+                (channel.clone(), *sequence, false)
+            }
+        }
+    }
+}
+
+/// Sudo messages games accept from ibc-hooks
+#[cw_serde]
+pub enum IbcHooksSudoMsg {
+    IbcLifecycleComplete(IbcLifecycleComplete),
+}
+
+#[cw_serde]
+struct IbcCallbackMemo {
+    ibc_callback: String,
+}
+
+/// Address ibc-hooks executes the contract with for `original_sender` coming through `channel`,
+/// sha256(sha256(prefix) ++ "channel/original_sender") with the bech32 prefix of this chain.
+/// Only that address can claim to be the remote sender, so the origin can't be spoofed
+pub fn intermediate_sender(api: &dyn Api, channel: &str, original_sender: &str) -> StdResult<Addr> {
+    let prefix_hash = Sha256::digest(IBC_HOOK_SENDER_PREFIX.as_bytes());
+    let hash = Sha256::new()
+        .chain_update(prefix_hash)
+        .chain_update(format!("{}/{}", channel, original_sender).as_bytes())
+        .finalize();
+
+    api.addr_humanize(&CanonicalAddr::from(hash.as_slice()))
+}
+
+/// Message paying `amount` to a player. Native tokens of players that asked for it are sent back
+/// to their origin chain, everything else is paid locally to `recipient`. Transfers back reply
+/// with `IBC_PAYOUT_REPLY_ID` so the game can track them, and ask ibc-hooks to report their outcome
+pub fn payout_msg(
+    env: &Env,
+    origin: Option<&IbcOrigin>,
+    denom: &Denom,
+    recipient: &Addr,
+    amount: Uint128,
+) -> StdResult<SubMsg> {
+    match (origin, denom) {
+        (Some(origin), Denom::Native(denom)) if origin.return_to_origin => {
+            let transfer = IbcMsg::Transfer {
+                channel_id: origin.channel.clone(),
+                to_address: origin.sender.clone(),
+                amount: coin(amount.u128(), denom),
+                timeout: IbcTimeout::with_timestamp(
+                    env.block.time.plus_seconds(IBC_TRANSFER_TIMEOUT_SECONDS),
+                ),
+                memo: Some(to_json_string(&IbcCallbackMemo {
+                    ibc_callback: env.contract.address.to_string(),
+                })?),
+            };
+            let payout = IbcPayout {
+                player: recipient.clone(),
+                denom: denom.clone(),
+                amount,
+            };
+
+            Ok(SubMsg::reply_on_success(transfer, IBC_PAYOUT_REPLY_ID)
+                .with_payload(to_json_binary(&payout)?))
+        }
+        _ => Ok(SubMsg::new(transfer_msg(denom, recipient, amount)?)),
+    }
+}
+
+/// Channel and sequence of the packet sent by a transfer, from its `send_packet` event
+pub fn sent_packet(response: &SubMsgResponse) -> StdResult<(String, u64)> {
+    let attribute = |key: &str| {
+        response
+            .events
+            .iter()
+            .filter(|event| event.ty == "send_packet")
+            .flat_map(|event| event.attributes.iter())
+            .find(|attr| attr.key == key)
+            .map(|attr| attr.value.clone())
+            .ok_or_else(|| StdError::generic_err(format!("Transfer sent no {}", key)))
+    };
+
+    let channel = attribute("packet_src_channel")?;
+    let sequence = attribute("packet_sequence")?
+        .parse::<u64>()
+        .map_err(|e| StdError::generic_err(e.to_string()))?;
+
+    Ok((channel, sequence))
+}
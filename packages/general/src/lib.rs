@@ -1 +1,12 @@
+pub mod asset;
+pub mod elo;
+pub mod events;
+pub mod experience;
+pub mod hooks;
+pub mod ibc;
+pub mod migration;
+pub mod oracle;
+pub mod rbac;
+pub mod router;
+pub mod timelock;
 pub mod users;
@@ -0,0 +1,427 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{from_json, to_json_binary, Binary, DepsMut, Env, StdError, StdResult, Storage};
+use cw_storage_plus::Item;
+use semver::Version;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use thiserror::Error;
+
+// Records rewritten by each call of a paged step
+pub const DEFAULT_MIGRATION_LIMIT: u32 = 100;
+pub const MAX_MIGRATION_LIMIT: u32 = 500;
+
+/// A rewrite of the stored state, run when the contract is migrated from a version older than
+/// the one that introduced it. `P` is the migrate message of the contract, for optional params
+pub struct MigrationStep<P> {
+    // Version of the contract that introduced the step
+    pub version: &'static str,
+    pub name: &'static str,
+    pub run: StepRun<P>,
+}
+
+/// Paged step: the deps, env and params, the cursor to resume from and the records to rewrite
+pub type PagedRun<P> = fn(DepsMut, &Env, &P, Option<Binary>, u32) -> StdResult<Option<Binary>>;
+
+pub enum StepRun<P> {
+    // Rewrites state of a bounded size at once
+    Once(fn(DepsMut, &Env, &P) -> StdResult<()>),
+    // Rewrites up to `limit` records after the cursor and returns the cursor to resume from, None
+    // once every record is rewritten. Used for maps growing with the usage of the contract
+    Paged(PagedRun<P>),
+}
+
+/// Migration that couldn't run all its steps within `migrate`, continued with
+/// `continue_migration` until no step is left
+#[cw_serde]
+pub struct PendingMigration {
+    // Steps left in order, the first one is in progress
+    pub steps: Vec<String>,
+    // Where the paged step in progress stopped
+    pub cursor: Option<Binary>,
+    // Migrate message the steps are run with
+    pub params: Binary,
+}
+
+const PENDING_MIGRATION: Item<PendingMigration> = Item::new("pending_migration");
+
+#[cw_serde]
+pub struct MigrationPlanResponse {
+    pub from_version: String,
+    pub to_version: String,
+    // Steps run, or that would run, in order
+    pub steps: Vec<String>,
+    // Steps of the migration in progress left to run with continue_migration
+    #[serde(default)]
+    pub remaining: Vec<String>,
+}
+
+#[derive(Error, Debug, PartialEq)]
+pub enum MigrationError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("Can only upgrade from {}, found {}", expected, found)]
+    WrongContract { expected: String, found: String },
+
+    #[error("Invalid version {}", version)]
+    InvalidVersion { version: String },
+
+    #[error("Can't downgrade from {} to {}", from, to)]
+    Downgrade { from: String, to: String },
+
+    #[error("Migration in progress, steps left: {}", steps)]
+    InProgress { steps: String },
+
+    #[error("No migration in progress")]
+    NotInProgress {},
+
+    #[error("Unknown migration step {}", name)]
+    UnknownStep { name: String },
+}
+
+fn parse_version(version: &str) -> Result<Version, MigrationError> {
+    Version::parse(version).map_err(|_| MigrationError::InvalidVersion {
+        version: version.to_string(),
+    })
+}
+
+/// Steps introduced after `from_version` and up to `to_version`, sorted by version. Steps of the
+/// same version keep the order they are registered in
+pub fn pending_steps<'a, P>(
+    steps: &'a [MigrationStep<P>],
+    from_version: &str,
+    to_version: &str,
+) -> Result<Vec<&'a MigrationStep<P>>, MigrationError> {
+    let from = parse_version(from_version)?;
+    let to = parse_version(to_version)?;
+    if from > to {
+        return Err(MigrationError::Downgrade {
+            from: from_version.to_string(),
+            to: to_version.to_string(),
+        });
+    }
+
+    let mut pending = vec![];
+    for step in steps {
+        let version = parse_version(step.version)?;
+        if from < version && version <= to {
+            pending.push((version, step));
+        }
+    }
+    pending.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+    Ok(pending.into_iter().map(|(_, step)| step).collect())
+}
+
+/// Checks the stored contract name, runs the pending steps and stores the new version. Once a
+/// paged step has rewritten a page, the steps left are stored and run by `continue_migration`
+pub fn migrate<P: Serialize>(
+    deps: DepsMut,
+    env: &Env,
+    contract_name: &str,
+    contract_version: &str,
+    steps: &[MigrationStep<P>],
+    params: &P,
+) -> Result<MigrationPlanResponse, MigrationError> {
+    let stored = cw2::get_contract_version(deps.storage)?;
+    if stored.contract != contract_name {
+        return Err(MigrationError::WrongContract {
+            expected: contract_name.to_string(),
+            found: stored.contract,
+        });
+    }
+    assert_migrated(deps.storage)?;
+
+    let pending = pending_steps(steps, &stored.version, contract_version)?;
+    cw2::set_contract_version(deps.storage, contract_name, contract_version)?;
+    let remaining = run_steps(deps, env, &pending, params, None, DEFAULT_MIGRATION_LIMIT)?;
+
+    Ok(MigrationPlanResponse {
+        from_version: stored.version,
+        to_version: contract_version.to_string(),
+        steps: pending.iter().map(|step| step.name.to_string()).collect(),
+        remaining,
+    })
+}
+
+/// Runs up to `limit` records of the migration in progress, and the steps after them until the
+/// next page. Returns the steps left
+pub fn continue_migration<P: Serialize + DeserializeOwned>(
+    deps: DepsMut,
+    env: &Env,
+    steps: &[MigrationStep<P>],
+    limit: Option<u32>,
+) -> Result<Vec<String>, MigrationError> {
+    let migration = PENDING_MIGRATION
+        .may_load(deps.storage)?
+        .ok_or(MigrationError::NotInProgress {})?;
+    let params: P = from_json(&migration.params)?;
+
+    let pending = migration
+        .steps
+        .iter()
+        .map(|name| {
+            steps
+                .iter()
+                .find(|step| step.name == name)
+                .ok_or_else(|| MigrationError::UnknownStep { name: name.clone() })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let limit = limit
+        .unwrap_or(DEFAULT_MIGRATION_LIMIT)
+        .clamp(1, MAX_MIGRATION_LIMIT);
+
+    run_steps(deps, env, &pending, &params, migration.cursor, limit)
+}
+
+/// Runs the steps in order until a paged step has more records left, which is stored with the
+/// steps after it
+fn run_steps<P: Serialize>(
+    mut deps: DepsMut,
+    env: &Env,
+    steps: &[&MigrationStep<P>],
+    params: &P,
+    mut cursor: Option<Binary>,
+    limit: u32,
+) -> Result<Vec<String>, MigrationError> {
+    for (index, step) in steps.iter().enumerate() {
+        match &step.run {
+            StepRun::Once(run) => run(deps.branch(), env, params)?,
+            StepRun::Paged(run) => {
+                if let Some(next) = run(deps.branch(), env, params, cursor.take(), limit)? {
+                    let remaining: Vec<String> = steps[index..]
+                        .iter()
+                        .map(|step| step.name.to_string())
+                        .collect();
+                    PENDING_MIGRATION.save(
+                        deps.storage,
+                        &PendingMigration {
+                            steps: remaining.clone(),
+                            cursor: Some(next),
+                            params: to_json_binary(params)?,
+                        },
+                    )?;
+                    return Ok(remaining);
+                }
+            }
+        }
+    }
+    PENDING_MIGRATION.remove(deps.storage);
+
+    Ok(vec![])
+}
+
+/// Cursor returned by a paged step, the key of the last record when a full page was rewritten
+pub fn next_cursor<K: Serialize, V>(records: &[(K, V)], limit: u32) -> StdResult<Option<Binary>> {
+    if records.len() < limit as usize {
+        return Ok(None);
+    }
+    records
+        .last()
+        .map(|(key, _)| to_json_binary(key))
+        .transpose()
+}
+
+/// Fails while a migration is in progress, the state is only partly rewritten
+pub fn assert_migrated(storage: &dyn Storage) -> Result<(), MigrationError> {
+    match PENDING_MIGRATION.may_load(storage)? {
+        Some(migration) => Err(MigrationError::InProgress {
+            steps: migration.steps.join(","),
+        }),
+        None => Ok(()),
+    }
+}
+
+/// Dry run of `migrate`, from `from_version` or the stored version of the contract
+pub fn query_migration_plan<P>(
+    storage: &dyn Storage,
+    contract_version: &str,
+    steps: &[MigrationStep<P>],
+    from_version: Option<String>,
+) -> StdResult<MigrationPlanResponse> {
+    let from_version = match from_version {
+        Some(version) => version,
+        None => cw2::get_contract_version(storage)?.version,
+    };
+    let pending = pending_steps(steps, &from_version, contract_version)
+        .map_err(|e| StdError::generic_err(e.to_string()))?;
+
+    let remaining = PENDING_MIGRATION
+        .may_load(storage)?
+        .map(|migration| migration.steps)
+        .unwrap_or_default();
+
+    Ok(MigrationPlanResponse {
+        from_version,
+        to_version: contract_version.to_string(),
+        steps: pending.iter().map(|step| step.name.to_string()).collect(),
+        remaining,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::testing::{mock_dependencies, mock_env};
+    use cosmwasm_std::{Empty, Order};
+    use cw_storage_plus::{Bound, Map};
+
+    use super::*;
+
+    const NAME: &str = "crates.io:game";
+    // Records rewritten by the paged step, with the steps that ran
+    const RECORDS: Map<u32, bool> = Map::new("records");
+    const RAN: Item<Vec<String>> = Item::new("ran");
+
+    fn record_run(storage: &mut dyn Storage, name: &str) -> StdResult<()> {
+        let mut ran = RAN.may_load(storage)?.unwrap_or_default();
+        ran.push(name.to_string());
+        RAN.save(storage, &ran)
+    }
+
+    fn config(deps: DepsMut, _env: &Env, _msg: &Empty) -> StdResult<()> {
+        record_run(deps.storage, "config")
+    }
+
+    fn pause(deps: DepsMut, _env: &Env, _msg: &Empty) -> StdResult<()> {
+        record_run(deps.storage, "pause")
+    }
+
+    fn records(
+        deps: DepsMut,
+        _env: &Env,
+        _msg: &Empty,
+        cursor: Option<Binary>,
+        limit: u32,
+    ) -> StdResult<Option<Binary>> {
+        let start = cursor.map(|cursor| from_json::<u32>(&cursor)).transpose()?;
+        let page = RECORDS
+            .range(
+                deps.storage,
+                start.map(Bound::exclusive),
+                None,
+                Order::Ascending,
+            )
+            .take(limit as usize)
+            .collect::<StdResult<Vec<_>>>()?;
+        for (key, _) in &page {
+            RECORDS.save(deps.storage, *key, &true)?;
+        }
+        next_cursor(&page, limit)
+    }
+
+    // Registered out of order, pending steps are sorted by version
+    const STEPS: &[MigrationStep<Empty>] = &[
+        MigrationStep {
+            version: "0.3.0",
+            name: "pause",
+            run: StepRun::Once(pause),
+        },
+        MigrationStep {
+            version: "0.2.0",
+            name: "config",
+            run: StepRun::Once(config),
+        },
+        MigrationStep {
+            version: "0.2.0",
+            name: "records",
+            run: StepRun::Paged(records),
+        },
+    ];
+
+    fn names(steps: &[&MigrationStep<Empty>]) -> Vec<&'static str> {
+        steps.iter().map(|step| step.name).collect()
+    }
+
+    #[test]
+    fn pending_steps_are_sorted_by_version() {
+        let steps = pending_steps(STEPS, "0.1.5", "0.3.0").unwrap();
+        assert_eq!(names(&steps), vec!["config", "records", "pause"]);
+        // Steps of the current version already ran
+        let steps = pending_steps(STEPS, "0.2.0", "0.3.0").unwrap();
+        assert_eq!(names(&steps), vec!["pause"]);
+        assert!(pending_steps(STEPS, "0.3.0", "0.3.0").unwrap().is_empty());
+        // Steps of later versions wait for the code introducing them
+        let steps = pending_steps(STEPS, "0.1.5", "0.2.0").unwrap();
+        assert_eq!(names(&steps), vec!["config", "records"]);
+    }
+
+    #[test]
+    fn downgrades_are_rejected() {
+        assert_eq!(
+            pending_steps(STEPS, "0.3.0", "0.2.0").err(),
+            Some(MigrationError::Downgrade {
+                from: "0.3.0".to_string(),
+                to: "0.2.0".to_string(),
+            })
+        );
+        assert_eq!(
+            pending_steps(STEPS, "latest", "0.2.0").err(),
+            Some(MigrationError::InvalidVersion {
+                version: "latest".to_string(),
+            })
+        );
+    }
+
+    #[test]
+    fn next_cursor_is_set_for_full_pages() {
+        let page = vec![(1u32, ()), (2u32, ())];
+        assert_eq!(next_cursor(&page, 3).unwrap(), None);
+        assert_eq!(
+            next_cursor(&page, 2).unwrap(),
+            Some(to_json_binary(&2u32).unwrap())
+        );
+        assert_eq!(next_cursor::<u32, ()>(&[], 0).unwrap(), None);
+    }
+
+    #[test]
+    fn paged_steps_resume_from_their_cursor() {
+        let mut deps = mock_dependencies();
+        let env = mock_env();
+        cw2::set_contract_version(&mut deps.storage, NAME, "0.1.5").unwrap();
+        let total = DEFAULT_MIGRATION_LIMIT + 3;
+        for key in 0..total {
+            RECORDS.save(&mut deps.storage, key, &false).unwrap();
+        }
+
+        // The first page is rewritten by migrate, the rest waits
+        let plan = migrate(deps.as_mut(), &env, NAME, "0.3.0", STEPS, &Empty {}).unwrap();
+        assert_eq!(plan.steps, vec!["config", "records", "pause"]);
+        assert_eq!(plan.remaining, vec!["records", "pause"]);
+        assert_eq!(
+            assert_migrated(&deps.storage).unwrap_err(),
+            MigrationError::InProgress {
+                steps: "records,pause".to_string(),
+            }
+        );
+        let migrate_again = migrate(deps.as_mut(), &env, NAME, "0.3.0", STEPS, &Empty {});
+        assert!(matches!(
+            migrate_again.unwrap_err(),
+            MigrationError::InProgress { .. }
+        ));
+
+        // Each call resumes after the last record, the steps after the paged one run at the end
+        let remaining = continue_migration(deps.as_mut(), &env, STEPS, Some(2)).unwrap();
+        assert_eq!(remaining, vec!["records", "pause"]);
+        let rewritten = RECORDS
+            .range(&deps.storage, None, None, Order::Ascending)
+            .filter(|item| item.as_ref().unwrap().1)
+            .count();
+        assert_eq!(rewritten as u32, DEFAULT_MIGRATION_LIMIT + 2);
+        let remaining = continue_migration(deps.as_mut(), &env, STEPS, Some(2)).unwrap();
+        assert!(remaining.is_empty());
+        assert!(RECORDS
+            .range(&deps.storage, None, None, Order::Ascending)
+            .all(|item| item.unwrap().1));
+        assert_eq!(RAN.load(&deps.storage).unwrap(), vec!["config", "pause"]);
+
+        assert_migrated(&deps.storage).unwrap();
+        assert_eq!(
+            continue_migration(deps.as_mut(), &env, STEPS, None).unwrap_err(),
+            MigrationError::NotInProgress {}
+        );
+        assert_eq!(
+            cw2::get_contract_version(&deps.storage).unwrap().version,
+            "0.3.0"
+        );
+    }
+}
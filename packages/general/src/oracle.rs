@@ -0,0 +1,59 @@
+use std::cmp::Ordering;
+
+use chrono::DateTime;
+use cosmwasm_std::{Int128, QuerierWrapper, StdError, StdResult};
+use neutron_sdk::bindings::oracle::query::{GetPriceResponse, OracleQuery};
+use neutron_sdk::bindings::oracle::types::CurrencyPair;
+use neutron_sdk::bindings::query::NeutronQuery;
+
+pub const USD_TICKER: &str = "USD";
+// All prices are normalized to this amount of decimals
+pub const PRICE_DECIMALS: u64 = 18;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct OraclePrice {
+    pub price: Int128,
+    // Unix timestamp (seconds) of the block where the price was last updated
+    pub timestamp: u64,
+}
+
+/// Queries the Skip Connect oracle for the USD price of a ticker, normalized to PRICE_DECIMALS
+pub fn query_usd_price(
+    querier: &QuerierWrapper<NeutronQuery>,
+    ticker: String,
+) -> StdResult<OraclePrice> {
+    let oracle_query = OracleQuery::GetPrice {
+        currency_pair: CurrencyPair {
+            base: ticker,
+            quote: USD_TICKER.to_string(),
+        },
+    };
+    let price_response: GetPriceResponse = querier.query(&oracle_query.into())?;
+    let dt = DateTime::parse_from_rfc3339(&price_response.price.block_timestamp)
+        .map_err(|e| StdError::generic_err(format!("Failed to parse timestamp: {}", e)))?;
+    // Convert to a Unix timestamp (seconds since the Unix epoch)
+    let timestamp = dt.timestamp() as u64;
+
+    Ok(OraclePrice {
+        price: normalize_price(price_response)?,
+        timestamp,
+    })
+}
+
+fn normalize_price(price_response: GetPriceResponse) -> StdResult<Int128> {
+    let price = price_response.price.price;
+    let normalized_price = match price_response.decimals.cmp(&PRICE_DECIMALS) {
+        Ordering::Greater => {
+            let divisor = i128::pow(10, (price_response.decimals - PRICE_DECIMALS) as u32);
+            price
+                .checked_div(Int128::from(divisor))
+                .map_err(|e| StdError::generic_err(e.to_string()))?
+        }
+        Ordering::Less => {
+            let multiplier = i128::pow(10, (PRICE_DECIMALS - price_response.decimals) as u32);
+            price.checked_mul(Int128::from(multiplier))?
+        }
+        Ordering::Equal => price,
+    };
+    Ok(normalized_price)
+}
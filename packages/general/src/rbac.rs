@@ -0,0 +1,304 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, Api, Empty, Order, StdError, StdResult, Storage};
+use cw_ownable::{initialize_owner, is_owner, OwnershipError};
+use cw_storage_plus::{Bound, Map};
+use thiserror::Error;
+
+// Maximum members returned per page by the role_members query
+pub const MAX_MEMBERS_LIMIT: u32 = 100;
+const DEFAULT_MEMBERS_LIMIT: u32 = 30;
+
+/// Roles shared by all contracts. The owner holds every role and is the only one that can grant
+/// and revoke them. It is kept by cw-ownable, so it is transferred in two steps with the
+/// `update_ownership` message
+#[cw_serde]
+#[derive(Copy, Eq)]
+pub enum Role {
+    Owner,
+    // Updates configs, dev wallets, hooks, IBC denoms and registered games
+    ConfigAdmin,
+    // Halts, resumes and pauses games
+    Pauser,
+    // Manages the price tickers and the assets rounds bet on
+    OracleManager,
+    // Creates games, bets and events
+    MarketCreator,
+    // Completes and cancels bets
+    Resolver,
+    // Verifies users
+    Moderator,
+}
+
+impl Role {
+    /// Roles the owner can grant
+    pub const GRANTABLE: [Role; 6] = [
+        Role::ConfigAdmin,
+        Role::Pauser,
+        Role::OracleManager,
+        Role::MarketCreator,
+        Role::Resolver,
+        Role::Moderator,
+    ];
+
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Role::Owner => "owner",
+            Role::ConfigAdmin => "config_admin",
+            Role::Pauser => "pauser",
+            Role::OracleManager => "oracle_manager",
+            Role::MarketCreator => "market_creator",
+            Role::Resolver => "resolver",
+            Role::Moderator => "moderator",
+        }
+    }
+}
+
+impl std::fmt::Display for Role {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[derive(Error, Debug, PartialEq)]
+pub enum RbacError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error(transparent)]
+    Ownership(#[from] OwnershipError),
+
+    #[error("{} doesn't have the {} role", address, role)]
+    MissingRole { address: Addr, role: Role },
+
+    #[error("The owner role is transferred with update_ownership")]
+    OwnerNotGrantable {},
+}
+
+#[cw_serde]
+pub struct RolesResponse {
+    // Every role for the owner
+    pub roles: Vec<Role>,
+}
+
+#[cw_serde]
+pub struct RoleMembersResponse {
+    // Addresses granted the role, the owner is not listed
+    pub members: Vec<Addr>,
+}
+
+// (role, member) -> Empty
+const ROLES: Map<(&str, &Addr), Empty> = Map::new("rbac_roles");
+
+/// Sets the owner and grants every other role to `admins`
+pub fn initialize(
+    storage: &mut dyn Storage,
+    api: &dyn Api,
+    owner: &str,
+    admins: &[Addr],
+) -> StdResult<()> {
+    initialize_owner(storage, api, Some(owner))?;
+    for admin in admins {
+        api.addr_validate(admin.as_str())?;
+        for role in Role::GRANTABLE {
+            ROLES.save(storage, (role.as_str(), admin), &Empty {})?;
+        }
+    }
+
+    Ok(())
+}
+
+pub fn has_role(storage: &dyn Storage, role: Role, address: &Addr) -> StdResult<bool> {
+    if is_owner(storage, address)? {
+        return Ok(true);
+    }
+
+    Ok(role != Role::Owner && ROLES.has(storage, (role.as_str(), address)))
+}
+
+pub fn assert_role(storage: &dyn Storage, role: Role, address: &Addr) -> Result<(), RbacError> {
+    if !has_role(storage, role, address)? {
+        return Err(RbacError::MissingRole {
+            address: address.clone(),
+            role,
+        });
+    }
+
+    Ok(())
+}
+
+/// Grants a role, only the owner can. Returns the validated member
+pub fn grant_role(
+    storage: &mut dyn Storage,
+    api: &dyn Api,
+    sender: &Addr,
+    role: Role,
+    address: &str,
+) -> Result<Addr, RbacError> {
+    assert_role(storage, Role::Owner, sender)?;
+    if role == Role::Owner {
+        return Err(RbacError::OwnerNotGrantable {});
+    }
+
+    let member = api.addr_validate(address)?;
+    ROLES.save(storage, (role.as_str(), &member), &Empty {})?;
+    Ok(member)
+}
+
+/// Revokes a role, only the owner can. Returns the validated member
+pub fn revoke_role(
+    storage: &mut dyn Storage,
+    api: &dyn Api,
+    sender: &Addr,
+    role: Role,
+    address: &str,
+) -> Result<Addr, RbacError> {
+    assert_role(storage, Role::Owner, sender)?;
+    if role == Role::Owner {
+        return Err(RbacError::OwnerNotGrantable {});
+    }
+
+    let member = api.addr_validate(address)?;
+    ROLES.remove(storage, (role.as_str(), &member));
+    Ok(member)
+}
+
+pub fn query_roles(storage: &dyn Storage, address: &Addr) -> StdResult<RolesResponse> {
+    let mut roles = vec![];
+    for role in [Role::Owner].into_iter().chain(Role::GRANTABLE) {
+        if has_role(storage, role, address)? {
+            roles.push(role);
+        }
+    }
+
+    Ok(RolesResponse { roles })
+}
+
+pub fn query_role_members(
+    storage: &dyn Storage,
+    role: Role,
+    start_after: Option<Addr>,
+    limit: Option<u32>,
+) -> StdResult<RoleMembersResponse> {
+    let limit = limit
+        .unwrap_or(DEFAULT_MEMBERS_LIMIT)
+        .min(MAX_MEMBERS_LIMIT) as usize;
+    let members = ROLES
+        .prefix(role.as_str())
+        .keys(
+            storage,
+            start_after.as_ref().map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .take(limit)
+        .collect::<StdResult<Vec<Addr>>>()?;
+
+    Ok(RoleMembersResponse { members })
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::testing::mock_dependencies;
+
+    use super::*;
+
+    #[test]
+    fn owner_grants_and_revokes_roles() {
+        let mut deps = mock_dependencies();
+        let owner = deps.api.addr_make("owner");
+        let admin = deps.api.addr_make("admin");
+        let pauser = deps.api.addr_make("pauser");
+        initialize(
+            &mut deps.storage,
+            &deps.api,
+            owner.as_str(),
+            &[deps.api.addr_make("admin")],
+        )
+        .unwrap();
+
+        // The owner holds every role, admins every role but the owner
+        assert!(has_role(&deps.storage, Role::Owner, &owner).unwrap());
+        assert!(has_role(&deps.storage, Role::Moderator, &owner).unwrap());
+        assert!(has_role(&deps.storage, Role::ConfigAdmin, &admin).unwrap());
+        assert!(!has_role(&deps.storage, Role::Owner, &admin).unwrap());
+
+        // Only the owner grants, and never the owner role
+        assert_eq!(
+            grant_role(
+                &mut deps.storage,
+                &deps.api,
+                &admin,
+                Role::Pauser,
+                pauser.as_str()
+            )
+            .unwrap_err(),
+            RbacError::MissingRole {
+                address: admin.clone(),
+                role: Role::Owner,
+            }
+        );
+        assert_eq!(
+            grant_role(
+                &mut deps.storage,
+                &deps.api,
+                &owner,
+                Role::Owner,
+                pauser.as_str()
+            )
+            .unwrap_err(),
+            RbacError::OwnerNotGrantable {}
+        );
+        grant_role(
+            &mut deps.storage,
+            &deps.api,
+            &owner,
+            Role::Pauser,
+            pauser.as_str(),
+        )
+        .unwrap();
+        assert_role(&deps.storage, Role::Pauser, &pauser).unwrap();
+        assert_eq!(
+            assert_role(&deps.storage, Role::ConfigAdmin, &pauser).unwrap_err(),
+            RbacError::MissingRole {
+                address: pauser.clone(),
+                role: Role::ConfigAdmin,
+            }
+        );
+        assert_eq!(
+            query_roles(&deps.storage, &pauser).unwrap().roles,
+            vec![Role::Pauser]
+        );
+        assert_eq!(
+            query_role_members(&deps.storage, Role::Pauser, None, None)
+                .unwrap()
+                .members
+                .len(),
+            2
+        );
+
+        revoke_role(
+            &mut deps.storage,
+            &deps.api,
+            &owner,
+            Role::Pauser,
+            pauser.as_str(),
+        )
+        .unwrap();
+        assert!(!has_role(&deps.storage, Role::Pauser, &pauser).unwrap());
+        assert!(query_roles(&deps.storage, &pauser)
+            .unwrap()
+            .roles
+            .is_empty());
+        assert_eq!(
+            revoke_role(
+                &mut deps.storage,
+                &deps.api,
+                &owner,
+                Role::Owner,
+                owner.as_str()
+            )
+            .unwrap_err(),
+            RbacError::OwnerNotGrantable {}
+        );
+    }
+}
@@ -0,0 +1,68 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, Decimal, Uint128};
+use cw20::Denom;
+
+use crate::asset::denom_key;
+
+#[cw_serde]
+pub enum AssetInfo {
+    Token { contract_addr: Addr },
+    NativeToken { denom: String },
+}
+
+impl AssetInfo {
+    pub fn to_denom(&self) -> Denom {
+        match self {
+            AssetInfo::Token { contract_addr } => Denom::Cw20(contract_addr.clone()),
+            AssetInfo::NativeToken { denom } => Denom::Native(denom.clone()),
+        }
+    }
+}
+
+/// One hop of a swap route, Astroport router style
+#[cw_serde]
+pub enum SwapOperation {
+    NativeSwap {
+        offer_denom: String,
+        ask_denom: String,
+    },
+    AstroSwap {
+        offer_asset_info: AssetInfo,
+        ask_asset_info: AssetInfo,
+    },
+}
+
+impl SwapOperation {
+    /// Denom of native tokens or address of CW20 contracts offered in this hop
+    pub fn offer_key(&self) -> String {
+        match self {
+            SwapOperation::NativeSwap { offer_denom, .. } => offer_denom.clone(),
+            SwapOperation::AstroSwap {
+                offer_asset_info, ..
+            } => denom_key(&offer_asset_info.to_denom()),
+        }
+    }
+
+    /// Denom of native tokens or address of CW20 contracts received in this hop
+    pub fn ask_key(&self) -> String {
+        match self {
+            SwapOperation::NativeSwap { ask_denom, .. } => ask_denom.clone(),
+            SwapOperation::AstroSwap { ask_asset_info, .. } => {
+                denom_key(&ask_asset_info.to_denom())
+            }
+        }
+    }
+}
+
+/// Message the router contract needs to accept in its ExecuteMsg
+#[cw_serde]
+pub enum RouterExecuteMsg {
+    ExecuteSwapOperations {
+        operations: Vec<SwapOperation>,
+        // The whole swap fails if less than this is received
+        minimum_receive: Option<Uint128>,
+        // Receiver of the output, the sender if not provided
+        to: Option<String>,
+        max_spread: Option<Decimal>,
+    },
+}
@@ -0,0 +1,208 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_schema::serde::{de::DeserializeOwned, Serialize};
+use cosmwasm_std::{Addr, Order, StdError, StdResult, Storage, Timestamp};
+use cw_storage_plus::{Bound, Item, Map};
+use thiserror::Error;
+
+// Maximum changes returned per page by the pending_changes query
+pub const MAX_CHANGES_LIMIT: u32 = 30;
+const DEFAULT_CHANGES_LIMIT: u32 = 10;
+
+/// Sensitive changes are queued for `delay` seconds before anyone can execute them, so users
+/// have time to react. The change type `T` is defined by each contract
+#[cw_serde]
+pub struct PendingChange<T> {
+    pub id: u64,
+    pub change: T,
+    pub proposer: Addr,
+    pub queued_at: Timestamp,
+    pub executable_at: Timestamp,
+}
+
+#[cw_serde]
+pub struct PendingChangesResponse<T> {
+    // Seconds a change waits before it can be executed
+    pub delay: u64,
+    pub changes: Vec<PendingChange<T>>,
+}
+
+#[derive(Error, Debug, PartialEq)]
+pub enum TimelockError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("Pending change {} not found", id)]
+    ChangeNotFound { id: u64 },
+
+    #[error("Pending change {} can't be executed before {}", id, executable_at)]
+    ChangeNotReady { id: u64, executable_at: Timestamp },
+}
+
+const DELAY: Item<u64> = Item::new("timelock_delay");
+const NEXT_CHANGE_ID: Item<u64> = Item::new("timelock_next_id");
+
+// id -> change, stored as the contract's change type
+const fn pending_changes<T>() -> Map<u64, PendingChange<T>> {
+    Map::new("timelock_changes")
+}
+
+/// Seconds a change waits before it can be executed, 0 when it was never set
+pub fn delay(storage: &dyn Storage) -> StdResult<u64> {
+    Ok(DELAY.may_load(storage)?.unwrap_or_default())
+}
+
+pub fn set_delay(storage: &mut dyn Storage, seconds: u64) -> StdResult<()> {
+    DELAY.save(storage, &seconds)
+}
+
+/// Queues a change that can be executed once the current delay has passed
+pub fn queue<T: Serialize + DeserializeOwned>(
+    storage: &mut dyn Storage,
+    proposer: Addr,
+    now: Timestamp,
+    change: T,
+) -> StdResult<PendingChange<T>> {
+    let id = NEXT_CHANGE_ID.may_load(storage)?.unwrap_or_default();
+    NEXT_CHANGE_ID.save(storage, &(id + 1))?;
+
+    let pending = PendingChange {
+        id,
+        change,
+        proposer,
+        queued_at: now,
+        executable_at: now.plus_seconds(delay(storage)?),
+    };
+    pending_changes().save(storage, id, &pending)?;
+    Ok(pending)
+}
+
+/// Removes a change whose delay has passed so it can be applied
+pub fn take_ready<T: Serialize + DeserializeOwned>(
+    storage: &mut dyn Storage,
+    id: u64,
+    now: Timestamp,
+) -> Result<PendingChange<T>, TimelockError> {
+    let pending: PendingChange<T> = pending_changes()
+        .may_load(storage, id)?
+        .ok_or(TimelockError::ChangeNotFound { id })?;
+    if now < pending.executable_at {
+        return Err(TimelockError::ChangeNotReady {
+            id,
+            executable_at: pending.executable_at,
+        });
+    }
+
+    pending_changes::<T>().remove(storage, id);
+    Ok(pending)
+}
+
+/// Removes a change without applying it
+pub fn cancel<T: Serialize + DeserializeOwned>(
+    storage: &mut dyn Storage,
+    id: u64,
+) -> Result<PendingChange<T>, TimelockError> {
+    let pending: PendingChange<T> = pending_changes()
+        .may_load(storage, id)?
+        .ok_or(TimelockError::ChangeNotFound { id })?;
+
+    pending_changes::<T>().remove(storage, id);
+    Ok(pending)
+}
+
+pub fn query_pending_changes<T: Serialize + DeserializeOwned>(
+    storage: &dyn Storage,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<PendingChangesResponse<T>> {
+    let limit = limit
+        .unwrap_or(DEFAULT_CHANGES_LIMIT)
+        .min(MAX_CHANGES_LIMIT) as usize;
+    let changes = pending_changes()
+        .range(
+            storage,
+            start_after.map(Bound::exclusive),
+            None,
+            Order::Ascending,
+        )
+        .take(limit)
+        .map(|item| item.map(|(_, pending)| pending))
+        .collect::<StdResult<Vec<PendingChange<T>>>>()?;
+
+    Ok(PendingChangesResponse {
+        delay: delay(storage)?,
+        changes,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use cosmwasm_std::testing::MockStorage;
+
+    use super::*;
+
+    const DELAY_SECONDS: u64 = 3_600;
+
+    fn now() -> Timestamp {
+        Timestamp::from_seconds(1_000)
+    }
+
+    fn queue_change(storage: &mut MockStorage, change: &str) -> PendingChange<String> {
+        queue(storage, Addr::unchecked("admin"), now(), change.to_string()).unwrap()
+    }
+
+    #[test]
+    fn changes_wait_for_the_delay() {
+        let mut storage = MockStorage::new();
+        assert_eq!(delay(&storage).unwrap(), 0);
+        set_delay(&mut storage, DELAY_SECONDS).unwrap();
+
+        let pending = queue_change(&mut storage, "fee");
+        assert_eq!(pending.id, 0);
+        assert_eq!(pending.executable_at, now().plus_seconds(DELAY_SECONDS));
+
+        let early = now().plus_seconds(DELAY_SECONDS - 1);
+        assert_eq!(
+            take_ready::<String>(&mut storage, 0, early).unwrap_err(),
+            TimelockError::ChangeNotReady {
+                id: 0,
+                executable_at: pending.executable_at,
+            }
+        );
+        let ready = take_ready::<String>(&mut storage, 0, pending.executable_at).unwrap();
+        assert_eq!(ready, pending);
+        // A change is applied once
+        assert_eq!(
+            take_ready::<String>(&mut storage, 0, pending.executable_at).unwrap_err(),
+            TimelockError::ChangeNotFound { id: 0 }
+        );
+    }
+
+    #[test]
+    fn cancelled_changes_are_gone() {
+        let mut storage = MockStorage::new();
+        set_delay(&mut storage, DELAY_SECONDS).unwrap();
+        queue_change(&mut storage, "fee");
+        queue_change(&mut storage, "wallets");
+
+        let cancelled = cancel::<String>(&mut storage, 0).unwrap();
+        assert_eq!(cancelled.change, "fee");
+        assert_eq!(
+            cancel::<String>(&mut storage, 0).unwrap_err(),
+            TimelockError::ChangeNotFound { id: 0 }
+        );
+        let later = now().plus_seconds(DELAY_SECONDS);
+        assert_eq!(
+            take_ready::<String>(&mut storage, 0, later).unwrap_err(),
+            TimelockError::ChangeNotFound { id: 0 }
+        );
+
+        // Ids are never reused
+        assert_eq!(queue_change(&mut storage, "router").id, 2);
+        let resp = query_pending_changes::<String>(&storage, None, None).unwrap();
+        assert_eq!(resp.delay, DELAY_SECONDS);
+        let ids: Vec<u64> = resp.changes.iter().map(|pending| pending.id).collect();
+        assert_eq!(ids, vec![1, 2]);
+        let resp = query_pending_changes::<String>(&storage, Some(1), Some(1)).unwrap();
+        assert_eq!(resp.changes[0].change, "router");
+    }
+}
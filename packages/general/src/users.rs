@@ -1,17 +1,40 @@
 use cosmwasm_schema::{cw_serde, QueryResponses};
 use cosmwasm_std::{Addr, Timestamp};
+use cw_ownable::{cw_ownable_execute, cw_ownable_query};
+
+use crate::migration::MigrationPlanResponse;
+use crate::rbac::{Role, RoleMembersResponse, RolesResponse};
+use crate::timelock::PendingChangesResponse;
 
 #[cw_serde]
 pub struct InstantiateMsg {
     pub config: Config,
+    // Owner of the contract, the instantiator if not set
+    #[serde(default)]
+    pub owner: Option<Addr>,
+    // Granted every role but the owner
     pub extra_admins: Option<Vec<Addr>>,
 }
 
 #[allow(clippy::large_enum_variant)]
+#[cw_ownable_execute]
 #[cw_serde]
 pub enum ExecuteMsg {
+    // Fields not set are kept. Queued as a pending change when the timelock delay is set
     UpdateConfig {
-        config: Config,
+        config: ConfigUpdate,
+    },
+    // Applies a pending change once its delay has passed, anyone can execute it
+    ExecuteChange {
+        id: u64,
+    },
+    // Owner actions to drop a pending change and to set the delay of sensitive changes.
+    // Increases of the delay apply at once, decreases are queued with the current delay
+    CancelChange {
+        id: u64,
+    },
+    UpdateTimelockDelay {
+        seconds: u64,
     },
     AddGame {
         address: Addr,
@@ -37,11 +60,13 @@ pub enum ExecuteMsg {
         experience: u64,
         elo: Option<Elo>,
     },
-    AddAdmin {
-        new_admin: Addr,
+    GrantRole {
+        role: Role,
+        address: String,
     },
-    RemoveAdmin {
-        old_admin: Addr,
+    RevokeRole {
+        role: Role,
+        address: String,
     },
     AddEvent {
         event_name: String,
@@ -82,6 +107,46 @@ pub struct Config {
     pub exp_increase_per_level: u64,
 }
 
+/// Fields of the config to update, the ones not set are kept
+#[cw_serde]
+#[derive(Default)]
+pub struct ConfigUpdate {
+    pub initial_exp_per_level: Option<u64>,
+    pub exp_increase_per_level: Option<u64>,
+}
+
+impl ConfigUpdate {
+    /// Splits the update into the fields that wait for the timelock, the ones changing the levels
+    /// users already reached (the experience curve), and the ones applied at once
+    pub fn split_timelocked(self) -> (ConfigUpdate, ConfigUpdate) {
+        let timelocked = ConfigUpdate {
+            initial_exp_per_level: self.initial_exp_per_level,
+            exp_increase_per_level: self.exp_increase_per_level,
+        };
+        let immediate = ConfigUpdate {
+            initial_exp_per_level: None,
+            exp_increase_per_level: None,
+        };
+        (timelocked, immediate)
+    }
+}
+
+/// Sensitive changes queued by crate::timelock until the delay passes
+#[cw_serde]
+pub enum ConfigChange {
+    Config { config: ConfigUpdate },
+    TimelockDelay { seconds: u64 },
+}
+
+impl ConfigChange {
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            ConfigChange::Config { .. } => "config",
+            ConfigChange::TimelockDelay { .. } => "timelock_delay",
+        }
+    }
+}
+
 #[cw_serde]
 pub struct Elo {
     // Elo to modify
@@ -98,6 +163,7 @@ pub enum SocialMedia {
     Discord(String),
 }
 
+#[cw_ownable_query]
 #[cw_serde]
 #[derive(QueryResponses)]
 pub enum QueryMsg {
@@ -116,8 +182,22 @@ pub enum QueryMsg {
         start_after: Option<Addr>,
         limit: Option<u32>,
     },
-    #[returns(Vec<Addr>)]
-    Admins {},
+    #[returns(PendingChangesResponse<ConfigChange>)]
+    PendingChanges {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    // Migration steps this code runs when migrating from from_version, the stored version if not set
+    #[returns(MigrationPlanResponse)]
+    MigrationPlan { from_version: Option<String> },
+    #[returns(RolesResponse)]
+    Roles { address: Addr },
+    #[returns(RoleMembersResponse)]
+    RoleMembers {
+        role: Role,
+        start_after: Option<Addr>,
+        limit: Option<u32>,
+    },
     #[returns(Vec<EventInfo>)]
     OngoingEvents {
         start_after: Option<String>,
@@ -132,8 +212,17 @@ pub enum QueryMsg {
     Participated { user: Addr, event_name: String },
 }
 
+/// Optional params of the migration steps, see crate::migration
 #[cw_serde]
-pub struct MigrateMsg {}
+#[derive(Default)]
+pub struct MigrateMsg {
+    // Owner when migrating from admins to roles, the first admin if not set
+    #[serde(default)]
+    pub owner: Option<Addr>,
+    // Timelock delay of config changes, in seconds
+    #[serde(default)]
+    pub timelock_delay: Option<u64>,
+}
 
 #[cw_serde]
 pub struct EventInfo {
@@ -0,0 +1,123 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{to_json_binary, Addr, Binary, CosmosMsg, StdResult, Uint128, WasmMsg};
+use cw_utils::Expiration;
+
+use crate::prediction_game::Direction;
+
+// Collection info returned by the CW721 contract_info query
+pub const POSITIONS_NAME: &str = "Velo Prediction Positions";
+pub const POSITIONS_SYMBOL: &str = "VPOS";
+
+/// Position token minted for a bet when position NFTs are enabled
+#[cw_serde]
+pub struct Position {
+    // Current holder, paid when the position is claimed
+    pub owner: Addr,
+    // Player that placed the bet, the bet is stored under this address
+    pub bettor: Addr,
+    pub round_id: Uint128,
+    pub approvals: Vec<Approval>,
+}
+
+#[cw_serde]
+pub enum PositionStatus {
+    // The round is not finished yet
+    Pending,
+    Won,
+    Lost,
+    // Nobody bet on the other side or the price didn't move
+    Refunded,
+}
+
+/// NFT metadata of a position, computed from the round every time it is queried
+#[cw_serde]
+pub struct PositionMetadata {
+    pub round_id: Uint128,
+    pub direction: Direction,
+    pub collateral: String,
+    pub stake: Uint128,
+    // Stake on the other side, for hedged positions
+    pub hedge_stake: Option<Uint128>,
+    pub status: PositionStatus,
+}
+
+// CW721 compatible types, so wallets and marketplaces can handle positions like any other NFT
+
+#[cw_serde]
+pub struct Approval {
+    pub spender: Addr,
+    pub expires: Expiration,
+}
+
+#[cw_serde]
+pub struct OwnerOfResponse {
+    pub owner: String,
+    pub approvals: Vec<Approval>,
+}
+
+#[cw_serde]
+pub struct ApprovalResponse {
+    pub approval: Approval,
+}
+
+#[cw_serde]
+pub struct ApprovalsResponse {
+    pub approvals: Vec<Approval>,
+}
+
+#[cw_serde]
+pub struct OperatorsResponse {
+    pub operators: Vec<Approval>,
+}
+
+#[cw_serde]
+pub struct NumTokensResponse {
+    pub count: u64,
+}
+
+#[cw_serde]
+pub struct ContractInfoResponse {
+    pub name: String,
+    pub symbol: String,
+}
+
+#[cw_serde]
+pub struct NftInfoResponse {
+    pub token_uri: Option<String>,
+    pub extension: PositionMetadata,
+}
+
+#[cw_serde]
+pub struct AllNftInfoResponse {
+    pub access: OwnerOfResponse,
+    pub info: NftInfoResponse,
+}
+
+#[cw_serde]
+pub struct TokensResponse {
+    pub tokens: Vec<String>,
+}
+
+/// Message sent to the receiving contract of send_nft
+#[cw_serde]
+pub struct Cw721ReceiveMsg {
+    pub sender: String,
+    pub token_id: String,
+    pub msg: Binary,
+}
+
+impl Cw721ReceiveMsg {
+    pub fn into_cosmos_msg(self, contract_addr: String) -> StdResult<CosmosMsg> {
+        Ok(WasmMsg::Execute {
+            contract_addr,
+            msg: to_json_binary(&ReceiverExecuteMsg::ReceiveNft(self))?,
+            funds: vec![],
+        }
+        .into())
+    }
+}
+
+#[cw_serde]
+enum ReceiverExecuteMsg {
+    ReceiveNft(Cw721ReceiveMsg),
+}
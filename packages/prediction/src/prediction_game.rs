@@ -1,7 +1,10 @@
 use cosmwasm_schema::cw_serde;
 use cosmwasm_schema::QueryResponses;
 use cosmwasm_std::Int128;
-use cosmwasm_std::{Addr, Decimal, Timestamp, Uint128};
+use cosmwasm_std::{Addr, Decimal, Order, Timestamp, Uint128};
+use cw20::Denom;
+use general::asset::denom_key;
+use general::experience::ExperienceConfig;
 
 #[cw_serde]
 pub enum Direction {
@@ -19,6 +22,15 @@ impl std::fmt::Display for Direction {
     }
 }
 
+impl Direction {
+    pub fn opposite(&self) -> Direction {
+        match self {
+            Direction::Bull => Direction::Bear,
+            Direction::Bear => Direction::Bull,
+        }
+    }
+}
+
 #[cw_serde]
 /**
  * Parameters which are mutable by a governance vote
@@ -26,16 +38,281 @@ impl std::fmt::Display for Direction {
 pub struct Config {
     /* After a round ends this is the duration of the next */
     pub next_round_seconds: Uint128,
-    pub minimum_bet: Uint128,
-    pub gaming_fee: Uint128,
-    //The token we are placing the bet with
-    pub token_denom: String,
+    //Tokens we can bet with, each one with its own pools in every round
+    pub collaterals: Vec<Collateral>,
     //Address of the users contract where this contract will add the XP.
     pub users_contract: Addr,
-    //Rewards for Users
-    pub exp_per_denom_bet: u64,
-    pub exp_per_denom_won: u64,
+    //K-factor used to compute the ELO change of a player on every settled bet
+    #[serde(default)]
+    pub elo_k_factor: u64,
     pub dev_wallet_list: Vec<WalletInfo>,
+    //DEX router used to swap other tokens into a collateral before betting
+    #[serde(default)]
+    pub swap_router: Option<Addr>,
+    //Mint a transferable position NFT for every bet, winnings are paid to its holder
+    #[serde(default)]
+    pub position_nfts: bool,
+    //Extra pool share of stakes placed when the bidding window opens, decreasing linearly to
+    //nothing at open time. 0.5 gives the first bets 1.5x shares, 0 disables it
+    #[serde(default)]
+    pub early_bet_bonus: Decimal,
+    //Players needed in a round to open it, 0 disables it
+    #[serde(default)]
+    pub min_players_to_open: u32,
+    //Seconds added to the bidding window of a round that doesn't meet the minimums to open
+    #[serde(default)]
+    pub bid_extension_seconds: Uint128,
+    //Extensions of a round before it is voided and refunded
+    #[serde(default)]
+    pub max_bid_extensions: u32,
+    //Latest finished rounds kept in full, older ones can be compacted with compact_rounds.
+    //0 disables compaction
+    #[serde(default)]
+    pub round_retention: u64,
+    //Let players bet on both sides of a round, each side is paid as a bet of its own
+    #[serde(default)]
+    pub hedging: bool,
+}
+
+/// Fields of the config to update, the ones not set are kept
+#[cw_serde]
+#[derive(Default)]
+pub struct ConfigUpdate {
+    pub next_round_seconds: Option<Uint128>,
+    pub collaterals: Option<Vec<Collateral>>,
+    pub users_contract: Option<Addr>,
+    pub elo_k_factor: Option<u64>,
+    pub dev_wallet_list: Option<Vec<WalletInfo>>,
+    pub swap_router: Option<Addr>,
+    pub position_nfts: Option<bool>,
+    pub early_bet_bonus: Option<Decimal>,
+    pub min_players_to_open: Option<u32>,
+    pub bid_extension_seconds: Option<Uint128>,
+    pub max_bid_extensions: Option<u32>,
+    pub round_retention: Option<u64>,
+    pub hedging: Option<bool>,
+}
+
+impl ConfigUpdate {
+    /// Splits the update into the fields that wait for the timelock, the ones moving funds or
+    /// trusted with them (fees and vaults of the collaterals, users contract, dev wallets, swap
+    /// router), and the ones applied at once
+    pub fn split_timelocked(self) -> (ConfigUpdate, ConfigUpdate) {
+        let timelocked = ConfigUpdate {
+            collaterals: self.collaterals,
+            users_contract: self.users_contract,
+            dev_wallet_list: self.dev_wallet_list,
+            swap_router: self.swap_router,
+            ..ConfigUpdate::default()
+        };
+        let immediate = ConfigUpdate {
+            collaterals: None,
+            users_contract: None,
+            dev_wallet_list: None,
+            swap_router: None,
+            ..self
+        };
+        (timelocked, immediate)
+    }
+}
+
+impl Config {
+    pub fn collateral(&self, collateral: &str) -> Option<&Collateral> {
+        self.collaterals.iter().find(|c| c.key() == collateral)
+    }
+}
+
+#[cw_serde]
+pub struct Collateral {
+    //The token we are placing the bet with, a native denom or a CW20 contract
+    pub denom: Denom,
+    pub minimum_bet: Uint128,
+    pub gaming_fee: Uint128,
+    //Rewards for Users, calculated from the USD value of each bet and win
+    pub experience: ExperienceConfig,
+    //Bets are taken by a liquidity vault at fixed odds instead of going to a parimutuel pool
+    #[serde(default)]
+    pub fixed_odds: Option<FixedOdds>,
+    //Amount bet on both sides of the pool needed to open a round, 0 disables it
+    #[serde(default)]
+    pub min_pool_to_open: Uint128,
+}
+
+impl Collateral {
+    /// Denom of native tokens, contract address of CW20 tokens
+    pub fn key(&self) -> String {
+        denom_key(&self.denom)
+    }
+
+    /// Empty pool of the collateral for a new round, with the current betting mode
+    pub fn pool(&self) -> CollateralPool {
+        CollateralPool {
+            fixed_odds: self.fixed_odds.clone(),
+            ..CollateralPool::new(self.key())
+        }
+    }
+}
+
+#[cw_serde]
+pub struct FixedOdds {
+    //Liquidity vault taking the other side of the bets, holding the same token as the collateral
+    pub vault: Addr,
+    //Share of the fair 2x payout kept by the house, 0.05 pays 1.9x
+    pub house_edge: Decimal,
+}
+
+impl FixedOdds {
+    /// Gross payout of a winning bet divided by its stake
+    pub fn odds(&self) -> Decimal {
+        Decimal::percent(200) * (Decimal::one() - self.house_edge)
+    }
+}
+
+/// Amounts bet with one collateral in a round. Pools of different collaterals never mix
+#[cw_serde]
+pub struct CollateralPool {
+    pub collateral: String,
+    pub bull_amount: Uint128,
+    pub bear_amount: Uint128,
+    // Fixed odds of the collateral when the round was created, kept for the whole round
+    #[serde(default)]
+    pub fixed_odds: Option<FixedOdds>,
+    // Sponsored prize shared by the winners on top of the pool, set when the round finishes
+    #[serde(default)]
+    pub boost: Uint128,
+    // Stakes of each side weighted by the early bet bonus, unset for rounds without weights
+    #[serde(default)]
+    pub bull_weighted: Option<Uint128>,
+    #[serde(default)]
+    pub bear_weighted: Option<Uint128>,
+    // The round didn't meet the minimums to open, every stake is returned
+    #[serde(default)]
+    pub voided: bool,
+}
+
+impl CollateralPool {
+    pub fn new(collateral: String) -> Self {
+        CollateralPool {
+            collateral,
+            bull_amount: Uint128::zero(),
+            bear_amount: Uint128::zero(),
+            fixed_odds: None,
+            boost: Uint128::zero(),
+            bull_weighted: None,
+            bear_weighted: None,
+            voided: false,
+        }
+    }
+
+    pub fn total(&self) -> Uint128 {
+        self.bull_amount + self.bear_amount
+    }
+
+    /// Total shares of a side, the prize of the pool is split by shares
+    pub fn side_shares(&self, direction: &Direction) -> Uint128 {
+        match direction {
+            Direction::Bull => self.bull_weighted.unwrap_or(self.bull_amount),
+            Direction::Bear => self.bear_weighted.unwrap_or(self.bear_amount),
+        }
+    }
+
+    /// Adds a stake and its shares to a side of the pool
+    pub fn add_stake(&mut self, direction: &Direction, amount: Uint128, shares: Uint128) {
+        let side_shares = self.side_shares(direction) + shares;
+        match direction {
+            Direction::Bull => {
+                self.bull_amount += amount;
+                self.bull_weighted = Some(side_shares);
+            }
+            Direction::Bear => {
+                self.bear_amount += amount;
+                self.bear_weighted = Some(side_shares);
+            }
+        }
+    }
+
+    /// Amount shared by the winners of a parimutuel pool, sponsored boost included
+    pub fn prize(&self) -> Uint128 {
+        self.total() + self.boost
+    }
+
+    pub fn side_amount(&self, direction: &Direction) -> Uint128 {
+        match direction {
+            Direction::Bull => self.bull_amount,
+            Direction::Bear => self.bear_amount,
+        }
+    }
+
+    /// Nobody bet on one of the sides or the round was voided, everybody gets their bet back
+    /// without fees. Sides are never empty with fixed odds, the vault is always on the other side
+    pub fn is_refund(&self) -> bool {
+        self.voided
+            || (self.fixed_odds.is_none()
+                && (self.bull_amount.is_zero() || self.bear_amount.is_zero()))
+    }
+}
+
+/// Pool of `collateral` in a round, empty if nobody bet with it
+pub fn find_pool(pools: &[CollateralPool], collateral: &str) -> CollateralPool {
+    pools
+        .iter()
+        .find(|pool| pool.collateral == collateral)
+        .cloned()
+        .unwrap_or_else(|| CollateralPool::new(collateral.to_string()))
+}
+
+/// Prize escrowed by a sponsor for the winners of a range of rounds
+#[cw_serde]
+pub struct Sponsorship {
+    pub id: u64,
+    pub sponsor: Addr,
+    // Collateral of the prize, only winners of its pool are boosted
+    pub collateral: String,
+    // First and last rounds boosted, both included
+    pub start_round: Uint128,
+    pub end_round: Uint128,
+    // Only rounds betting on this asset are boosted
+    pub denom: Option<String>,
+    // Added to the prize of each round, refunded to the sponsor if the round has no winners
+    pub amount_per_round: Uint128,
+}
+
+/// Operations paused by the admins, each one can be paused on its own
+#[cw_serde]
+#[derive(Default)]
+pub struct PauseState {
+    pub betting: bool,
+    // Rounds are neither opened nor closed, the live round is frozen
+    pub rounds: bool,
+    pub claims: bool,
+    // Why the operations are paused, for players
+    pub reason: Option<String>,
+    // When the admins expect to resume, for players
+    pub resume_at: Option<Timestamp>,
+    // When rounds were paused, the bidding and live rounds are delayed by the pause on resume
+    pub rounds_paused_at: Option<Timestamp>,
+    // Set for good by the emergency exit, betting and rounds can't be resumed
+    #[serde(default)]
+    pub emergency_exit: bool,
+}
+
+/// Sensitive changes queued by general::timelock until the delay passes
+#[allow(clippy::large_enum_variant)]
+#[cw_serde]
+pub enum ConfigChange {
+    Config { config: ConfigUpdate },
+    DevWallets { dev_wallets: Vec<WalletInfo> },
+    TimelockDelay { seconds: u64 },
+}
+
+impl ConfigChange {
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            ConfigChange::Config { .. } => "config",
+            ConfigChange::DevWallets { .. } => "dev_wallets",
+            ConfigChange::TimelockDelay { .. } => "timelock_delay",
+        }
+    }
 }
 
 #[cw_serde]
@@ -44,9 +321,14 @@ pub struct NextRound {
     pub bid_time: Timestamp,
     pub open_time: Timestamp,
     pub close_time: Timestamp,
-    pub bull_amount: Uint128,
-    pub bear_amount: Uint128,
+    pub pools: Vec<CollateralPool>,
     pub denom: String,
+    // Players with a bet in the round
+    #[serde(default)]
+    pub players: u32,
+    // Times the bidding window was extended because the round didn't meet the minimums to open
+    #[serde(default)]
+    pub extensions: u32,
 }
 
 #[cw_serde]
@@ -56,8 +338,7 @@ pub struct LiveRound {
     pub open_time: Timestamp,
     pub close_time: Timestamp,
     pub open_price: Int128,
-    pub bull_amount: Uint128,
-    pub bear_amount: Uint128,
+    pub pools: Vec<CollateralPool>,
     pub denom: String,
 }
 
@@ -70,9 +351,99 @@ pub struct FinishedRound {
     pub open_price: Int128,
     pub close_price: Int128,
     pub winner: Option<Direction>,
+    pub pools: Vec<CollateralPool>,
+    pub denom: String,
+    // Rebuilt from the summary of a compacted round, without boosts, fixed odds and weights
+    #[serde(default)]
+    pub compacted: bool,
+}
+
+/// What is kept of a finished round once it is compacted
+#[cw_serde]
+pub struct RoundSummary {
+    pub id: Uint128,
+    pub bid_time: Timestamp,
+    pub open_time: Timestamp,
+    pub close_time: Timestamp,
+    pub open_price: Int128,
+    pub close_price: Int128,
+    pub winner: Option<Direction>,
+    pub totals: Vec<PoolTotals>,
+    pub denom: String,
+}
+
+#[cw_serde]
+pub struct PoolTotals {
+    pub collateral: String,
     pub bull_amount: Uint128,
     pub bear_amount: Uint128,
-    pub denom: String,
+    pub voided: bool,
+}
+
+impl From<&FinishedRound> for RoundSummary {
+    fn from(round: &FinishedRound) -> Self {
+        RoundSummary {
+            id: round.id,
+            bid_time: round.bid_time,
+            open_time: round.open_time,
+            close_time: round.close_time,
+            open_price: round.open_price,
+            close_price: round.close_price,
+            winner: round.winner.clone(),
+            totals: round
+                .pools
+                .iter()
+                .map(|pool| PoolTotals {
+                    collateral: pool.collateral.clone(),
+                    bull_amount: pool.bull_amount,
+                    bear_amount: pool.bear_amount,
+                    voided: pool.voided,
+                })
+                .collect(),
+            denom: round.denom.clone(),
+        }
+    }
+}
+
+impl From<RoundSummary> for FinishedRound {
+    fn from(summary: RoundSummary) -> Self {
+        FinishedRound {
+            id: summary.id,
+            bid_time: summary.bid_time,
+            open_time: summary.open_time,
+            close_time: summary.close_time,
+            open_price: summary.open_price,
+            close_price: summary.close_price,
+            winner: summary.winner,
+            pools: summary
+                .totals
+                .into_iter()
+                .map(|totals| CollateralPool {
+                    bull_amount: totals.bull_amount,
+                    bear_amount: totals.bear_amount,
+                    voided: totals.voided,
+                    ..CollateralPool::new(totals.collateral)
+                })
+                .collect(),
+            denom: summary.denom,
+            compacted: true,
+        }
+    }
+}
+
+#[cw_serde]
+pub enum OrderBy {
+    Asc,
+    Desc,
+}
+
+impl From<OrderBy> for Order {
+    fn from(order: OrderBy) -> Self {
+        match order {
+            OrderBy::Asc => Order::Ascending,
+            OrderBy::Desc => Order::Descending,
+        }
+    }
 }
 
 #[cw_serde]
@@ -83,9 +454,30 @@ pub struct DenomTicker {
 
 pub mod msg {
     use super::*;
+    use cosmwasm_std::Binary;
+    use cw20::Cw20ReceiveMsg;
+    use cw_ownable::{cw_ownable_execute, cw_ownable_query};
+    use cw_utils::Expiration;
+    use general::migration::MigrationPlanResponse;
+    use general::rbac::{Role, RoleMembersResponse, RolesResponse};
+    use general::router::SwapOperation;
+    use general::timelock::PendingChangesResponse;
 
+    /// Optional params of the migration steps, see general::migration
     #[cw_serde]
-    pub struct MigrateMsg {}
+    #[derive(Default)]
+    pub struct MigrateMsg {
+        // Owner when migrating from admins to roles, the first admin if not set
+        #[serde(default)]
+        pub owner: Option<Addr>,
+        // Timelock delay of sensitive config changes, in seconds
+        #[serde(default)]
+        pub timelock_delay: Option<u64>,
+        // XP of the collateral converted from a config stored before collaterals, which had
+        // XP per token instead of per USD. Required when migrating from such a config
+        #[serde(default)]
+        pub experience: Option<ExperienceConfig>,
+    }
 
     #[cw_serde]
     pub struct InstantiateMsg {
@@ -93,17 +485,41 @@ pub mod msg {
         pub config: Config,
         // What are we betting against
         pub denom_tickers: Vec<DenomTicker>,
-        // Additional admins for the contract
+        // Owner of the contract, the instantiator if not set
+        #[serde(default)]
+        pub owner: Option<Addr>,
+        // Additional admins for the contract, granted every role but the owner
         pub extra_admins: Option<Vec<Addr>>,
     }
 
+    #[cw_ownable_execute]
     #[cw_serde]
     pub enum ExecuteMsg {
         /**
-         * Update part of or all of the mutable config params
+         * Update part of or all of the mutable config params, the fields not set are kept.
+         * Queued as a pending change when the timelock delay is set
          */
         UpdateConfig {
-            config: Config,
+            config: ConfigUpdate,
+        },
+        /**
+         * Applies a pending change once its delay has passed, anyone can execute it
+         */
+        ExecuteChange {
+            id: u64,
+        },
+        /**
+         * Owner action to drop a pending change
+         */
+        CancelChange {
+            id: u64,
+        },
+        /**
+         * Owner action to set the seconds sensitive changes wait before they can be executed.
+         * Increases apply at once, decreases are queued with the current delay
+         */
+        UpdateTimelockDelay {
+            seconds: u64,
         },
         /**
          * Price go up
@@ -127,20 +543,68 @@ pub mod msg {
          */
         CloseRound {},
         /**
-         * Settle winnings for an account
+         * Settle winnings for an account, for all rounds played with this collateral
          */
-        CollectWinnings {},
+        CollectWinnings {
+            collateral: String,
+        },
         CollectionWinningRound {
             round_id: Uint128,
         },
+        /**
+         * Pause betting and rounds, claims stay open. Resume clears every pause
+         */
         Halt {},
         Resume {},
-        AddAdmin {
-            new_admin: Addr,
+        /**
+         * Pause or resume betting, round progression and claims separately
+         */
+        UpdatePause {
+            betting: bool,
+            rounds: bool,
+            claims: bool,
+            reason: Option<String>,
+            resume_at: Option<Timestamp>,
+        },
+        /**
+         * Irreversibly void the bidding and live rounds of a halted game so every player can
+         * withdraw their stake without fees. Betting and rounds stay paused for good
+         */
+        EmergencyExit {},
+        /**
+         * Refund the stakes of up to limit players that didn't claim a voided round. Owner only
+         */
+        EmergencyRefund {
+            round_id: Uint128,
+            limit: Option<u32>,
         },
-        RemoveAdmin {
-            old_admin: Addr,
+        /**
+         * Prunes up to limit claim records and lost bets of the rounds older than the round
+         * retention, and compacts the ones with nothing left to claim into summaries. Resumes
+         * where the previous call stopped. Anyone can call it
+         */
+        CompactRounds {
+            limit: Option<u32>,
         },
+        /**
+         * Rewrites up to limit records of the migration in progress, the other messages are
+         * rejected until it is done. Anyone can call it
+         */
+        ContinueMigration {
+            limit: Option<u32>,
+        },
+        /**
+         * Owner actions to grant and revoke the roles of general::rbac
+         */
+        GrantRole {
+            role: Role,
+            address: String,
+        },
+        RevokeRole {
+            role: Role,
+            address: String,
+        },
+        // Queued as a pending change when the timelock delay is set
         ModifyDevWallet {
             new_dev_wallets: Vec<WalletInfo>,
         },
@@ -151,8 +615,136 @@ pub mod msg {
         ModifyBetArray {
             denoms: Vec<String>,
         },
+        /**
+         * Register a contract that gets notified of bets, settlements and claims
+         */
+        AddHook {
+            address: Addr,
+        },
+        RemoveHook {
+            address: Addr,
+        },
+        /**
+         * Bet with CW20 tokens, the message is a ReceiveMsg
+         */
+        Receive(Cw20ReceiveMsg),
+        /**
+         * Swap the native coin sent into a collateral through the swap router and bet the output
+         */
+        SwapAndBet {
+            round_id: Uint128,
+            direction: Direction,
+            collateral: String,
+            operations: Vec<SwapOperation>,
+            // The bet is rejected if the swap gives less than this
+            minimum_receive: Option<Uint128>,
+            max_spread: Option<Decimal>,
+        },
+        /**
+         * Bet placed from another chain, as the wasm memo of an ICS-20 transfer handled by ibc-hooks.
+         * The player is the address ibc-hooks derives for the original sender and channel
+         */
+        IbcBet {
+            channel: String,
+            original_sender: String,
+            round_id: Uint128,
+            direction: Direction,
+            return_to_origin: bool,
+        },
+        /**
+         * Settle winnings of a player that bet from another chain and asked for them to be sent back.
+         * Anyone can relay it, the winnings always go to the origin chain address
+         */
+        CollectIbcWinnings {
+            player: Addr,
+            collateral: String,
+        },
+        /**
+         * Send again winnings whose transfer back to the origin chain failed or timed out.
+         * Anyone can relay it, like collect_ibc_winnings
+         */
+        RetryIbcPayout {
+            player: Addr,
+            collateral: String,
+        },
+        /**
+         * Allow-list of the native collaterals that can be bet from other chains. The denom
+         * received through IBC must be the denom of a collateral, as the pools and winnings are in
+         * the collateral, no other denom is converted into one
+         */
+        AddIbcDenom {
+            ibc_denom: String,
+        },
+        RemoveIbcDenom {
+            ibc_denom: String,
+        },
+        /**
+         * Escrow a prize for the winners of rounds start_round to end_round, paid with the
+         * funds attached in one of the collaterals. The funds must be amount_per_round for
+         * every round of the range
+         */
+        Sponsor {
+            start_round: Uint128,
+            end_round: Uint128,
+            denom: Option<String>,
+            amount_per_round: Uint128,
+        },
+        /**
+         * Settle a position NFT, paying its holder. The token is burnt
+         */
+        ClaimPosition {
+            token_id: String,
+        },
+        /**
+         * CW721 messages to move position NFTs
+         */
+        TransferNft {
+            recipient: String,
+            token_id: String,
+        },
+        SendNft {
+            contract: String,
+            token_id: String,
+            msg: Binary,
+        },
+        Approve {
+            spender: String,
+            token_id: String,
+            expires: Option<Expiration>,
+        },
+        Revoke {
+            spender: String,
+            token_id: String,
+        },
+        ApproveAll {
+            operator: String,
+            expires: Option<Expiration>,
+        },
+        RevokeAll {
+            operator: String,
+        },
     }
 
+    /**
+     * Bets placed with CW20 tokens, the amount bet is the amount of tokens sent
+     */
+    #[cw_serde]
+    pub enum ReceiveMsg {
+        BetBull {
+            round_id: Uint128,
+        },
+        BetBear {
+            round_id: Uint128,
+        },
+        Sponsor {
+            start_round: Uint128,
+            end_round: Uint128,
+            denom: Option<String>,
+            amount_per_round: Uint128,
+        },
+    }
+
+    #[cw_ownable_query]
     #[cw_serde]
     #[derive(QueryResponses)]
     pub enum QueryMsg {
@@ -160,10 +752,34 @@ pub mod msg {
         Config {},
         #[returns(StatusResponse)]
         Status {},
+        #[returns(PauseState)]
+        PauseStatus {},
+        #[returns(PendingChangesResponse<ConfigChange>)]
+        PendingChanges {
+            start_after: Option<u64>,
+            limit: Option<u32>,
+        },
+        /// Migration steps this code runs when migrating from `from_version`, the stored
+        /// version if not set
+        #[returns(MigrationPlanResponse)]
+        MigrationPlan { from_version: Option<String> },
         #[returns(MyCurrentPositionResponse)]
         MyCurrentPosition { address: String },
         #[returns(RoundResponse)]
         FinishedRound { round_id: Uint128 },
+        #[returns(FinishedRoundsResponse)]
+        FinishedRounds {
+            start_after: Option<Uint128>,
+            limit: Option<u32>,
+            order: Option<OrderBy>,
+        },
+        #[returns(FinishedRoundsResponse)]
+        FinishedRoundsByDenom {
+            denom: String,
+            start_after: Option<Uint128>,
+            limit: Option<u32>,
+            order: Option<OrderBy>,
+        },
         #[returns(MyGameResponse)]
         MyGameList {
             player: Addr,
@@ -171,15 +787,27 @@ pub mod msg {
             limit: Option<u32>,
         },
         #[returns(PendingRewardResponse)]
-        MyPendingReward { player: Addr },
+        MyPendingReward { player: Addr, collateral: String },
+        /// Rounds with a reward left to claim after `start_after`, the total counts every round
         #[returns(PendingRewardRoundsResponse)]
-        MyPendingRewardRounds { player: Addr },
+        MyPendingRewardRounds {
+            player: Addr,
+            collateral: String,
+            start_after: Option<Uint128>,
+            limit: Option<u32>,
+        },
         #[returns(PendingRewardResponse)]
         MyPendingRewardRound { round_id: Uint128, player: Addr },
         #[returns(PendingRefundableAmountResponse)]
-        MyRefundableAmount { player: Addr },
+        MyRefundableAmount { player: Addr, collateral: String },
+        /// Rounds with a refund left to claim after `start_after`, the total counts every round
         #[returns(PendingRefundableAmountRoundsResponse)]
-        MyRefundableAmountRounds { player: Addr },
+        MyRefundableAmountRounds {
+            player: Addr,
+            collateral: String,
+            start_after: Option<Uint128>,
+            limit: Option<u32>,
+        },
         #[returns(RoundUsersResponse)]
         GetUsersPerRound {
             round_id: Uint128,
@@ -199,13 +827,87 @@ pub mod msg {
             limit: Option<u32>,
         },
         #[returns(TotalSpentResponse)]
-        TotalSpent { player: Addr },
-        #[returns(AdminsResponse)]
-        GetAdmins {},
+        TotalSpent { player: Addr, collateral: String },
+        #[returns(RolesResponse)]
+        Roles { address: Addr },
+        #[returns(RoleMembersResponse)]
+        RoleMembers {
+            role: Role,
+            start_after: Option<Addr>,
+            limit: Option<u32>,
+        },
         #[returns(RoundDenomsResponse)]
         GetRoundDenoms {},
         #[returns(TickersResponse)]
         GetTickers {},
+        /// With a player the simulation adds to their existing bet and fails where their bet would
+        #[returns(SimulateBetResponse)]
+        SimulateBet {
+            round_id: Uint128,
+            direction: Direction,
+            collateral: String,
+            amount: Uint128,
+            player: Option<Addr>,
+        },
+        #[returns(general::hooks::HooksResponse)]
+        GetHooks {
+            start_after: Option<Addr>,
+            limit: Option<u32>,
+        },
+        #[returns(Option<general::ibc::IbcOrigin>)]
+        GetIbcOrigin { player: Addr },
+        #[returns(general::ibc::IbcDenomsResponse)]
+        GetIbcDenoms {},
+        #[returns(Uint128)]
+        GetFailedIbcPayout { player: Addr, collateral: String },
+        /**
+         * CW721 queries of position NFTs
+         */
+        #[returns(crate::positions::OwnerOfResponse)]
+        OwnerOf {
+            token_id: String,
+            include_expired: Option<bool>,
+        },
+        #[returns(crate::positions::ApprovalResponse)]
+        Approval {
+            token_id: String,
+            spender: String,
+            include_expired: Option<bool>,
+        },
+        #[returns(crate::positions::ApprovalsResponse)]
+        Approvals {
+            token_id: String,
+            include_expired: Option<bool>,
+        },
+        #[returns(crate::positions::OperatorsResponse)]
+        AllOperators {
+            owner: String,
+            include_expired: Option<bool>,
+            start_after: Option<String>,
+            limit: Option<u32>,
+        },
+        #[returns(crate::positions::NumTokensResponse)]
+        NumTokens {},
+        #[returns(crate::positions::ContractInfoResponse)]
+        ContractInfo {},
+        #[returns(crate::positions::NftInfoResponse)]
+        NftInfo { token_id: String },
+        #[returns(crate::positions::AllNftInfoResponse)]
+        AllNftInfo {
+            token_id: String,
+            include_expired: Option<bool>,
+        },
+        #[returns(crate::positions::TokensResponse)]
+        Tokens {
+            owner: String,
+            start_after: Option<String>,
+            limit: Option<u32>,
+        },
+        #[returns(crate::positions::TokensResponse)]
+        AllTokens {
+            start_after: Option<String>,
+            limit: Option<u32>,
+        },
     }
 }
 
@@ -213,15 +915,29 @@ pub type ConfigResponse = Config;
 
 pub type RoundResponse = FinishedRound;
 
+#[cw_serde]
+pub struct FinishedRoundsResponse {
+    pub rounds: Vec<FinishedRound>,
+}
+
 #[cw_serde]
 pub struct StatusResponse {
     pub bidding_round: Option<NextRound>,
     pub live_round: Option<LiveRound>,
     pub current_time: Timestamp,
+    // Sponsorships with rounds left to settle
+    pub sponsorships: Vec<Sponsorship>,
 }
 
 #[cw_serde]
 pub struct MyCurrentPositionResponse {
+    // One entry per collateral the player has bet with in the live or the next round
+    pub positions: Vec<CollateralPosition>,
+}
+
+#[cw_serde]
+pub struct CollateralPosition {
+    pub collateral: String,
     pub live_bear_amount: Uint128,
     pub live_bull_amount: Uint128,
     pub next_bear_amount: Uint128,
@@ -265,11 +981,6 @@ pub struct PendingRefundableAmountRoundsResponse {
     pub pending_refundable_amount_total: Uint128,
 }
 
-#[cw_serde]
-pub struct AdminsResponse {
-    pub admins: Vec<Addr>,
-}
-
 #[cw_serde]
 pub struct RoundDenomsResponse {
     pub denoms: Vec<String>,
@@ -280,6 +991,23 @@ pub struct TickersResponse {
     pub tickers: Vec<String>,
 }
 
+#[cw_serde]
+pub struct SimulateBetResponse {
+    // Pools of the collateral in the round after placing the bet
+    pub bull_amount: Uint128,
+    pub bear_amount: Uint128,
+    // Pool shares the bet would earn now, higher than the amount with the early bet bonus
+    pub shares: Uint128,
+    // Total amount the player would have on the side, including their existing stake
+    pub total_stake: Uint128,
+    // Gross payout divided by the total stake
+    pub multiplier: Decimal,
+    // What would be claimable if the chosen direction wins, before fees
+    pub gross_payout: Uint128,
+    pub fee: Uint128,
+    pub net_payout: Uint128,
+}
+
 #[cw_serde]
 pub struct WalletInfo {
     pub address: Addr,
@@ -295,6 +1023,7 @@ pub struct TotalSpentResponse {
 pub struct ClaimInfo {
     pub player: Addr,
     pub round_id: Uint128,
+    pub collateral: String,
     pub claimed_amount: Uint128,
 }
 
@@ -305,9 +1034,178 @@ pub type ClaimInfoKey = (u128, Addr);
 pub struct BetInfo {
     pub player: Addr,
     pub round_id: Uint128,
+    pub collateral: String,
+    pub amount: Uint128,
+    pub direction: Direction,
+    // Position NFT of the bet, its holder is paid instead of the player
+    #[serde(default)]
+    pub token_id: Option<String>,
+    // Gross payout if the direction wins, for bets taken by a liquidity vault
+    #[serde(default)]
+    pub fixed_payout: Option<Uint128>,
+    // Amount weighted by the early bet bonus, unset for bets placed before it existed
+    #[serde(default)]
+    pub weighted_amount: Option<Uint128>,
+    // Stake on the side opposite to `direction`, only with hedging
+    #[serde(default)]
+    pub hedge: Option<HedgeStake>,
+}
+
+/// Side of a hedged bet opposite to the direction of the bet
+#[cw_serde]
+pub struct HedgeStake {
     pub amount: Uint128,
+    pub fixed_payout: Option<Uint128>,
+    pub weighted_amount: Option<Uint128>,
+}
+
+impl BetInfo {
+    /// Shares of the bet in the pool of its side
+    pub fn shares(&self) -> Uint128 {
+        self.weighted_amount.unwrap_or(self.amount)
+    }
+
+    /// The bet split into one bet per side it has a stake on, hedged bets have two
+    pub fn sides(&self) -> Vec<BetInfo> {
+        let mut sides = vec![BetInfo {
+            hedge: None,
+            ..self.clone()
+        }];
+        if let Some(hedge) = &self.hedge {
+            sides.push(BetInfo {
+                amount: hedge.amount,
+                direction: self.direction.opposite(),
+                fixed_payout: hedge.fixed_payout,
+                weighted_amount: hedge.weighted_amount,
+                hedge: None,
+                ..self.clone()
+            });
+        }
+        sides
+    }
+
+    /// Adds a stake to the side `direction`, opening the hedge if it is the other side, and
+    /// returns that side
+    pub fn add_stake(
+        &mut self,
+        direction: &Direction,
+        amount: Uint128,
+        shares: Uint128,
+        fixed_payout: Option<Uint128>,
+    ) -> BetInfo {
+        let add_payout = |current: Option<Uint128>| match fixed_payout {
+            Some(payout) => Some(current.unwrap_or_default() + payout),
+            None => current,
+        };
+
+        if *direction == self.direction {
+            self.weighted_amount = Some(self.shares() + shares);
+            self.amount += amount;
+            self.fixed_payout = add_payout(self.fixed_payout);
+        } else {
+            let hedge = self.hedge.get_or_insert(HedgeStake {
+                amount: Uint128::zero(),
+                fixed_payout: None,
+                weighted_amount: None,
+            });
+            hedge.weighted_amount = Some(hedge.weighted_amount.unwrap_or(hedge.amount) + shares);
+            hedge.amount += amount;
+            hedge.fixed_payout = add_payout(hedge.fixed_payout);
+        }
+
+        self.sides()
+            .into_iter()
+            .find(|side| side.direction == *direction)
+            .unwrap_or_else(|| self.clone())
+    }
+}
+
+/// Data set on the response of a bet
+#[cw_serde]
+pub struct BetReceipt {
+    pub round_id: Uint128,
     pub direction: Direction,
+    pub collateral: String,
+    // Amount placed with this bet
+    pub stake: Uint128,
+    // Total amount the player has in this round on the side of the bet
+    pub total_stake: Uint128,
+    // Pools of the collateral in the round after the bet
+    pub bull_amount: Uint128,
+    pub bear_amount: Uint128,
+    // Position NFT of the bet if position NFTs are enabled
+    pub token_id: Option<String>,
+    // Total gross payout if the direction wins, when the bet was taken at fixed odds
+    pub fixed_payout: Option<Uint128>,
+    // Pool shares earned by this bet, higher than the stake with the early bet bonus
+    pub shares: Uint128,
+}
+
+#[cw_serde]
+pub struct RoundPayout {
+    pub round_id: Uint128,
+    pub amount: Uint128,
+}
+
+/// Data set on the response of a claim
+#[cw_serde]
+pub struct ClaimReceipt {
+    pub player: Addr,
+    pub collateral: String,
+    // Gross payout (winnings or refund) of each claimed round
+    pub payouts: Vec<RoundPayout>,
+    pub gross: Uint128,
+    pub fee: Uint128,
+    pub net: Uint128,
+}
+
+/// Data set on the response of a close round
+#[cw_serde]
+pub struct CloseRoundReceipt {
+    // Round that was settled, if any
+    pub finished_round: Option<FinishedRound>,
+    // Round that stopped accepting bets and went live, if any
+    pub live_round: Option<LiveRound>,
+    // New round open for bets, if any
+    pub new_round_id: Option<Uint128>,
+    // Round that didn't meet the minimums to open and was voided, if any
+    #[serde(default)]
+    pub voided_round_id: Option<Uint128>,
 }
 
 /// Primary key for betinfo: (round_id, player)
 pub type BetInfoKey = (u128, Addr);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_fields_trusted_with_funds_are_timelocked() {
+        let update = ConfigUpdate {
+            next_round_seconds: Some(Uint128::new(60)),
+            users_contract: Some(Addr::unchecked("users")),
+            swap_router: Some(Addr::unchecked("router")),
+            hedging: Some(true),
+            ..ConfigUpdate::default()
+        };
+
+        let (timelocked, immediate) = update.split_timelocked();
+        assert_eq!(
+            timelocked,
+            ConfigUpdate {
+                users_contract: Some(Addr::unchecked("users")),
+                swap_router: Some(Addr::unchecked("router")),
+                ..ConfigUpdate::default()
+            }
+        );
+        assert_eq!(
+            immediate,
+            ConfigUpdate {
+                next_round_seconds: Some(Uint128::new(60)),
+                hedging: Some(true),
+                ..ConfigUpdate::default()
+            }
+        );
+    }
+}
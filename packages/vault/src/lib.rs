@@ -0,0 +1 @@
+pub mod liquidity_vault;
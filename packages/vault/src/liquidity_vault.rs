@@ -0,0 +1,154 @@
+use cosmwasm_schema::cw_serde;
+use cosmwasm_std::{Addr, Decimal, Uint128};
+use cw20::Denom;
+use prediction::prediction_game::Direction;
+
+#[cw_serde]
+pub struct Config {
+    //Token deposited by the LPs, must be the collateral of the games using the vault
+    pub denom: Denom,
+    //Max payout of one direction of a round, as a share of the vault assets
+    pub max_round_exposure: Decimal,
+}
+
+/// What the vault owes a game for one of its rounds
+#[cw_serde]
+#[derive(Default)]
+pub struct RoundExposure {
+    // Stakes forwarded by the game, paid back if the round has no winner
+    pub stakes: Uint128,
+    // Paid to the game if the direction wins
+    pub bull_payout: Uint128,
+    pub bear_payout: Uint128,
+}
+
+impl RoundExposure {
+    pub fn side_payout(&self, direction: &Direction) -> Uint128 {
+        match direction {
+            Direction::Bull => self.bull_payout,
+            Direction::Bear => self.bear_payout,
+        }
+    }
+
+    /// Amount reserved until the round is settled, the worst outcome for the vault
+    pub fn locked(&self) -> Uint128 {
+        self.stakes.max(self.bull_payout).max(self.bear_payout)
+    }
+
+    /// Amount paid to the game once the round is settled
+    pub fn payout(&self, winner: Option<&Direction>) -> Uint128 {
+        match winner {
+            Some(direction) => self.side_payout(direction),
+            None => self.stakes,
+        }
+    }
+}
+
+#[cw_serde]
+pub struct StateResponse {
+    // Balance of the vault, including the stakes of unsettled rounds
+    pub assets: Uint128,
+    pub locked: Uint128,
+    pub total_shares: Uint128,
+}
+
+#[cw_serde]
+pub struct SharesResponse {
+    pub shares: Uint128,
+    // Current value of the shares
+    pub amount: Uint128,
+}
+
+#[cw_serde]
+pub struct CapacityResponse {
+    // Max payout the vault accepts on top of the current exposure of the round
+    pub max_payout: Uint128,
+}
+
+#[cw_serde]
+pub struct GamesResponse {
+    pub games: Vec<Addr>,
+}
+
+pub mod msg {
+    use super::*;
+    use cosmwasm_schema::QueryResponses;
+    use cw20::Cw20ReceiveMsg;
+    use cw_ownable::{cw_ownable_execute, cw_ownable_query};
+
+    #[cw_serde]
+    pub struct MigrateMsg {}
+
+    #[cw_serde]
+    pub struct InstantiateMsg {
+        pub owner: Option<String>,
+        pub config: Config,
+        // Games allowed to lock liquidity
+        pub games: Vec<String>,
+    }
+
+    #[cw_ownable_execute]
+    #[cw_serde]
+    pub enum ExecuteMsg {
+        // The denom can't be changed, LPs have deposited it
+        UpdateConfig {
+            max_round_exposure: Decimal,
+        },
+        AddGame {
+            game: String,
+        },
+        RemoveGame {
+            game: String,
+        },
+        // Deposit native tokens and receive shares of the vault
+        Deposit {},
+        // Deposit CW20 tokens
+        Receive(Cw20ReceiveMsg),
+        // Burn shares and get their value back, only from the liquidity that is not locked
+        Withdraw {
+            shares: Uint128,
+        },
+        // Called by a game after forwarding the stake of a fixed odds bet
+        LockPayout {
+            round_id: Uint128,
+            direction: Direction,
+            stake: Uint128,
+            payout: Uint128,
+        },
+        // Called by a game when the round is finished, the payout of the round is sent to it
+        SettleRound {
+            round_id: Uint128,
+            winner: Option<Direction>,
+        },
+    }
+
+    #[cw_serde]
+    pub enum ReceiveMsg {
+        Deposit {},
+    }
+
+    #[cw_ownable_query]
+    #[cw_serde]
+    #[derive(QueryResponses)]
+    pub enum QueryMsg {
+        #[returns(Config)]
+        Config {},
+        #[returns(StateResponse)]
+        State {},
+        #[returns(SharesResponse)]
+        Shares { address: String },
+        #[returns(CapacityResponse)]
+        Capacity {
+            game: String,
+            round_id: Uint128,
+            direction: Direction,
+        },
+        #[returns(RoundExposure)]
+        RoundExposure { game: String, round_id: Uint128 },
+        #[returns(GamesResponse)]
+        Games {
+            start_after: Option<String>,
+            limit: Option<u32>,
+        },
+    }
+}